@@ -23,6 +23,10 @@ use crate::{Result, Timestamp};
 /// Type alias for API keys, which are UUIDs.
 pub type ApiKey = Uuid;
 
+pub mod persistence;
+#[cfg(feature = "clob")]
+pub mod signing;
+
 /// Generic set of credentials used to authenticate to the Polymarket API. These credentials are
 /// returned when calling [`crate::clob::Client::create_or_derive_api_key`], [`crate::clob::Client::derive_api_key`], or
 /// [`crate::clob::Client::create_api_key`]. They are used by the [`state::Authenticated`] client to
@@ -67,6 +71,10 @@ impl Credentials {
 /// Each client can exist in one state at a time, i.e. [`state::Unauthenticated`] or
 /// [`state::Authenticated`].
 pub mod state {
+    use std::sync::{PoisonError, RwLock};
+
+    #[cfg(all(feature = "clob", feature = "ws"))]
+    use crate::auth::ApiKey;
     use crate::auth::{Credentials, Kind};
     use crate::types::Address;
 
@@ -81,22 +89,63 @@ pub mod state {
     ///
     /// See `examples/authenticated.rs` for more context.
     #[non_exhaustive]
-    #[derive(Clone, Debug)]
-    #[cfg_attr(
-        not(feature = "clob"),
-        expect(dead_code, reason = "Fields used by clob module when feature enabled")
-    )]
+    #[derive(Debug)]
     pub struct Authenticated<K: Kind> {
         /// The signer's address that created the credentials
         pub(crate) address: Address,
         /// The [`Credentials`]'s `secret` is used to generate an [`crate::signer::hmac`] which is
-        /// passed in the L2 headers ([`super::HeaderMap`]) `POLY_SIGNATURE` field.
-        pub(crate) credentials: Credentials,
+        /// passed in the L2 headers ([`super::HeaderMap`]) `POLY_SIGNATURE` field. Behind a lock
+        /// so that they can be rotated while the client is shared.
+        pub(crate) credentials: RwLock<Credentials>,
         /// The [`Kind`] that this [`Authenticated`] exhibits. Used to generate additional headers
         /// for different types of authentication, e.g. Builder.
         pub(crate) kind: K,
     }
 
+    impl<K: Kind> Authenticated<K> {
+        pub(crate) fn new(address: Address, credentials: Credentials, kind: K) -> Self {
+            Self {
+                address,
+                credentials: RwLock::new(credentials),
+                kind,
+            }
+        }
+
+        /// Snapshot of the current credentials.
+        pub(crate) fn credentials(&self) -> Credentials {
+            self.credentials
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone()
+        }
+
+        /// Key of the current credentials, without copying their secrets.
+        #[cfg(all(feature = "clob", feature = "ws"))]
+        pub(crate) fn api_key(&self) -> ApiKey {
+            self.credentials
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .key
+        }
+
+        /// Replaces the credentials, returning the previous ones.
+        #[cfg(feature = "clob")]
+        pub(crate) fn replace_credentials(&self, credentials: Credentials) -> Credentials {
+            let mut current = self
+                .credentials
+                .write()
+                .unwrap_or_else(PoisonError::into_inner);
+
+            std::mem::replace(&mut current, credentials)
+        }
+    }
+
+    impl<K: Kind> Clone for Authenticated<K> {
+        fn clone(&self) -> Self {
+            Self::new(self.address, self.credentials(), self.kind.clone())
+        }
+    }
+
     /// The clob state can only be [`Unauthenticated`] or [`Authenticated`].
     pub trait State: sealed::Sealed {}
 
@@ -157,9 +206,10 @@ pub(crate) mod l1 {
     use alloy::hex::ToHexExt as _;
     use alloy::primitives::{ChainId, U256};
     use alloy::signers::Signer;
-    use alloy::sol_types::SolStruct as _;
     use reqwest::header::HeaderMap;
+    use serde::Serialize;
 
+    use crate::auth::signing::typed_data;
     use crate::{Result, Timestamp};
 
     pub(crate) const POLY_ADDRESS: &str = "POLY_ADDRESS";
@@ -169,6 +219,7 @@ pub(crate) mod l1 {
 
     sol! {
         #[non_exhaustive]
+        #[derive(Serialize)]
         struct ClobAuth {
             address address;
             string  timestamp;
@@ -177,8 +228,18 @@ pub(crate) mod l1 {
         }
     }
 
+    /// EIP-712 domain of the [`ClobAuth`] message on `chain_id`.
+    pub(crate) fn domain(chain_id: ChainId) -> Eip712Domain {
+        Eip712Domain {
+            name: Some(Cow::Borrowed("ClobAuthDomain")),
+            version: Some(Cow::Borrowed("1")),
+            chain_id: Some(U256::from(chain_id)),
+            ..Eip712Domain::default()
+        }
+    }
+
     /// Returns the [`HeaderMap`] needed to obtain [`Credentials`] .
-    pub(crate) async fn create_headers<S: Signer>(
+    pub(crate) async fn create_headers<S: Signer + Sync>(
         signer: &S,
         chain_id: ChainId,
         timestamp: Timestamp,
@@ -193,15 +254,9 @@ pub(crate) mod l1 {
             message: "This message attests that I control the given wallet".to_owned(),
         };
 
-        let domain = Eip712Domain {
-            name: Some(Cow::Borrowed("ClobAuthDomain")),
-            version: Some(Cow::Borrowed("1")),
-            chain_id: Some(U256::from(chain_id)),
-            ..Eip712Domain::default()
-        };
-
-        let hash = auth.eip712_signing_hash(&domain);
-        let signature = signer.sign_hash(&hash).await?;
+        let signature = signer
+            .sign_dynamic_typed_data(&typed_data(&auth, domain(chain_id))?)
+            .await?;
 
         let mut map = HeaderMap::new();
         map.insert(
@@ -224,7 +279,7 @@ pub(crate) mod l2 {
     use secrecy::ExposeSecret as _;
 
     use crate::auth::state::Authenticated;
-    use crate::auth::{Kind, hmac, to_message};
+    use crate::auth::{Credentials, Kind, hmac, to_message};
     use crate::{Result, Timestamp};
 
     pub(crate) const POLY_ADDRESS: &str = "POLY_ADDRESS";
@@ -233,13 +288,14 @@ pub(crate) mod l2 {
     pub(crate) const POLY_SIGNATURE: &str = "POLY_SIGNATURE";
     pub(crate) const POLY_TIMESTAMP: &str = "POLY_TIMESTAMP";
 
-    /// Returns the [`Headers`] needed to interact with any authenticated endpoints.
+    /// Returns the [`Headers`] needed to interact with any authenticated endpoints, signed with
+    /// `credentials`.
     pub(crate) async fn create_headers<K: Kind>(
         state: &Authenticated<K>,
+        credentials: &Credentials,
         request: &Request,
         timestamp: Timestamp,
     ) -> Result<HeaderMap> {
-        let credentials = &state.credentials;
         let signature = hmac(&credentials.secret, &to_message(request, timestamp))?;
 
         let mut map = HeaderMap::new();
@@ -248,10 +304,10 @@ pub(crate) mod l2 {
             POLY_ADDRESS,
             state.address.encode_hex_with_prefix().parse()?,
         );
-        map.insert(POLY_API_KEY, state.credentials.key.to_string().parse()?);
+        map.insert(POLY_API_KEY, credentials.key.to_string().parse()?);
         map.insert(
             POLY_PASSPHRASE,
-            state.credentials.passphrase.expose_secret().parse()?,
+            credentials.passphrase.expose_secret().parse()?,
         );
         map.insert(POLY_SIGNATURE, signature.parse()?);
         map.insert(POLY_TIMESTAMP, timestamp.to_string().parse()?);
@@ -266,8 +322,10 @@ pub(crate) mod l2 {
 
 /// Specific structs and methods used in configuring and authenticating the Builder flow
 pub mod builder {
-    use reqwest::header::HeaderMap;
-    use reqwest::{Client, Request};
+    use std::sync::Arc;
+
+    use reqwest::header::{AUTHORIZATION, HeaderMap};
+    use reqwest::{Method, Request};
     use secrecy::ExposeSecret as _;
     use serde::{Deserialize, Serialize};
     use serde_json::json;
@@ -275,6 +333,7 @@ pub mod builder {
     pub use url::Url;
 
     use crate::auth::{Credentials, body_to_string, hmac, to_message};
+    use crate::transport::{self, Transport};
     use crate::{Result, Timestamp};
 
     pub(crate) const POLY_BUILDER_API_KEY: &str = "POLY_BUILDER_API_KEY";
@@ -321,7 +380,8 @@ pub mod builder {
     #[derive(Clone, Debug)]
     pub struct Builder {
         pub(crate) config: Config,
-        pub(crate) client: Client,
+        /// Sends the requests to a [`Config::Remote`] signing server
+        pub(crate) client: Arc<dyn Transport>,
     }
 
     impl Builder {
@@ -354,18 +414,16 @@ pub mod builder {
                         "timestamp": timestamp,
                     });
 
-                    let mut headers = HeaderMap::new();
+                    let mut remote_request = Request::new(Method::POST, host.clone());
+                    *remote_request.body_mut() = Some(serde_json::to_vec(&payload)?.into());
+
+                    let headers = remote_request.headers_mut();
                     if let Some(token) = token {
-                        headers.insert("Authorization", format!("Bearer {token}").parse()?);
+                        headers.insert(AUTHORIZATION, format!("Bearer {token}").parse()?);
                     }
+                    transport::insert_default_headers(headers);
 
-                    let response = self
-                        .client
-                        .post(host.to_string())
-                        .headers(headers)
-                        .json(&payload)
-                        .send()
-                        .await?;
+                    let response = self.client.execute(remote_request).await?;
 
                     let remote_headers: HeaderPayload = response.error_for_status()?.json().await?;
 
@@ -423,6 +481,7 @@ fn hmac(secret: &SecretString, message: &str) -> Result<String> {
 #[cfg(test)]
 mod tests {
     use std::str::FromStr as _;
+    use std::sync::Arc;
 
     #[cfg(feature = "clob")]
     use alloy::signers::local::LocalSigner;
@@ -474,9 +533,9 @@ mod tests {
     async fn l2_headers_should_succeed() -> anyhow::Result<()> {
         let signer = LocalSigner::from_str(PRIVATE_KEY)?;
 
-        let authenticated = Authenticated {
-            address: signer.address(),
-            credentials: Credentials {
+        let authenticated = Authenticated::new(
+            signer.address(),
+            Credentials {
                 key: Uuid::nil(),
                 passphrase: SecretString::from(
                     "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_owned(),
@@ -485,11 +544,12 @@ mod tests {
                     "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_owned(),
                 ),
             },
-            kind: Normal,
-        };
+            Normal,
+        );
 
         let request = Request::new(Method::GET, Url::parse("http://localhost/")?);
-        let headers = l2::create_headers(&authenticated, &request, 1).await?;
+        let headers =
+            l2::create_headers(&authenticated, &authenticated.credentials(), &request, 1).await?;
 
         assert_eq!(
             headers[l2::POLY_ADDRESS],
@@ -524,7 +584,7 @@ mod tests {
 
         let builder = builder::Builder {
             config,
-            client: Client::default(),
+            client: Arc::new(Client::default()),
         };
 
         let headers = builder.create_headers(&request, timestamp).await?;
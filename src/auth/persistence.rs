@@ -0,0 +1,376 @@
+//! Persistence of [`Credentials`] across process restarts.
+//!
+//! Deriving credentials requires the L1 signer and a call to the CLOB. Given a
+//! [`CredentialStore`], [`AuthenticationBuilder`](crate::clob::client::AuthenticationBuilder)
+//! first loads the credentials stored for the signer's address, and only derives new ones, then
+//! saves them, when none are stored or the CLOB rejects the stored ones.
+//!
+//! Built-in stores:
+//!
+//! - [`MemoryStore`] keeps credentials for the lifetime of the process
+//! - [`EnvStore`] reads credentials from environment variables
+//! - `FileStore` keeps credentials in a file encrypted with a passphrase (requires `keystore`)
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use std::str::FromStr as _;
+//!
+//! use polymarket_client_sdk::POLYGON;
+//! use polymarket_client_sdk::auth::persistence::FileStore;
+//! use polymarket_client_sdk::auth::{LocalSigner, Signer as _};
+//! use polymarket_client_sdk::clob::{Client, Config};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let signer = LocalSigner::from_str("0x...")?.with_chain_id(Some(POLYGON));
+//! let store = FileStore::new("credentials.json", std::env::var("CREDENTIALS_PASSPHRASE")?);
+//!
+//! // Derives and saves credentials on the first run, loads them on later runs
+//! let client = Client::new("https://clob.polymarket.com", Config::default())?
+//!     .authentication_builder(&signer)
+//!     .credential_store(store)
+//!     .authenticate()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::env;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+
+use crate::Result;
+use crate::auth::Credentials;
+use crate::error::Error;
+use crate::types::Address;
+
+/// Environment variable read by [`EnvStore`] for the API key.
+pub const API_KEY_VAR: &str = "POLYMARKET_API_KEY";
+/// Environment variable read by [`EnvStore`] for the API secret.
+pub const API_SECRET_VAR: &str = "POLYMARKET_API_SECRET";
+/// Environment variable read by [`EnvStore`] for the API passphrase.
+pub const API_PASSPHRASE_VAR: &str = "POLYMARKET_API_PASSPHRASE";
+/// Optional environment variable read by [`EnvStore`] for the address the credentials belong to.
+pub const API_ADDRESS_VAR: &str = "POLYMARKET_API_ADDRESS";
+
+/// Loads and saves the [`Credentials`] of a signer's address.
+///
+/// Stores shared through an [`Arc`] are stores too, so several clients can use the same one.
+#[async_trait]
+pub trait CredentialStore: Send + Sync {
+    /// Credentials stored for `address`, if any.
+    async fn load(&self, address: Address) -> Result<Option<Credentials>>;
+
+    /// Stores `credentials` for `address`, replacing any stored before.
+    async fn save(&self, address: Address, credentials: &Credentials) -> Result<()>;
+}
+
+#[async_trait]
+impl<S: CredentialStore + ?Sized> CredentialStore for Arc<S> {
+    async fn load(&self, address: Address) -> Result<Option<Credentials>> {
+        (**self).load(address).await
+    }
+
+    async fn save(&self, address: Address, credentials: &Credentials) -> Result<()> {
+        (**self).save(address, credentials).await
+    }
+}
+
+/// Keeps credentials in memory, so clients re-authenticated by the same process reuse them.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    credentials: DashMap<Address, Credentials>,
+}
+
+impl MemoryStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CredentialStore for MemoryStore {
+    async fn load(&self, address: Address) -> Result<Option<Credentials>> {
+        Ok(self
+            .credentials
+            .get(&address)
+            .map(|credentials| credentials.clone()))
+    }
+
+    async fn save(&self, address: Address, credentials: &Credentials) -> Result<()> {
+        self.credentials.insert(address, credentials.clone());
+        Ok(())
+    }
+}
+
+/// Reads the credentials of any address from [`API_KEY_VAR`], [`API_SECRET_VAR`] and
+/// [`API_PASSPHRASE_VAR`].
+///
+/// The variables hold a single set of credentials, which is returned for whichever address is
+/// loaded unless [`API_ADDRESS_VAR`] is set, in which case loading them for another address fails.
+/// Set it when the environment may be shared by several signers.
+///
+/// The environment is read-only: saving does nothing, so credentials are derived on every start
+/// until the variables are set.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EnvStore;
+
+#[async_trait]
+impl CredentialStore for EnvStore {
+    async fn load(&self, address: Address) -> Result<Option<Credentials>> {
+        if let Ok(expected) = env::var(API_ADDRESS_VAR) {
+            let expected: Address = expected
+                .parse()
+                .map_err(|e| Error::validation(format!("Invalid {API_ADDRESS_VAR}: {e}")))?;
+            if expected != address {
+                return Err(Error::validation(format!(
+                    "Credentials in the environment belong to {expected}, not {address}"
+                )));
+            }
+        }
+
+        let (Ok(key), Ok(secret), Ok(passphrase)) = (
+            env::var(API_KEY_VAR),
+            env::var(API_SECRET_VAR),
+            env::var(API_PASSPHRASE_VAR),
+        ) else {
+            return Ok(None);
+        };
+
+        let key = key
+            .parse()
+            .map_err(|e| Error::validation(format!("Invalid {API_KEY_VAR}: {e}")))?;
+
+        Ok(Some(Credentials::new(key, secret, passphrase)))
+    }
+
+    async fn save(&self, _address: Address, _credentials: &Credentials) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "keystore")]
+pub use file::FileStore;
+
+#[cfg(feature = "keystore")]
+mod file {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io::ErrorKind as IoErrorKind;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex, PoisonError};
+
+    use async_trait::async_trait;
+    use base64::Engine as _;
+    use base64::engine::general_purpose::STANDARD;
+    use chacha20poly1305::aead::{Aead as _, KeyInit as _};
+    use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+    use rand::RngExt as _;
+    use secrecy::{ExposeSecret as _, SecretString};
+    use serde::{Deserialize, Serialize};
+    use sha2::Sha256;
+
+    use super::CredentialStore;
+    use crate::Result;
+    use crate::auth::{ApiKey, Credentials};
+    use crate::error::{Error, Kind as ErrorKind};
+    use crate::types::Address;
+
+    const VERSION: u8 = 1;
+    /// PBKDF2-HMAC-SHA256 iterations recommended by OWASP.
+    const DEFAULT_ITERATIONS: u32 = 600_000;
+    /// Most iterations a file may ask for, so a tampered file cannot stall loading for hours.
+    const MAX_ITERATIONS: u32 = 10_000_000;
+
+    /// Encrypted content of the file, with what is needed to decrypt it.
+    #[derive(Deserialize, Serialize)]
+    struct Envelope {
+        version: u8,
+        iterations: u32,
+        salt: String,
+        nonce: String,
+        ciphertext: String,
+    }
+
+    /// Keeps the credentials of any number of addresses in a JSON file, encrypted with
+    /// XChaCha20-Poly1305 under a key derived from a passphrase with PBKDF2-HMAC-SHA256.
+    ///
+    /// The file is rewritten with a fresh salt and nonce on every save, by writing a uniquely named
+    /// sibling file and renaming it over the original. Saves through the same store, or its clones,
+    /// run one at a time, so concurrent saves for different addresses all end up in the file.
+    /// Files are read, written and decrypted on the blocking thread pool of the runtime.
+    #[derive(Clone, Debug)]
+    pub struct FileStore {
+        path: PathBuf,
+        passphrase: SecretString,
+        iterations: u32,
+        /// Held while a save reads, updates and rewrites the file
+        saving: Arc<Mutex<()>>,
+    }
+
+    impl FileStore {
+        /// Creates a store keeping credentials at `path`, encrypted with `passphrase`. The file is
+        /// created on the first save.
+        #[must_use]
+        pub fn new<P: Into<PathBuf>, S: Into<String>>(path: P, passphrase: S) -> Self {
+            Self {
+                path: path.into(),
+                passphrase: SecretString::from(passphrase.into()),
+                iterations: DEFAULT_ITERATIONS,
+                saving: Arc::default(),
+            }
+        }
+
+        /// Sets the PBKDF2 iterations used when saving. Defaults to 600,000, and saving fails above
+        /// 10,000,000. Loading uses the iterations recorded in the file, within the same limit.
+        #[must_use]
+        pub fn iterations(mut self, iterations: u32) -> Self {
+            self.iterations = iterations;
+            self
+        }
+
+        fn cipher(&self, salt: &[u8], iterations: u32) -> Result<XChaCha20Poly1305> {
+            if iterations > MAX_ITERATIONS {
+                return Err(Error::validation(format!(
+                    "{iterations} PBKDF2 iterations exceed the limit of {MAX_ITERATIONS}"
+                )));
+            }
+
+            let mut key = [0_u8; 32];
+            pbkdf2::pbkdf2_hmac::<Sha256>(
+                self.passphrase.expose_secret().as_bytes(),
+                salt,
+                iterations,
+                &mut key,
+            );
+
+            Ok(XChaCha20Poly1305::new(&key.into()))
+        }
+
+        fn read(&self) -> Result<HashMap<Address, StoredCredentials>> {
+            let contents = match fs::read(&self.path) {
+                Ok(contents) => contents,
+                Err(e) if e.kind() == IoErrorKind::NotFound => return Ok(HashMap::new()),
+                Err(e) => return Err(Error::with_source(ErrorKind::Internal, e)),
+            };
+
+            let envelope: Envelope = serde_json::from_slice(&contents)?;
+            if envelope.version != VERSION {
+                return Err(Error::validation(format!(
+                    "Unsupported credential file version {}, expected {VERSION}",
+                    envelope.version
+                )));
+            }
+
+            let salt = STANDARD.decode(envelope.salt)?;
+            let nonce = STANDARD.decode(envelope.nonce)?;
+            let ciphertext = STANDARD.decode(envelope.ciphertext)?;
+            let nonce = XNonce::from_exact_iter(nonce)
+                .ok_or(Error::validation("Invalid credential file nonce"))?;
+
+            let plaintext = self
+                .cipher(&salt, envelope.iterations)?
+                .decrypt(&nonce, ciphertext.as_slice())
+                .map_err(|_e| {
+                    Error::validation(
+                        "Unable to decrypt credential file, the passphrase may be wrong",
+                    )
+                })?;
+
+            Ok(serde_json::from_slice(&plaintext)?)
+        }
+
+        fn write(&self, credentials: &HashMap<Address, StoredCredentials>) -> Result<()> {
+            let salt: [u8; 16] = rand::rng().random();
+            let nonce: [u8; 24] = rand::rng().random();
+
+            let plaintext = serde_json::to_vec(credentials)?;
+            let ciphertext = self
+                .cipher(&salt, self.iterations)?
+                .encrypt(&nonce.into(), plaintext.as_slice())
+                .map_err(|_e| Error::validation("Unable to encrypt credential file"))?;
+
+            let envelope = Envelope {
+                version: VERSION,
+                iterations: self.iterations,
+                salt: STANDARD.encode(salt),
+                nonce: STANDARD.encode(nonce),
+                ciphertext: STANDARD.encode(ciphertext),
+            };
+
+            let suffix: u64 = rand::rng().random();
+            let mut temporary = self.path.clone().into_os_string();
+            temporary.push(format!(".{suffix:016x}.tmp"));
+            let result = fs::write(&temporary, serde_json::to_vec_pretty(&envelope)?)
+                .and_then(|()| fs::rename(&temporary, &self.path));
+            if result.is_err() {
+                _ = fs::remove_file(&temporary);
+            }
+
+            result.map_err(|e| Error::with_source(ErrorKind::Internal, e))
+        }
+    }
+
+    /// Credentials with their secrets exposed, for stores that serialize them.
+    #[derive(Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct StoredCredentials {
+        api_key: ApiKey,
+        secret: String,
+        passphrase: String,
+    }
+
+    impl From<&Credentials> for StoredCredentials {
+        fn from(credentials: &Credentials) -> Self {
+            Self {
+                api_key: credentials.key,
+                secret: credentials.secret.expose_secret().to_owned(),
+                passphrase: credentials.passphrase.expose_secret().to_owned(),
+            }
+        }
+    }
+
+    impl From<StoredCredentials> for Credentials {
+        fn from(stored: StoredCredentials) -> Self {
+            Self::new(stored.api_key, stored.secret, stored.passphrase)
+        }
+    }
+
+    #[async_trait]
+    impl CredentialStore for FileStore {
+        async fn load(&self, address: Address) -> Result<Option<Credentials>> {
+            let store = self.clone();
+
+            blocking(move || Ok(store.read()?.remove(&address).map(Credentials::from))).await
+        }
+
+        async fn save(&self, address: Address, credentials: &Credentials) -> Result<()> {
+            let store = self.clone();
+            let credentials = StoredCredentials::from(credentials);
+
+            blocking(move || {
+                let _saving = store.saving.lock().unwrap_or_else(PoisonError::into_inner);
+                let mut stored = store.read()?;
+                stored.insert(address, credentials);
+
+                store.write(&stored)
+            })
+            .await
+        }
+    }
+
+    /// Runs `f`, which does file I/O and key derivation, without blocking the runtime.
+    async fn blocking<T, F>(f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        tokio::task::spawn_blocking(f)
+            .await
+            .map_err(|e| Error::with_source(ErrorKind::Internal, e))?
+    }
+}
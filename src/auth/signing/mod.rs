@@ -0,0 +1,290 @@
+//! Signing through a remote signing service, such as one fronting a KMS or HSM.
+//!
+//! [`RemoteSigner`] implements [`Signer`] by sending every EIP-712 message to be signed to a
+//! service over HTTP, so the private key never has to be loaded by the process using the SDK. The
+//! L1 authentication message and order signatures are requested as typed data, so a
+//! [`RemoteSigner`] can be passed anywhere a [`LocalSigner`](super::LocalSigner) is, e.g. to
+//! [`Client::authentication_builder`](crate::clob::Client::authentication_builder) and
+//! [`Client::sign`](crate::clob::Client::sign). It refuses to sign bare hashes or messages.
+//!
+//! # Protocol
+//!
+//! The service exposes two JSON endpoints relative to its base URL. Every request carries the
+//! signer's token as an `Authorization: Bearer <token>` header, like the
+//! [remote builder](super::builder::Config::remote) flow.
+//!
+//! | Endpoint | Request | Response |
+//! |----------|---------|----------|
+//! | `GET address` | | `{"address": "0x…"}` |
+//! | `POST sign` | `{"address": "0x…", "typedData": {…}}` | `{"signature": "0x…"}` |
+//!
+//! - `address` is the address whose key signs, as `0x`-prefixed hex
+//! - `typedData` is the [EIP-712](https://eips.ethereum.org/EIPS/eip-712) payload as accepted by
+//!   `eth_signTypedData_v4`: `types`, `primaryType`, `domain` and `message`. The SDK only requests
+//!   signatures of `ClobAuth` messages on the `ClobAuthDomain` and of `Order`s on the domains of
+//!   the exchange contracts, so services can refuse anything else
+//! - `signature` is the 65-byte `r || s || v` signature of the EIP-712 hash of `typedData`, as
+//!   `0x`-prefixed hex
+//! - Any non-2xx status is a refusal to sign, and is surfaced as an error
+//!
+//! Every returned signature is checked to recover to the signer's address before it is used.
+//!
+//! A reference implementation of the service wrapping any [`Signer`] is available in
+//! `service` (requires `signing-service`).
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use polymarket_client_sdk::POLYGON;
+//! use polymarket_client_sdk::auth::Signer as _;
+//! use polymarket_client_sdk::auth::signing::RemoteSigner;
+//! use polymarket_client_sdk::clob::{Client, Config};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let token = std::env::var("SIGNING_SERVICE_TOKEN")?;
+//! let signer = RemoteSigner::connect("https://signer.internal", token)
+//!     .await?
+//!     .with_chain_id(Some(POLYGON));
+//!
+//! let client = Client::new("https://clob.polymarket.com", Config::default())?
+//!     .authentication_builder(&signer)
+//!     .authenticate()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "signing-service")]
+pub mod service;
+
+use std::fmt;
+use std::str::FromStr as _;
+use std::sync::Arc;
+
+use alloy::dyn_abi::Eip712Domain;
+use alloy::dyn_abi::eip712::{Resolver, TypedData};
+use alloy::primitives::{ChainId, Signature};
+use alloy::signers::UnsupportedSignerOperation;
+use alloy::sol_types::SolStruct;
+use async_trait::async_trait;
+use reqwest::header::AUTHORIZATION;
+use reqwest::{Client, Method, Request};
+use secrecy::{ExposeSecret as _, SecretString};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::Result;
+use crate::auth::Signer;
+use crate::error::{Error, Kind};
+use crate::timeout::{self, DEFAULT_CONNECT_TIMEOUT, DEFAULT_REQUEST_TIMEOUT};
+use crate::transport::{self, Transport};
+use crate::types::{Address, B256};
+
+/// Body of a `POST sign` request.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SignRequest {
+    pub(crate) address: Address,
+    pub(crate) typed_data: TypedData,
+}
+
+/// Body of a `POST sign` response.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct SignResponse {
+    pub(crate) signature: String,
+}
+
+/// Body of a `GET address` response.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct AddressResponse {
+    pub(crate) address: Address,
+}
+
+/// The EIP-712 payload of `message` on `domain`, as passed to
+/// [`Signer::sign_dynamic_typed_data`] so that remote signers can inspect what they sign.
+pub(crate) fn typed_data<T: SolStruct + Serialize>(
+    message: &T,
+    domain: Eip712Domain,
+) -> Result<TypedData> {
+    let mut resolver = Resolver::from_struct::<T>();
+    resolver
+        .ingest_string(domain.encode_type())
+        .map_err(|e| Error::with_source(Kind::Internal, e))?;
+
+    Ok(TypedData {
+        domain,
+        resolver,
+        primary_type: T::NAME.to_owned(),
+        message: serde_json::to_value(message)?,
+    })
+}
+
+/// A [`Signer`] whose key is held by a remote signing service. See the [module docs](self) for
+/// the protocol it speaks.
+///
+/// Requests are bounded by [`DEFAULT_REQUEST_TIMEOUT`], or the timeout set with
+/// [`timeout::within`].
+#[derive(Clone)]
+pub struct RemoteSigner {
+    host: Url,
+    token: SecretString,
+    address: Address,
+    chain_id: Option<ChainId>,
+    client: Arc<dyn Transport>,
+}
+
+impl fmt::Debug for RemoteSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteSigner")
+            .field("host", &self.host.as_str())
+            .field("address", &self.address)
+            .field("chain_id", &self.chain_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RemoteSigner {
+    /// Creates a signer for `address`, whose key is held by the service at `host`, without
+    /// contacting the service. `token` authenticates the signer to the service.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `host` is not a valid URL.
+    pub fn new<T: Into<String>>(host: &str, token: T, address: Address) -> Result<Self> {
+        let mut host = Url::parse(host)?;
+        // Endpoints are joined onto the host, which would replace a last segment without a slash
+        if !host.path().ends_with('/') {
+            host.set_path(&format!("{}/", host.path()));
+        }
+
+        let client = Client::builder()
+            .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            host,
+            token: SecretString::from(token.into()),
+            address,
+            chain_id: None,
+            client: Arc::new(client),
+        })
+    }
+
+    /// Creates a signer for the address the service at `host` signs for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `host` is not a valid URL, or the service cannot be reached or refuses
+    /// the request.
+    pub async fn connect<T: Into<String>>(host: &str, token: T) -> Result<Self> {
+        let mut signer = Self::new(host, token, Address::ZERO)?;
+        signer.address = signer.remote_address().await?;
+
+        Ok(signer)
+    }
+
+    /// Sends the requests to the signing service through `transport` instead of the default
+    /// [`reqwest::Client`], e.g. to configure mutual TLS.
+    #[must_use]
+    pub fn with_transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.client = Arc::new(transport);
+        self
+    }
+
+    /// Base URL of the signing service.
+    #[must_use]
+    pub fn host(&self) -> &Url {
+        &self.host
+    }
+
+    /// Fetches the address the signing service signs for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the service cannot be reached or refuses the request.
+    pub async fn remote_address(&self) -> Result<Address> {
+        let request = self.request(Method::GET, "address")?;
+        let response = self.client.execute(request).await?;
+        let body: AddressResponse = response.error_for_status()?.json().await?;
+
+        Ok(body.address)
+    }
+
+    async fn sign_remotely(&self, typed_data: &TypedData) -> Result<Signature> {
+        let hash: B256 = typed_data
+            .eip712_signing_hash()
+            .map_err(|e| Error::with_source(Kind::Internal, e))?;
+        let payload = SignRequest {
+            address: self.address,
+            typed_data: typed_data.clone(),
+        };
+
+        let mut request = self.request(Method::POST, "sign")?;
+        *request.body_mut() = Some(serde_json::to_vec(&payload)?.into());
+
+        let response = self.client.execute(request).await?;
+        let body: SignResponse = response.error_for_status()?.json().await?;
+
+        let signature = Signature::from_str(&body.signature).map_err(|e| {
+            Error::validation(format!(
+                "Signing service returned an invalid signature: {e}"
+            ))
+        })?;
+        let recovered = signature
+            .recover_address_from_prehash(&hash)
+            .map_err(|e| Error::with_source(Kind::Internal, e))?;
+        if recovered != self.address {
+            return Err(Error::validation(format!(
+                "Signing service returned a signature by {recovered}, not by {}",
+                self.address
+            )));
+        }
+
+        Ok(signature)
+    }
+
+    fn request(&self, method: Method, endpoint: &str) -> Result<Request> {
+        let mut request = Request::new(method, self.host.join(endpoint)?);
+        *request.timeout_mut() = Some(timeout::scoped().unwrap_or(DEFAULT_REQUEST_TIMEOUT));
+
+        let headers = request.headers_mut();
+        headers.insert(
+            AUTHORIZATION,
+            format!("Bearer {}", self.token.expose_secret()).parse()?,
+        );
+        transport::insert_default_headers(headers);
+
+        Ok(request)
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    /// Always fails: the service only signs typed data it can inspect.
+    async fn sign_hash(&self, _hash: &B256) -> alloy::signers::Result<Signature> {
+        Err(alloy::signers::Error::UnsupportedOperation(
+            UnsupportedSignerOperation::SignHash,
+        ))
+    }
+
+    async fn sign_dynamic_typed_data(
+        &self,
+        payload: &TypedData,
+    ) -> alloy::signers::Result<Signature> {
+        self.sign_remotely(payload)
+            .await
+            .map_err(alloy::signers::Error::other)
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        self.chain_id = chain_id;
+    }
+}
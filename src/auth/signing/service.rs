@@ -0,0 +1,324 @@
+//! Reference implementation of the [remote signing protocol](super).
+//!
+//! **Feature flag:** `signing-service`
+//!
+//! [`SigningServer`] serves the protocol over HTTP/1 for any [`Signer`], e.g. a KMS-backed signer
+//! running in an isolated process, so trading processes only ever hold a [`RemoteSigner`]. It
+//! rejects requests that do not carry its token, and only signs `ClobAuth` messages and `Order`s
+//! for the wrapped signer's address, on the signer's chain and the domains the SDK signs them on.
+//! Deployments needing further policy, such as rate limits or audit logs, can use it as a
+//! starting point.
+//!
+//! The server speaks plain HTTP; terminate TLS in front of it when it is reachable from other
+//! hosts.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use std::str::FromStr as _;
+//!
+//! use polymarket_client_sdk::POLYGON;
+//! use polymarket_client_sdk::auth::{LocalSigner, Signer as _};
+//! use polymarket_client_sdk::auth::signing::RemoteSigner;
+//! use polymarket_client_sdk::auth::signing::service::SigningServer;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let signer = LocalSigner::from_str("0x...")?.with_chain_id(Some(POLYGON));
+//! let token = std::env::var("SIGNING_SERVICE_TOKEN")?;
+//! let server = SigningServer::bind("127.0.0.1:8080", signer, token.clone()).await?;
+//!
+//! let remote = RemoteSigner::connect(&server.host(), token)
+//!     .await?
+//!     .with_chain_id(Some(POLYGON));
+//! assert_eq!(remote.address(), server.address());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`RemoteSigner`]: super::RemoteSigner
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use alloy::dyn_abi::TypedData;
+use alloy::primitives::ChainId;
+use alloy::sol_types::SolStruct as _;
+use http_body_util::{BodyExt as _, Full, LengthLimitError, Limited};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde_json::{Value, json};
+use sha2::{Digest as _, Sha256};
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tokio::task::JoinHandle;
+
+use super::{AddressResponse, SignRequest, SignResponse};
+use crate::auth::Signer;
+use crate::auth::l1::{self, ClobAuth};
+use crate::clob::types::{Order, order_domain};
+use crate::contract_config;
+use crate::error::{Error, Kind};
+use crate::types::Address;
+
+/// Largest request body the server reads, well above the size of any signing request.
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+/// A JSON response body with its status.
+type Reply = (StatusCode, Value);
+
+/// Serves the remote signing protocol for a [`Signer`] on a local socket.
+///
+/// The server runs on the current Tokio runtime until it is dropped.
+#[derive(Debug)]
+pub struct SigningServer {
+    address: Address,
+    local_addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl SigningServer {
+    /// Starts serving `signer` on `addr`. Use port `0` to pick a free port.
+    ///
+    /// Only requests carrying `token` as a bearer token are served, and only messages on the
+    /// signer's chain are signed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `token` is empty, `signer` has no chain ID, or `addr` cannot be bound.
+    pub async fn bind<A, S, T>(addr: A, signer: S, token: T) -> crate::Result<Self>
+    where
+        A: ToSocketAddrs,
+        S: Signer + Send + Sync + 'static,
+        T: Into<String>,
+    {
+        let token = token.into();
+        if token.is_empty() {
+            return Err(Error::validation(
+                "The signing service requires a non-empty token",
+            ));
+        }
+        let chain_id = signer.chain_id().ok_or(Error::validation(
+            "The signing service requires a signer with a chain ID",
+        ))?;
+
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| Error::with_source(Kind::Internal, e))?;
+        let local_addr = listener
+            .local_addr()
+            .map_err(|e| Error::with_source(Kind::Internal, e))?;
+
+        let address = signer.address();
+        let service = Arc::new(Service {
+            signer,
+            chain_id,
+            // Comparing digests keeps the comparison time independent of the token
+            token: Sha256::digest(token).into(),
+        });
+
+        Ok(Self {
+            address,
+            local_addr,
+            task: tokio::spawn(serve(listener, service)),
+        })
+    }
+
+    /// Base URL of the service, to pass to [`RemoteSigner::connect`](super::RemoteSigner::connect).
+    #[must_use]
+    pub fn host(&self) -> String {
+        format!("http://{}", self.local_addr)
+    }
+
+    /// Socket the server listens on.
+    #[must_use]
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Address of the wrapped signer.
+    #[must_use]
+    pub fn address(&self) -> Address {
+        self.address
+    }
+}
+
+impl Drop for SigningServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+struct Service<S> {
+    signer: S,
+    chain_id: ChainId,
+    /// SHA-256 digest of the bearer token requests must carry.
+    token: [u8; 32],
+}
+
+/// Accepts HTTP/1 connections on `listener` until the task is aborted or the listener fails.
+async fn serve<S: Signer + Send + Sync + 'static>(listener: TcpListener, service: Arc<Service<S>>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        let service = Arc::clone(&service);
+
+        tokio::spawn(async move {
+            let handler = service_fn(move |request| handle(Arc::clone(&service), request));
+            // Clients hanging up mid-request are not actionable by the server
+            _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), handler)
+                .await;
+        });
+    }
+}
+
+async fn handle<S: Signer + Send + Sync>(
+    service: Arc<Service<S>>,
+    request: Request<Incoming>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let (parts, body) = request.into_parts();
+
+    let (status, body) = match Limited::new(body, MAX_BODY_SIZE).collect().await {
+        Ok(body) => route(
+            &service,
+            &parts.method,
+            parts.uri.path(),
+            &parts.headers,
+            &body.to_bytes(),
+        )
+        .await
+        .unwrap_or_else(|reply| reply),
+        Err(e) if e.is::<LengthLimitError>() => {
+            error(StatusCode::PAYLOAD_TOO_LARGE, "Request body too large")
+        }
+        Err(e) => error(StatusCode::BAD_REQUEST, format!("Invalid request: {e}")),
+    };
+
+    Ok(Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Full::new(Bytes::from(body.to_string())))
+        .unwrap_or_default())
+}
+
+async fn route<S: Signer + Send + Sync>(
+    service: &Service<S>,
+    method: &Method,
+    path: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<Reply, Reply> {
+    authorize(service, headers)?;
+
+    let address = service.signer.address();
+    match (method, path.trim_end_matches('/')) {
+        (&Method::GET, "/address") => Ok((StatusCode::OK, json!(AddressResponse { address }))),
+        (&Method::POST, "/sign") => {
+            let request: SignRequest = serde_json::from_slice(body)
+                .map_err(|e| error(StatusCode::BAD_REQUEST, format!("Invalid request: {e}")))?;
+
+            if request.address != address {
+                return Err(error(
+                    StatusCode::FORBIDDEN,
+                    format!("Cannot sign for {}, only for {address}", request.address),
+                ));
+            }
+            check(&request.typed_data, address, service.chain_id)?;
+
+            let hash = request
+                .typed_data
+                .eip712_signing_hash()
+                .map_err(|e| error(StatusCode::BAD_REQUEST, format!("Invalid request: {e}")))?;
+            let signature = service
+                .signer
+                .sign_hash(&hash)
+                .await
+                .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+            Ok((
+                StatusCode::OK,
+                json!(SignResponse {
+                    signature: signature.to_string(),
+                }),
+            ))
+        }
+        _ => Err(error(StatusCode::NOT_FOUND, "Not found")),
+    }
+}
+
+/// Refuses anything but a `ClobAuth` message or an `Order` by `address`, on the domain the SDK
+/// signs it on for `chain_id`.
+fn check(typed_data: &TypedData, address: Address, chain_id: ChainId) -> Result<(), Reply> {
+    let (encode_type, domains, signer_field) = match typed_data.primary_type.as_str() {
+        "ClobAuth" => (
+            ClobAuth::eip712_encode_type(),
+            vec![l1::domain(chain_id)],
+            "address",
+        ),
+        "Order" => (
+            Order::eip712_encode_type(),
+            [false, true]
+                .into_iter()
+                .filter_map(|neg_risk| contract_config(chain_id, neg_risk))
+                .map(|config| order_domain(chain_id, config.exchange))
+                .collect(),
+            "signer",
+        ),
+        other => {
+            return Err(error(
+                StatusCode::FORBIDDEN,
+                format!("Cannot sign {other} messages, only ClobAuth and Order"),
+            ));
+        }
+    };
+
+    if typed_data.encode_type().ok().as_deref() != Some(encode_type.as_ref()) {
+        return Err(error(
+            StatusCode::FORBIDDEN,
+            format!("Unexpected {} type", typed_data.primary_type),
+        ));
+    }
+    if !domains.contains(&typed_data.domain) {
+        return Err(error(
+            StatusCode::FORBIDDEN,
+            format!(
+                "Cannot sign {} messages on this domain",
+                typed_data.primary_type
+            ),
+        ));
+    }
+
+    let signer = typed_data
+        .message
+        .get(signer_field)
+        .and_then(|value| serde_json::from_value::<Address>(value.clone()).ok());
+    if signer != Some(address) {
+        return Err(error(
+            StatusCode::FORBIDDEN,
+            format!("Can only sign messages by {address}"),
+        ));
+    }
+
+    Ok(())
+}
+
+fn authorize<S>(service: &Service<S>, headers: &HeaderMap) -> Result<(), Reply> {
+    let expected = &service.token;
+    let presented = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| <[u8; 32]>::from(Sha256::digest(token)));
+
+    if presented.as_ref() == Some(expected) {
+        Ok(())
+    } else {
+        Err(error(StatusCode::UNAUTHORIZED, "Unauthorized"))
+    }
+}
+
+fn error<M: Into<String>>(status: StatusCode, message: M) -> Reply {
+    (status, json!({ "error": message.into() }))
+}
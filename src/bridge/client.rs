@@ -1,14 +1,17 @@
-use reqwest::{
-    Client as ReqwestClient, Method,
-    header::{HeaderMap, HeaderValue},
-};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::Method;
 use url::Url;
 
 use super::types::{
     DepositRequest, DepositResponse, QuoteRequest, QuoteResponse, StatusRequest, StatusResponse,
     SupportedAssetsResponse, WithdrawRequest, WithdrawResponse,
 };
-use crate::Result;
+use crate::retry::RetryPolicy;
+use crate::timeout::DEFAULT_REQUEST_TIMEOUT;
+use crate::transport::Transport;
+use crate::{HttpClient, Result};
 
 /// Client for the Polymarket Bridge API.
 ///
@@ -38,7 +41,7 @@ use crate::Result;
 #[derive(Clone, Debug)]
 pub struct Client {
     host: Url,
-    client: ReqwestClient,
+    client: HttpClient,
 }
 
 impl Default for Client {
@@ -55,13 +58,7 @@ impl Client {
     ///
     /// Returns an error if the host URL is invalid or the HTTP client fails to build.
     pub fn new(host: &str) -> Result<Client> {
-        let mut headers = HeaderMap::new();
-
-        headers.insert("User-Agent", HeaderValue::from_static("rs_clob_client"));
-        headers.insert("Accept", HeaderValue::from_static("*/*"));
-        headers.insert("Connection", HeaderValue::from_static("keep-alive"));
-        headers.insert("Content-Type", HeaderValue::from_static("application/json"));
-        let client = ReqwestClient::builder().default_headers(headers).build()?;
+        let client = HttpClient::new(RetryPolicy::default(), DEFAULT_REQUEST_TIMEOUT)?;
 
         Ok(Self {
             host: Url::parse(host)?,
@@ -69,6 +66,39 @@ impl Client {
         })
     }
 
+    /// Sets the [`RetryPolicy`] applied to every request made by this client.
+    ///
+    /// The default policy makes a single attempt.
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.client.set_retry_policy(retry_policy);
+        self
+    }
+
+    /// Sets the timeout of every request made by this client, covering the connection, the
+    /// request and reading the response. A request that times out fails with an error of kind
+    /// [`Kind::Timeout`](crate::error::Kind::Timeout).
+    ///
+    /// Defaults to [`DEFAULT_REQUEST_TIMEOUT`]. See [`timeout`](crate::timeout) to override it
+    /// for a single call.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client.set_timeout(timeout);
+        self
+    }
+
+    /// Sets the [`Transport`] every request made by this client is sent through, e.g. a
+    /// [`reqwest::Client`] configured with a proxy or timeouts, or a
+    /// [`Stack`](crate::transport::Stack) of middleware.
+    ///
+    /// Defaults to a [`reqwest::Client`] that gives up connecting after
+    /// [`DEFAULT_CONNECT_TIMEOUT`](crate::timeout::DEFAULT_CONNECT_TIMEOUT).
+    #[must_use]
+    pub fn with_transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.client.set_transport(Arc::new(transport));
+        self
+    }
+
     /// Returns the host URL for the client.
     #[must_use]
     pub fn host(&self) -> &Url {
@@ -76,7 +106,7 @@ impl Client {
     }
 
     #[must_use]
-    fn client(&self) -> &ReqwestClient {
+    fn client(&self) -> &HttpClient {
         &self.client
     }
 
@@ -0,0 +1,389 @@
+//! Order book impact of an aggressive order.
+//!
+//! [`simulate`] walks an [`OrderBookSummaryResponse`] the same way the market order builder
+//! ([`Client::market_order`](crate::clob::Client::market_order)) does when it prices an order:
+//!
+//! - a buy for an [`Amount::usdc`] spends up to that much USDC on the asks,
+//! - a buy for an [`Amount::shares`] takes up to that many shares from the asks,
+//! - a sell for an [`Amount::shares`] gives up to that many shares to the bids,
+//!
+//! best price first, optionally ignoring every level beyond a price limit. The resulting
+//! [`Impact`] describes the fill the order would get if the book did not change before it
+//! reached the exchange.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use polymarket_client_sdk::clob::analytics::impact::{ProposedOrder, simulate};
+//! use polymarket_client_sdk::clob::types::response::{
+//!     FeeRateResponse, OrderBookSummaryResponse, OrderSummary,
+//! };
+//! use polymarket_client_sdk::clob::types::{Amount, Side, TickSize};
+//! use polymarket_client_sdk::types::{B256, U256, dec};
+//!
+//! # fn main() -> polymarket_client_sdk::Result<()> {
+//! // Levels are listed the way the API returns them, best price last
+//! let book = OrderBookSummaryResponse::builder()
+//!     .market(B256::ZERO)
+//!     .asset_id(U256::from(1))
+//!     .timestamp(chrono::Utc::now())
+//!     .asks(vec![
+//!         OrderSummary::builder().price(dec!(0.52)).size(dec!(100)).build(),
+//!         OrderSummary::builder().price(dec!(0.50)).size(dec!(100)).build(),
+//!     ])
+//!     .min_order_size(dec!(5))
+//!     .neg_risk(false)
+//!     .tick_size(TickSize::Hundredth)
+//!     .build();
+//!
+//! let order = ProposedOrder::builder()
+//!     .side(Side::Buy)
+//!     .amount(Amount::shares(dec!(150))?)
+//!     .build();
+//! let fee_rate = FeeRateResponse::builder().base_fee(0).build();
+//!
+//! let impact = simulate(&book, &order, &fee_rate)?;
+//!
+//! assert_eq!(impact.filled_size, dec!(150));
+//! assert_eq!(impact.vwap, Some(dec!(76) / dec!(150)));
+//! assert_eq!(impact.worst_price, Some(dec!(0.52)));
+//! assert!(impact.is_complete());
+//! # Ok(())
+//! # }
+//! ```
+
+use bon::Builder;
+
+use crate::Result;
+use crate::clob::types::response::{FeeRateResponse, OrderBookSummaryResponse, OrderSummary};
+use crate::clob::types::{Amount, AmountInner, Side};
+use crate::error::Error;
+use crate::types::Decimal;
+
+/// Basis points in one unit.
+const BPS: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+
+/// An aggressive order to simulate against a book.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Builder)]
+pub struct ProposedOrder {
+    pub side: Side,
+    /// How much to buy or sell. Sells must be given in shares.
+    pub amount: Amount,
+    /// Highest price a buy may pay or lowest price a sell may accept. Levels beyond it are not
+    /// taken.
+    pub price_limit: Option<Decimal>,
+}
+
+/// What a [`ProposedOrder`] would do to a book.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Impact {
+    /// Shares bought or sold
+    pub filled_size: Decimal,
+    /// USDC paid for the shares bought, or received for the shares sold, before fees
+    pub filled_notional: Decimal,
+    /// Volume weighted average price of the fill, if anything was filled
+    pub vwap: Option<Decimal>,
+    /// Price of the first level taken, if anything was filled
+    pub best_price: Option<Decimal>,
+    /// Price of the last level taken, if anything was filled. This is the price the market order
+    /// builder gives the order.
+    pub worst_price: Option<Decimal>,
+    /// Part of the amount left unfilled, in the amount's unit: USDC for [`Amount::usdc`], shares
+    /// for [`Amount::shares`]
+    pub remaining: Decimal,
+    /// Fee charged on the fill, in the asset received: shares for buys, USDC for sells
+    pub fee: Decimal,
+    /// Number of price levels taken from, including a partially taken one
+    pub levels: usize,
+}
+
+impl Impact {
+    /// Whether the whole amount would be filled.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.remaining.is_zero()
+    }
+
+    /// Difference between the average and the best price of the fill, in the order's
+    /// disfavor: positive when buying above or selling below the best price.
+    #[must_use]
+    pub fn slippage(&self, side: Side) -> Option<Decimal> {
+        let (vwap, best) = (self.vwap?, self.best_price?);
+
+        match side {
+            Side::Buy => Some(vwap - best),
+            _ => Some(best - vwap),
+        }
+    }
+}
+
+/// Simulates `order` against `book`, charging fees at `fee_rate`.
+///
+/// # Errors
+///
+/// Returns an error if `order` is a sell with an amount in USDC, which the market order builder
+/// rejects too, or has an unknown side.
+pub fn simulate(
+    book: &OrderBookSummaryResponse,
+    order: &ProposedOrder,
+    fee_rate: &FeeRateResponse,
+) -> Result<Impact> {
+    // The API lists both sides of the book best price last
+    let levels = match (order.side, order.amount.0) {
+        (Side::Buy, _) => &book.asks,
+        (Side::Sell, AmountInner::Shares(_)) => &book.bids,
+        (Side::Sell, AmountInner::Usdc(_)) => {
+            return Err(Error::validation(
+                "Sell Orders must specify their `amount`s in shares",
+            ));
+        }
+        (side, _) => return Err(Error::validation(format!("Invalid side: {side}"))),
+    };
+
+    let levels = levels
+        .iter()
+        .rev()
+        .take_while(|level| within_limit(order.side, level.price, order.price_limit));
+    let fill = walk(levels, order.amount.0);
+    let fee = fill.fee(order.side, fee_rate.base_fee);
+
+    Ok(Impact {
+        filled_size: fill.size,
+        filled_notional: fill.notional,
+        vwap: (!fill.size.is_zero()).then(|| fill.notional / fill.size),
+        best_price: fill.best_price,
+        worst_price: fill.worst_price,
+        remaining: fill.remaining,
+        fee,
+        levels: fill.levels.len(),
+    })
+}
+
+/// Whether `price` respects the optional price `limit` of a `side` order.
+pub(crate) fn within_limit(side: Side, price: Decimal, limit: Option<Decimal>) -> bool {
+    match (side, limit) {
+        (Side::Buy, Some(limit)) => price <= limit,
+        (Side::Sell, Some(limit)) => price >= limit,
+        _ => true,
+    }
+}
+
+/// Result of taking liquidity from consecutive levels.
+#[derive(Debug, Default)]
+pub(crate) struct Fill {
+    pub(crate) size: Decimal,
+    pub(crate) notional: Decimal,
+    pub(crate) remaining: Decimal,
+    pub(crate) best_price: Option<Decimal>,
+    pub(crate) worst_price: Option<Decimal>,
+    /// Price and shares taken at each level
+    levels: Vec<(Decimal, Decimal)>,
+}
+
+impl Fill {
+    /// Polymarket charges `rate * min(price, 1 - price)` per share, in USDC when selling and in
+    /// shares, divided by the price, when buying.
+    fn fee(&self, side: Side, base_fee_bps: u32) -> Decimal {
+        let rate = Decimal::from(base_fee_bps) / BPS;
+
+        self.levels
+            .iter()
+            .map(|&(price, size)| {
+                let fee = rate * price.min(Decimal::ONE - price) * size;
+                match side {
+                    Side::Buy if !price.is_zero() => fee / price,
+                    _ => fee,
+                }
+            })
+            .sum()
+    }
+}
+
+/// Takes `amount` from `levels`, best price first, until it is filled or the levels run out.
+pub(crate) fn walk<'level, I>(levels: I, amount: AmountInner) -> Fill
+where
+    I: IntoIterator<Item = &'level OrderSummary>,
+{
+    let mut fill = Fill {
+        remaining: amount.as_inner(),
+        ..Fill::default()
+    };
+
+    for level in levels {
+        if fill.remaining.is_zero() {
+            break;
+        }
+        if level.size <= Decimal::ZERO {
+            continue;
+        }
+
+        let available = match amount {
+            AmountInner::Usdc(_) => level.size * level.price,
+            AmountInner::Shares(_) => level.size,
+        };
+        // Only divide for the last, partially taken level, so rounding never leaves a remainder
+        let (size, taken) = match amount {
+            _ if available <= fill.remaining => (level.size, available),
+            AmountInner::Usdc(_) => (fill.remaining / level.price, fill.remaining),
+            AmountInner::Shares(_) => (fill.remaining, fill.remaining),
+        };
+        let notional = size * level.price;
+
+        fill.remaining -= taken;
+        fill.size += size;
+        fill.notional += notional;
+        fill.best_price.get_or_insert(level.price);
+        fill.worst_price = Some(level.price);
+        fill.levels.push((level.price, size));
+    }
+
+    fill
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+    use crate::clob::types::TickSize;
+    use crate::types::{B256, U256, dec};
+
+    fn level(price: Decimal, size: Decimal) -> OrderSummary {
+        OrderSummary::builder().price(price).size(size).build()
+    }
+
+    /// Bids at 0.40 and 0.30, asks at 0.50 and 0.60, 100 shares each, listed best price last.
+    fn book() -> OrderBookSummaryResponse {
+        OrderBookSummaryResponse::builder()
+            .market(B256::ZERO)
+            .asset_id(U256::from(1))
+            .timestamp(DateTime::UNIX_EPOCH)
+            .bids(vec![
+                level(dec!(0.30), dec!(100)),
+                level(dec!(0.40), dec!(100)),
+            ])
+            .asks(vec![
+                level(dec!(0.60), dec!(100)),
+                level(dec!(0.50), dec!(100)),
+            ])
+            .min_order_size(dec!(5))
+            .neg_risk(false)
+            .tick_size(TickSize::Hundredth)
+            .build()
+    }
+
+    fn fee_rate(base_fee: u32) -> FeeRateResponse {
+        FeeRateResponse::builder().base_fee(base_fee).build()
+    }
+
+    #[test]
+    fn usdc_buy_should_spend_amount_across_levels() -> Result<()> {
+        let order = ProposedOrder::builder()
+            .side(Side::Buy)
+            .amount(Amount::usdc(dec!(80))?)
+            .build();
+
+        let impact = simulate(&book(), &order, &fee_rate(0))?;
+
+        assert_eq!(impact.filled_notional, dec!(80));
+        assert_eq!(impact.filled_size, dec!(150));
+        assert_eq!(impact.best_price, Some(dec!(0.50)));
+        assert_eq!(impact.worst_price, Some(dec!(0.60)));
+        assert_eq!(impact.levels, 2);
+        assert!(impact.is_complete());
+        assert_eq!(impact.fee, Decimal::ZERO);
+
+        Ok(())
+    }
+
+    #[test]
+    fn share_sell_should_report_remaining_when_book_runs_out() -> Result<()> {
+        let order = ProposedOrder::builder()
+            .side(Side::Sell)
+            .amount(Amount::shares(dec!(250))?)
+            .build();
+
+        let impact = simulate(&book(), &order, &fee_rate(0))?;
+
+        assert_eq!(impact.filled_size, dec!(200));
+        assert_eq!(impact.filled_notional, dec!(70));
+        assert_eq!(impact.vwap, Some(dec!(0.35)));
+        assert_eq!(impact.remaining, dec!(50));
+        assert_eq!(impact.slippage(Side::Sell), Some(dec!(0.05)));
+        assert!(!impact.is_complete());
+
+        Ok(())
+    }
+
+    #[test]
+    fn price_limit_should_stop_the_walk() -> Result<()> {
+        let order = ProposedOrder::builder()
+            .side(Side::Buy)
+            .amount(Amount::shares(dec!(150))?)
+            .price_limit(dec!(0.55))
+            .build();
+
+        let impact = simulate(&book(), &order, &fee_rate(0))?;
+
+        assert_eq!(impact.filled_size, dec!(100));
+        assert_eq!(impact.worst_price, Some(dec!(0.50)));
+        assert_eq!(impact.remaining, dec!(50));
+
+        Ok(())
+    }
+
+    #[test]
+    fn fees_should_be_charged_in_the_asset_received() -> Result<()> {
+        let buy = ProposedOrder::builder()
+            .side(Side::Buy)
+            .amount(Amount::shares(dec!(100))?)
+            .build();
+        let sell = ProposedOrder::builder()
+            .side(Side::Sell)
+            .amount(Amount::shares(dec!(100))?)
+            .build();
+
+        // 2% of min(0.5, 0.5) per share, paid in shares at 0.5 each
+        let impact = simulate(&book(), &buy, &fee_rate(200))?;
+        assert_eq!(impact.fee, dec!(2));
+
+        // 2% of min(0.4, 0.6) per share, paid in USDC
+        let impact = simulate(&book(), &sell, &fee_rate(200))?;
+        assert_eq!(impact.fee, dec!(0.8));
+
+        Ok(())
+    }
+
+    #[test]
+    fn partial_usdc_level_should_leave_nothing_remaining() -> Result<()> {
+        let mut book = book();
+        book.asks = vec![level(dec!(0.30), dec!(1000))];
+        let order = ProposedOrder::builder()
+            .side(Side::Buy)
+            .amount(Amount::usdc(dec!(100))?)
+            .build();
+
+        let impact = simulate(&book, &order, &fee_rate(0))?;
+
+        assert!(impact.is_complete());
+        assert_eq!(impact.filled_size.round_dp(6), dec!(333.333333));
+
+        Ok(())
+    }
+
+    #[test]
+    fn usdc_sell_should_fail() -> Result<()> {
+        let order = ProposedOrder::builder()
+            .side(Side::Sell)
+            .amount(Amount::usdc(dec!(10))?)
+            .build();
+
+        let err = simulate(&book(), &order, &fee_rate(0)).unwrap_err();
+
+        assert!(err.to_string().contains("in shares"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+//! Pure calculations over market data, run locally without any network access.
+//!
+//! - [`impact`]: what an aggressive order would do to an order book before it is sent.
+
+pub mod impact;
@@ -1,21 +1,18 @@
-use std::borrow::Cow;
 use std::marker::PhantomData;
 use std::mem;
 use std::sync::Arc;
-#[cfg(feature = "heartbeats")]
 use std::time::Duration;
 
-use alloy::dyn_abi::Eip712Domain;
 use alloy::primitives::U256;
 use alloy::signers::Signer;
-use alloy::sol_types::SolStruct as _;
 use async_stream::try_stream;
 use bon::Builder;
-use chrono::{NaiveDate, Utc};
+use chrono::{NaiveDate, TimeDelta, Utc};
 use dashmap::DashMap;
 use futures::Stream;
-use reqwest::header::{HeaderMap, HeaderValue};
-use reqwest::{Client as ReqwestClient, Method, Request};
+use futures::future::try_join_all;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Request};
 use serde_json::json;
 #[cfg(all(feature = "tracing", feature = "heartbeats"))]
 use tracing::{debug, error};
@@ -25,9 +22,17 @@ use uuid::Uuid;
 use {tokio::sync::oneshot::Receiver, tokio::time, tokio_util::sync::CancellationToken};
 
 use crate::auth::builder::{Builder, Config as BuilderConfig};
+use crate::auth::persistence::CredentialStore;
+use crate::auth::signing::typed_data;
 use crate::auth::state::{Authenticated, State, Unauthenticated};
 use crate::auth::{Credentials, Kind, Normal};
-use crate::clob::order_builder::{Limit, Market, OrderBuilder, generate_seed};
+use crate::clob::clock::{Clock, ClockSync};
+use crate::clob::order_batch::OrderBatchBuilder;
+use crate::clob::order_builder::{Limit, Market, OrderBuilder, RandomSalt, SaltGenerator};
+use crate::clob::rate_limit::{EndpointGroup, RateLimitStats, RateLimiter, RateLimits};
+use crate::clob::risk::{self, RiskCheck};
+#[cfg(feature = "ctf")]
+use crate::clob::types::Order;
 use crate::clob::types::request::{
     BalanceAllowanceRequest, CancelMarketOrderRequest, DeleteNotificationsRequest,
     LastTradePriceRequest, MidpointRequest, OrderBookSummaryRequest, OrdersRequest,
@@ -52,22 +57,30 @@ use crate::clob::types::{
     CreateRfqRequestRequest, CreateRfqRequestResponse, RfqQuote, RfqQuotesRequest, RfqRequest,
     RfqRequestsRequest,
 };
-use crate::clob::types::{SignableOrder, SignatureType, SignedOrder, TickSize};
+use crate::clob::types::{
+    ReplaceOrderResult, ReplaceStrategy, SignableOrder, SignatureType, SignedOrder, TickSize,
+    order_domain,
+};
+use crate::clob::wallet;
 use crate::error::{Error, Kind as ErrorKind, Synchronization};
+use crate::retry::RetryPolicy;
+use crate::timeout::DEFAULT_REQUEST_TIMEOUT;
+use crate::transport::Transport;
 use crate::types::Address;
 use crate::{
-    AMOY, POLYGON, Result, Timestamp, ToQueryParams as _, auth, contract_config,
+    AMOY, HttpClient, POLYGON, Result, Timestamp, ToQueryParams as _, auth, contract_config,
     derive_proxy_wallet, derive_safe_wallet,
 };
 
-const ORDER_NAME: Option<Cow<'static, str>> = Some(Cow::Borrowed("Polymarket CTF Exchange"));
-const VERSION: Option<Cow<'static, str>> = Some(Cow::Borrowed("1"));
-
 const TERMINAL_CURSOR: &str = "LTE="; // base64("-1")
 
+/// Maximum number of orders accepted by the CLOB in a single `POST /orders` request.
+/// [`Client::post_order_batches`] splits larger batches into several requests.
+pub const MAX_ORDERS_PER_BATCH: usize = 15;
+
 /// The type used to build a request to authenticate the inner [`Client<Unauthorized>`]. Calling
 /// `authenticate` on this will elevate that inner `client` into an [`Client<Authenticated<K>>`].
-pub struct AuthenticationBuilder<'signer, S: Signer, K: Kind = Normal> {
+pub struct AuthenticationBuilder<'signer, S: Signer + Sync, K: Kind = Normal> {
     /// The initially unauthenticated client that is "carried forward" into the authenticated client.
     client: Client<Unauthenticated>,
     /// The signer used to generate the L1 headers that will return a set of [`Credentials`].
@@ -87,10 +100,18 @@ pub struct AuthenticationBuilder<'signer, S: Signer, K: Kind = Normal> {
     /// The optional [`SignatureType`], see `funder` for more information.
     signature_type: Option<SignatureType>,
     /// The optional salt/seed generator for use in creating [`SignableOrder`]s
-    salt_generator: Option<fn() -> u64>,
+    salt_generator: Option<Arc<dyn SaltGenerator>>,
+    /// The optional [`CredentialStore`] consulted, when `credentials` are not present, before
+    /// creating or deriving [`Credentials`].
+    credential_store: Option<Arc<dyn CredentialStore>>,
+    /// Whether `signature_type` was set with [`Self::signature_type`], rather than carried over
+    /// from the client.
+    signature_type_set: bool,
+    /// Whether `funder` and `signature_type` are detected on-chain instead of being supplied.
+    auto_detect_wallet: bool,
 }
 
-impl<S: Signer, K: Kind> AuthenticationBuilder<'_, S, K> {
+impl<S: Signer + Sync, K: Kind> AuthenticationBuilder<'_, S, K> {
     #[must_use]
     pub fn nonce(mut self, nonce: u32) -> Self {
         self.nonce = Some(nonce);
@@ -112,12 +133,41 @@ impl<S: Signer, K: Kind> AuthenticationBuilder<'_, S, K> {
     #[must_use]
     pub fn signature_type(mut self, signature_type: SignatureType) -> Self {
         self.signature_type = Some(signature_type);
+        self.signature_type_set = true;
         self
     }
 
+    /// Sets the [`SaltGenerator`] of every order built by the client. Defaults to [`RandomSalt`].
     #[must_use]
-    pub fn salt_generator(mut self, salt_generator: fn() -> u64) -> Self {
-        self.salt_generator = Some(salt_generator);
+    pub fn salt_generator<G: SaltGenerator + 'static>(mut self, salt_generator: G) -> Self {
+        self.salt_generator = Some(Arc::new(salt_generator));
+        self
+    }
+
+    /// Loads the signer's [`Credentials`] from `store` when none are supplied, and only creates or
+    /// derives them, then saves them to `store`, if none are stored or the CLOB rejects the stored
+    /// ones.
+    ///
+    /// Stored credentials are checked with a `GET /auth/api-keys` request before being used. An
+    /// [`EnvStore`](crate::auth::persistence::EnvStore) is not keyed by address: unless
+    /// [`API_ADDRESS_VAR`](crate::auth::persistence::API_ADDRESS_VAR) is set, it returns the same
+    /// credentials for any signer.
+    #[must_use]
+    pub fn credential_store<C: CredentialStore + 'static>(mut self, store: C) -> Self {
+        self.credential_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Detects the funder and [`SignatureType`] of the signer instead of requiring them to be
+    /// supplied.
+    ///
+    /// Both the Proxy and the Gnosis Safe wallet addresses of the signer are derived, and the
+    /// [`Config`]'s RPC host is asked which one is deployed, or, failing that, holds collateral.
+    /// When neither is, the signer trades from its own EOA. Cannot be combined with
+    /// [`Self::funder`] or [`Self::signature_type`], and requires [`Config::rpc_host`] to be set.
+    #[must_use]
+    pub fn auto_detect_wallet(mut self) -> Self {
+        self.auto_detect_wallet = true;
         self
     }
 
@@ -140,10 +190,29 @@ impl<S: Signer, K: Kind> AuthenticationBuilder<'_, S, K> {
             }
         };
 
+        let (funder, signature_type) = if self.auto_detect_wallet {
+            if self.funder.is_some() || self.signature_type_set {
+                return Err(Error::validation(
+                    "Wallet auto-detection and funder or signature type are both set. If auto-detection is enabled, then you must not supply a funder or signature type",
+                ));
+            }
+
+            let rpc_host = inner.config.rpc_host.as_deref().ok_or(Error::validation(
+                "Wallet auto-detection requires a Polygon JSON-RPC endpoint, set one with Config::rpc_host",
+            ))?;
+            let rpc_host = Url::parse(rpc_host)?;
+            match wallet::detect(&rpc_host, self.signer.address(), chain_id).await? {
+                Some((funder, signature_type)) => (Some(funder), Some(signature_type)),
+                None => (None, Some(SignatureType::Eoa)),
+            }
+        } else {
+            (self.funder, self.signature_type)
+        };
+
         // Auto-derive funder from signer using CREATE2 when using proxy signature types
         // without explicit funder. This computes the deterministic wallet address that
         // Polymarket deploys for the user.
-        let funder = match (self.funder, self.signature_type) {
+        let funder = match (funder, signature_type) {
             (None, Some(SignatureType::Proxy)) => {
                 let derived =
                     derive_proxy_wallet(self.signer.address(), chain_id).ok_or_else(|| {
@@ -167,7 +236,7 @@ impl<S: Signer, K: Kind> AuthenticationBuilder<'_, S, K> {
             (funder, _) => funder,
         };
 
-        match (funder, self.signature_type) {
+        match (funder, signature_type) {
             (Some(_), Some(sig @ SignatureType::Eoa)) => {
                 return Err(Error::validation(format!(
                     "Cannot have a funder address with a {sig} signature type"
@@ -192,18 +261,44 @@ impl<S: Signer, K: Kind> AuthenticationBuilder<'_, S, K> {
                 ));
             }
             Some(credentials) => credentials,
-            None => {
-                inner
-                    .create_or_derive_api_key(self.signer, self.nonce)
-                    .await?
-            }
+            None => match &self.credential_store {
+                Some(_) if self.nonce.is_some() => {
+                    return Err(Error::validation(
+                        "Credential store and nonce are both set. If nonce is set, then you must not supply a credential store",
+                    ));
+                }
+                Some(store) => {
+                    let address = self.signer.address();
+                    let stored = match store.load(address).await? {
+                        Some(credentials) => {
+                            match inner.check_credentials(address, &credentials).await {
+                                Ok(()) => Some(credentials),
+                                // Rejected, e.g. because the key was deleted, so derive them again.
+                                // Propagate network/internal errors.
+                                Err(err) if err.kind() == ErrorKind::Status => None,
+                                Err(err) => return Err(err),
+                            }
+                        }
+                        None => None,
+                    };
+
+                    if let Some(credentials) = stored {
+                        credentials
+                    } else {
+                        let credentials = inner.create_or_derive_api_key(self.signer, None).await?;
+                        store.save(self.signer.address(), &credentials).await?;
+                        credentials
+                    }
+                }
+                None => {
+                    inner
+                        .create_or_derive_api_key(self.signer, self.nonce)
+                        .await?
+                }
+            },
         };
 
-        let state = Authenticated {
-            address: self.signer.address(),
-            credentials,
-            kind: self.kind,
-        };
+        let state = Authenticated::new(self.signer.address(), credentials, self.kind);
 
         #[cfg_attr(
             not(feature = "heartbeats"),
@@ -222,9 +317,12 @@ impl<S: Signer, K: Kind> AuthenticationBuilder<'_, S, K> {
                 tick_sizes: inner.tick_sizes,
                 neg_risk: inner.neg_risk,
                 fee_rate_bps: inner.fee_rate_bps,
+                #[cfg(feature = "ctf")]
+                nonces: inner.nonces,
                 funder,
-                signature_type: self.signature_type.unwrap_or(SignatureType::Eoa),
-                salt_generator: self.salt_generator.unwrap_or(generate_seed),
+                signature_type: signature_type.unwrap_or(SignatureType::Eoa),
+                salt_generator: self.salt_generator.unwrap_or_else(|| Arc::new(RandomSalt)),
+                clock: inner.clock,
             }),
             #[cfg(feature = "heartbeats")]
             heartbeat_token: DroppingCancellationToken(None),
@@ -364,10 +462,42 @@ pub struct Config {
     /// headers. This adds another round trip to the requests.
     #[builder(default)]
     use_server_time: bool,
+    /// Measures the skew between the local and the server clocks periodically and applies it to
+    /// auth header timestamps, instead of fetching the server time for every request. Takes
+    /// precedence over `use_server_time`. See [`clock`](crate::clob::clock).
+    clock_sync: Option<ClockSync>,
     /// Override for the geoblock API host. Defaults to `https://polymarket.com`.
     /// This is primarily useful for testing.
     #[builder(into)]
     geoblock_host: Option<String>,
+    /// Polygon JSON-RPC endpoint queried by
+    /// [`AuthenticationBuilder::auto_detect_wallet`], which requires it to be set.
+    #[builder(into)]
+    rpc_host: Option<String>,
+    /// Retry policy applied to every request made by the [`Client`]. Defaults to a single attempt.
+    /// Requests are only retried if their HTTP method is listed as idempotent by the policy, so
+    /// order placement is not retried by default. Authenticated requests are never retried, as
+    /// their signed headers would be stale by the next attempt.
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+    /// Client-side rate limits per endpoint group. When set, requests that would exceed a quota
+    /// wait for capacity instead of being sent. See [`rate_limit`](crate::clob::rate_limit).
+    rate_limits: Option<RateLimits>,
+    /// Timeout of every request made by the [`Client`], covering the connection, the request and
+    /// reading the response. A request that times out fails with an error of kind
+    /// [`Kind::Timeout`](crate::error::Kind::Timeout). Defaults to
+    /// [`DEFAULT_REQUEST_TIMEOUT`]; see [`timeout`](crate::timeout) to override it for a single
+    /// call.
+    timeout: Option<Duration>,
+    /// [`Transport`] every request made by the [`Client`] is sent through, e.g. a
+    /// [`reqwest::Client`] configured with a proxy, or a [`Stack`](crate::transport::Stack) of
+    /// middleware. Defaults to a [`reqwest::Client`] that gives up connecting after
+    /// [`DEFAULT_CONNECT_TIMEOUT`](crate::timeout::DEFAULT_CONNECT_TIMEOUT).
+    transport: Option<Arc<dyn Transport>>,
+    /// Pre-trade checks run on every order before [`Client::post_order`] or
+    /// [`Client::post_orders`] sends it. See [`risk`](crate::clob::risk).
+    #[builder(default)]
+    risk_checks: Vec<Arc<dyn RiskCheck>>,
     #[cfg(feature = "heartbeats")]
     #[builder(default = Duration::from_secs(5))]
     /// How often the [`Client`] will automatically submit heartbeats. The default is five (5) seconds.
@@ -386,14 +516,17 @@ struct ClientInner<S: State> {
     host: Url,
     /// The [`Url`] for the geoblock API endpoint.
     geoblock_host: Url,
-    /// The inner [`ReqwestClient`] used to make requests to `host`.
-    client: ReqwestClient,
+    /// The inner [`HttpClient`] used to make requests to `host`.
+    client: HttpClient,
     /// Local cache of [`TickSize`] per token ID
     tick_sizes: DashMap<U256, TickSize>,
     /// Local cache representing whether this token is part of a `neg_risk` market
     neg_risk: DashMap<U256, bool>,
     /// Local cache representing the fee rate in basis points per token ID
     fee_rate_bps: DashMap<U256, u32>,
+    /// Current on-chain order nonce per exchange contract, stamped on orders signed without one
+    #[cfg(feature = "ctf")]
+    nonces: DashMap<Address, U256>,
     /// The funder for this [`ClientInner`]. If funder is present, then `signature_type` cannot
     /// be [`SignatureType::Eoa`]. Conversely, if funder is absent, then `signature_type` cannot be
     /// [`SignatureType::Proxy`] or [`SignatureType::GnosisSafe`].
@@ -401,22 +534,44 @@ struct ClientInner<S: State> {
     /// The signature type for this [`ClientInner`]. Defaults to [`SignatureType::Eoa`]
     signature_type: SignatureType,
     /// The salt/seed generator for use in creating [`SignableOrder`]s
-    salt_generator: fn() -> u64,
+    salt_generator: Arc<dyn SaltGenerator>,
+    /// Skew to the server clock, measured when `config.clock_sync` is set
+    clock: Clock,
 }
 
 impl<S: State> ClientInner<S> {
     pub async fn server_time(&self) -> Result<Timestamp> {
-        let request = self
-            .client
-            .request(Method::GET, format!("{}time", self.host))
-            .build()?;
-
-        crate::request(&self.client, request, None).await
+        server_time(&self.client, &self.host).await
+    }
+
+    /// Timestamp of auth headers, taken from the clock configured in [`Config`].
+    async fn timestamp(&self) -> Result<Timestamp> {
+        if let Some(sync) = &self.config.clock_sync {
+            // Owned, as the skew may be measured again in a background task
+            let (client, host) = (self.client.clone(), self.host.clone());
+            self.clock
+                .now(
+                    sync,
+                    move || async move { server_time(&client, &host).await },
+                )
+                .await
+        } else if self.config.use_server_time {
+            self.server_time().await
+        } else {
+            Ok(Utc::now().timestamp())
+        }
     }
 }
 
-impl ClientInner<Unauthenticated> {
-    pub async fn create_api_key<S: Signer>(
+/// Fetches the CLOB server's current time.
+async fn server_time(client: &HttpClient, host: &Url) -> Result<Timestamp> {
+    let request = client.request(Method::GET, format!("{host}time")).build()?;
+
+    crate::request(client, request, None).await
+}
+
+impl<St: State> ClientInner<St> {
+    pub async fn create_api_key<S: Signer + Sync>(
         &self,
         signer: &S,
         nonce: Option<u32>,
@@ -430,7 +585,7 @@ impl ClientInner<Unauthenticated> {
         crate::request(&self.client, request, Some(headers)).await
     }
 
-    pub async fn derive_api_key<S: Signer>(
+    pub async fn derive_api_key<S: Signer + Sync>(
         &self,
         signer: &S,
         nonce: Option<u32>,
@@ -444,7 +599,7 @@ impl ClientInner<Unauthenticated> {
         crate::request(&self.client, request, Some(headers)).await
     }
 
-    async fn create_or_derive_api_key<S: Signer>(
+    async fn create_or_derive_api_key<S: Signer + Sync>(
         &self,
         signer: &S,
         nonce: Option<u32>,
@@ -460,16 +615,32 @@ impl ClientInner<Unauthenticated> {
         }
     }
 
-    async fn create_headers<S: Signer>(&self, signer: &S, nonce: Option<u32>) -> Result<HeaderMap> {
+    /// Checks that the CLOB accepts the `credentials` of `address` with a cheap authenticated
+    /// request.
+    async fn check_credentials(&self, address: Address, credentials: &Credentials) -> Result<()> {
+        let request = self
+            .client
+            .request(Method::GET, format!("{}auth/api-keys", self.host))
+            .build()?;
+        let state = Authenticated::new(address, credentials.clone(), Normal);
+        let timestamp = self.timestamp().await?;
+        let headers = auth::l2::create_headers(&state, credentials, &request, timestamp).await?;
+
+        let _: ApiKeysResponse = crate::request(&self.client, request, Some(headers)).await?;
+
+        Ok(())
+    }
+
+    async fn create_headers<S: Signer + Sync>(
+        &self,
+        signer: &S,
+        nonce: Option<u32>,
+    ) -> Result<HeaderMap> {
         let chain_id = signer.chain_id().ok_or(Error::validation(
             "Chain id not set, be sure to provide one on the signer",
         ))?;
 
-        let timestamp = if self.config.use_server_time {
-            self.server_time().await?
-        } else {
-            Utc::now().timestamp()
-        };
+        let timestamp = self.timestamp().await?;
 
         auth::l1::create_headers(signer, chain_id, timestamp, nonce).await
     }
@@ -525,6 +696,21 @@ impl<S: State> Client<S> {
         self.inner.tick_sizes.insert(token_id, tick_size);
     }
 
+    /// Tick size cached for a token, without fetching it.
+    #[cfg(feature = "ws")]
+    pub(crate) fn cached_tick_size(&self, token_id: U256) -> Option<TickSize> {
+        self.inner
+            .tick_sizes
+            .get(&token_id)
+            .map(|tick_size| *tick_size)
+    }
+
+    /// Removes the cached tick size of a token, so the next lookup fetches it.
+    #[cfg(feature = "ws")]
+    pub(crate) fn forget_tick_size(&self, token_id: U256) {
+        self.inner.tick_sizes.remove(&token_id);
+    }
+
     /// Pre-populates the neg risk cache for a token, avoiding the HTTP call.
     ///
     /// Use this when you already have the neg risk data from another source
@@ -594,6 +780,16 @@ impl<S: State> Client<S> {
         self.inner.server_time().await
     }
 
+    /// Returns the last measured difference between the server and the local clocks, positive
+    /// when the server is ahead.
+    ///
+    /// Only measured when [`Config`] has a [`ClockSync`], and `None` until the first authenticated
+    /// request. See [`clock`](crate::clob::clock).
+    #[must_use]
+    pub fn clock_skew(&self) -> Option<TimeDelta> {
+        self.inner.clock.skew()
+    }
+
     /// Retrieves the midpoint price for a single market outcome token.
     ///
     /// The midpoint is the average of the best bid and best ask prices,
@@ -1144,7 +1340,14 @@ impl<S: State> Client<S> {
         }
     }
 
-    fn client(&self) -> &ReqwestClient {
+    /// Returns the current queue statistics for `group`, or `None` if no rate limit is configured
+    /// for it. See [`Config`]'s `rate_limits`.
+    #[must_use]
+    pub fn rate_limit_stats(&self, group: EndpointGroup) -> Option<RateLimitStats> {
+        self.inner.client.rate_limiter()?.stats(group)
+    }
+
+    fn client(&self) -> &HttpClient {
         &self.inner.client
     }
 }
@@ -1176,14 +1379,16 @@ impl Client<Unauthenticated> {
     /// # }
     /// ```
     pub fn new(host: &str, config: Config) -> Result<Client<Unauthenticated>> {
-        let mut headers = HeaderMap::new();
-
-        headers.insert("User-Agent", HeaderValue::from_static("rs_clob_client"));
-        headers.insert("Accept", HeaderValue::from_static("*/*"));
-        headers.insert("Connection", HeaderValue::from_static("keep-alive"));
-        headers.insert("Content-Type", HeaderValue::from_static("application/json"));
-
-        let client = ReqwestClient::builder().default_headers(headers).build()?;
+        let mut client = HttpClient::new(
+            config.retry_policy.clone(),
+            config.timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT),
+        )?;
+        if let Some(transport) = &config.transport {
+            client.set_transport(Arc::clone(transport));
+        }
+        if let Some(rate_limits) = &config.rate_limits {
+            client = client.with_rate_limiter(RateLimiter::new(rate_limits));
+        }
 
         let geoblock_host = Url::parse(
             config
@@ -1201,10 +1406,13 @@ impl Client<Unauthenticated> {
                 tick_sizes: DashMap::new(),
                 neg_risk: DashMap::new(),
                 fee_rate_bps: DashMap::new(),
+                #[cfg(feature = "ctf")]
+                nonces: DashMap::new(),
                 state: Unauthenticated,
                 funder: None,
                 signature_type: SignatureType::Eoa,
-                salt_generator: generate_seed,
+                salt_generator: Arc::new(RandomSalt),
+                clock: Clock::default(),
             }),
             #[cfg(feature = "heartbeats")]
             heartbeat_token: DroppingCancellationToken(None),
@@ -1239,7 +1447,7 @@ impl Client<Unauthenticated> {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn authentication_builder<S: Signer>(
+    pub fn authentication_builder<S: Signer + Sync>(
         self,
         signer: &S,
     ) -> AuthenticationBuilder<'_, S, Normal> {
@@ -1252,12 +1460,15 @@ impl Client<Unauthenticated> {
             signature_type: Some(self.inner.signature_type),
             client: self,
             salt_generator: None,
+            credential_store: None,
+            signature_type_set: false,
+            auto_detect_wallet: false,
         }
     }
 
     /// Attempts to create a new set of [`Credentials`] and returns an error if there already is one
     /// for the particular L2 header's (signer) `address` and `nonce`.
-    pub async fn create_api_key<S: Signer>(
+    pub async fn create_api_key<S: Signer + Sync>(
         &self,
         signer: &S,
         nonce: Option<u32>,
@@ -1267,7 +1478,7 @@ impl Client<Unauthenticated> {
 
     /// Attempts to derive an existing set of [`Credentials`] and returns an error if there
     /// are none for the particular L2 header's (signer) `address` and `nonce`.
-    pub async fn derive_api_key<S: Signer>(
+    pub async fn derive_api_key<S: Signer + Sync>(
         &self,
         signer: &S,
         nonce: Option<u32>,
@@ -1278,7 +1489,7 @@ impl Client<Unauthenticated> {
     /// Idempotent alternative to [`Self::create_api_key`] and [`Self::derive_api_key`], which will
     /// either create a new set of [`Credentials`] if they do not exist already, or return them if
     /// they do.
-    pub async fn create_or_derive_api_key<S: Signer>(
+    pub async fn create_or_derive_api_key<S: Signer + Sync>(
         &self,
         signer: &S,
         nonce: Option<u32>,
@@ -1313,10 +1524,13 @@ impl<K: Kind> Client<Authenticated<K>> {
                 tick_sizes: inner.tick_sizes,
                 neg_risk: inner.neg_risk,
                 fee_rate_bps: inner.fee_rate_bps,
+                #[cfg(feature = "ctf")]
+                nonces: inner.nonces,
                 // Reset the order parameters that were previously stored on the client
                 funder: None,
                 signature_type: SignatureType::Eoa,
-                salt_generator: generate_seed,
+                salt_generator: Arc::new(RandomSalt),
+                clock: inner.clock,
             }),
             #[cfg(feature = "heartbeats")]
             heartbeat_token: DroppingCancellationToken(None),
@@ -1344,10 +1558,35 @@ impl<K: Kind> Client<Authenticated<K>> {
     /// Returns the credentials associated with this authenticated client.
     ///
     /// These credentials are required to authorize interactions with the CLOB
-    /// and authenticate the WebSocket user channel connection.
+    /// and authenticate the WebSocket user channel connection. They can change after
+    /// [`Self::rotate_credentials`], so this returns a copy of the current ones.
     #[must_use]
-    pub fn credentials(&self) -> &Credentials {
-        &self.state().credentials
+    pub fn credentials(&self) -> Credentials {
+        self.state().credentials()
+    }
+
+    /// Address orders are made from: the funder if set, otherwise the signer.
+    #[cfg(feature = "ctf")]
+    pub(crate) fn maker(&self) -> Address {
+        self.inner.funder.unwrap_or_else(|| self.address())
+    }
+
+    #[cfg(feature = "ctf")]
+    pub(crate) fn signature_type(&self) -> SignatureType {
+        self.inner.signature_type
+    }
+
+    /// Records the current on-chain nonce of the maker on `exchange`, stamped on every order
+    /// signed for that exchange without a nonce of its own.
+    #[cfg(feature = "ctf")]
+    pub(crate) fn set_nonce(&self, exchange: Address, nonce: U256) {
+        self.inner.nonces.insert(exchange, nonce);
+    }
+
+    /// Nonce recorded for `exchange` by [`Self::set_nonce`], if any.
+    #[cfg(feature = "ctf")]
+    pub(crate) fn nonce(&self, exchange: Address) -> Option<U256> {
+        self.inner.nonces.get(&exchange).map(|nonce| *nonce)
     }
 
     /// Return all API keys associated with the address corresponding to the inner signer in
@@ -1380,6 +1619,60 @@ impl<K: Kind> Client<Authenticated<K>> {
         crate::request(&self.inner.client, request, Some(headers)).await
     }
 
+    /// Creates a new API key with `signer` and `nonce`, then switches this client and all of its
+    /// clones to it. Requests already in flight finish with the previous key and heartbeats keep
+    /// running throughout, so open orders are not cancelled. Returns the new credentials, which
+    /// should be persisted in place of the previous ones.
+    ///
+    /// Each `nonce` maps to a single key, so `nonce` must differ from the one of the current key.
+    /// When `delete_old` is set, the previous key is deleted once the new one is in use.
+    ///
+    /// WebSocket connections authenticated with the previous key are not affected. An
+    /// [`OrderManager`](crate::clob::OrderManager) made from the client follows the new key, while
+    /// a [`PositionBook`](crate::clob::positions::PositionBook) needs it passed to
+    /// [`PositionBook::add_api_key`](crate::clob::positions::PositionBook::add_api_key).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `signer` is not the client's signer or the key cannot be created, in
+    /// which case the client keeps the previous key. Also returns an error if the previous key
+    /// cannot be deleted, in which case the client already uses the new key.
+    pub async fn rotate_credentials<S: Signer + Sync>(
+        &self,
+        signer: &S,
+        nonce: u32,
+        delete_old: bool,
+    ) -> Result<Credentials> {
+        if signer.address() != self.address() {
+            return Err(Error::validation(format!(
+                "Credentials of {} cannot be rotated with signer {}",
+                self.address(),
+                signer.address()
+            )));
+        }
+
+        let credentials = self.inner.create_api_key(signer, Some(nonce)).await?;
+        if credentials.key() == self.state().credentials().key() {
+            return Err(Error::validation(format!(
+                "Nonce {nonce} returned the current API key, use another nonce to rotate it"
+            )));
+        }
+
+        let old = self.state().replace_credentials(credentials.clone());
+
+        if delete_old {
+            let request = self
+                .client()
+                .request(Method::DELETE, format!("{}auth/api-key", self.host()))
+                .build()?;
+            let headers = self.create_headers_with(&request, &old).await?;
+
+            crate::request::<serde_json::Value>(&self.inner.client, request, Some(headers)).await?;
+        }
+
+        Ok(credentials)
+    }
+
     /// Checks if the account is in closed-only mode (banned from opening new positions).
     ///
     /// Returns the ban status indicating whether the user can only close existing
@@ -1414,23 +1707,45 @@ impl<K: Kind> Client<Authenticated<K>> {
         self.order_builder()
     }
 
+    /// Creates an [`OrderBatchBuilder`] used to construct a ladder of limit orders on one token.
+    #[must_use]
+    pub fn order_ladder(&self) -> OrderBatchBuilder<K> {
+        OrderBatchBuilder {
+            client: Client {
+                inner: Arc::clone(&self.inner),
+                #[cfg(feature = "heartbeats")]
+                heartbeat_token: self.heartbeat_token.clone(),
+            },
+            token_id: None,
+            bids: None,
+            asks: None,
+            order_type: None,
+            post_only: None,
+            expiration: None,
+        }
+    }
+
     /// Attempts to sign the provided [`SignableOrder`] using the inner signer of [`Authenticated<K>`]
     #[expect(
         clippy::missing_panics_doc,
         reason = "No need to publicly document as we are guarded by the typestate pattern. \
         We cannot call `sign` without first calling `authenticate`"
     )]
-    pub async fn sign<S: Signer>(
+    pub async fn sign<S: Signer + Sync>(
         &self,
         signer: &S,
         SignableOrder {
             order,
             order_type,
             post_only,
+            neg_risk,
+            managed_nonce,
         }: SignableOrder,
     ) -> Result<SignedOrder> {
-        let token_id = order.tokenId;
-        let neg_risk = self.neg_risk(token_id).await?.neg_risk;
+        let neg_risk = match neg_risk {
+            Some(neg_risk) => neg_risk,
+            None => self.neg_risk(order.tokenId).await?.neg_risk,
+        };
         let chain_id = signer
             .chain_id()
             .expect("Validated not none in `authenticate`");
@@ -1439,27 +1754,45 @@ impl<K: Kind> Client<Authenticated<K>> {
             .ok_or(Error::missing_contract_config(chain_id, neg_risk))?
             .exchange;
 
-        let domain = Eip712Domain {
-            name: ORDER_NAME,
-            version: VERSION,
-            chain_id: Some(U256::from(chain_id)),
-            verifying_contract: Some(exchange_contract),
-            ..Eip712Domain::default()
+        #[cfg(feature = "ctf")]
+        let order = if managed_nonce && let Some(nonce) = self.nonce(exchange_contract) {
+            Order { nonce, ..order }
+        } else {
+            order
         };
+        #[cfg(not(feature = "ctf"))]
+        let _: bool = managed_nonce;
+
+        let domain = order_domain(chain_id, exchange_contract);
 
         let signature = signer
-            .sign_hash(&order.eip712_signing_hash(&domain))
+            .sign_dynamic_typed_data(&typed_data(&order, domain)?)
             .await?;
 
         Ok(SignedOrder {
             order,
             signature,
             order_type,
-            owner: self.state().credentials.key,
+            owner: self.state().credentials().key,
             post_only,
+            chain_id,
+            neg_risk,
         })
     }
 
+    /// Signs every [`SignableOrder`] concurrently with [`Self::sign`], keeping their order.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered while signing, in which case no order is returned.
+    pub async fn sign_orders<S: Signer + Sync>(
+        &self,
+        signer: &S,
+        orders: Vec<SignableOrder>,
+    ) -> Result<Vec<SignedOrder>> {
+        try_join_all(orders.into_iter().map(|order| self.sign(signer, order))).await
+    }
+
     /// Posts a signed order to the orderbook.
     ///
     /// Submits a single limit or market order that has been signed with the
@@ -1472,8 +1805,16 @@ impl<K: Kind> Client<Authenticated<K>> {
     /// - The order signature is invalid
     /// - The user has insufficient balance or allowance
     /// - The order price/size violates market rules
+    /// - The order is rejected by one of the configured [`RiskCheck`]s
     /// - The request fails
     pub async fn post_order(&self, order: SignedOrder) -> Result<PostOrderResponse> {
+        risk::evaluate(
+            &self.inner.config.risk_checks,
+            self,
+            std::slice::from_ref(&order),
+        )
+        .await?;
+
         let request = self
             .client()
             .request(Method::POST, format!("{}order", self.host()))
@@ -1488,16 +1829,51 @@ impl<K: Kind> Client<Authenticated<K>> {
     ///
     /// This is the batch version of [`Self::post_order`], allowing efficient
     /// submission of multiple orders at once. All orders are validated and
-    /// processed atomically.
+    /// processed atomically. The CLOB accepts at most [`MAX_ORDERS_PER_BATCH`] orders per
+    /// request, see [`Self::post_order_batches`] for larger batches.
     ///
     /// # Errors
     ///
-    /// Returns an error if any order fails validation or the request fails.
+    /// Returns an error if any order fails validation, is rejected by one of the configured
+    /// [`RiskCheck`]s, or the request fails. A single rejected order prevents the whole batch from
+    /// being sent.
     pub async fn post_orders(&self, orders: Vec<SignedOrder>) -> Result<Vec<PostOrderResponse>> {
+        risk::evaluate(&self.inner.config.risk_checks, self, &orders).await?;
+
+        self.send_orders(&orders).await
+    }
+
+    /// Posts any number of signed orders, e.g. a ladder built with [`Self::order_ladder`], in
+    /// requests of at most [`MAX_ORDERS_PER_BATCH`] orders sent one after the other.
+    ///
+    /// Every request is sent even if a previous one failed, and the result of each is returned in
+    /// the order of `orders`, so that the orders that were placed can be told apart from those
+    /// that were not.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any order is rejected by one of the configured [`RiskCheck`]s, in which
+    /// case no request is sent.
+    pub async fn post_order_batches(
+        &self,
+        orders: Vec<SignedOrder>,
+    ) -> Result<Vec<Result<Vec<PostOrderResponse>>>> {
+        risk::evaluate(&self.inner.config.risk_checks, self, &orders).await?;
+
+        let mut results = Vec::with_capacity(orders.len().div_ceil(MAX_ORDERS_PER_BATCH));
+        for chunk in orders.chunks(MAX_ORDERS_PER_BATCH) {
+            results.push(self.send_orders(chunk).await);
+        }
+
+        Ok(results)
+    }
+
+    /// Sends `orders` in a single `POST /orders` request, without risk checks.
+    async fn send_orders(&self, orders: &[SignedOrder]) -> Result<Vec<PostOrderResponse>> {
         let request = self
             .client()
             .request(Method::POST, format!("{}orders", self.host()))
-            .json(&orders)
+            .json(orders)
             .build()?;
         let headers = self.create_headers(&request).await?;
 
@@ -1559,6 +1935,53 @@ impl<K: Kind> Client<Authenticated<K>> {
         crate::request(&self.inner.client, request, Some(headers)).await
     }
 
+    /// Replaces the resting order `order_id` with `order`, canceling one and posting the other in
+    /// the sequence given by `strategy`.
+    ///
+    /// `order` is signed before either leg is sent, so a signing failure leaves the old order
+    /// untouched. The second leg is only sent if the first one succeeded: with
+    /// [`ReplaceStrategy::CancelFirst`], the new order is only posted once the old one is reported
+    /// as canceled, and with [`ReplaceStrategy::PostFirst`], the old order is only canceled once
+    /// the new one is accepted. Failures of either leg are reported in the returned
+    /// [`ReplaceOrderResult`] rather than as an error, so callers can tell exactly which legs went
+    /// through.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `order` cannot be signed, in which case neither leg is sent.
+    pub async fn replace_order<S: Signer + Sync>(
+        &self,
+        signer: &S,
+        order_id: &str,
+        order: SignableOrder,
+        strategy: ReplaceStrategy,
+    ) -> Result<ReplaceOrderResult> {
+        let order = self.sign(signer, order).await?;
+
+        let mut result = ReplaceOrderResult {
+            order_id: order_id.to_owned(),
+            cancel: None,
+            post: None,
+        };
+
+        match strategy {
+            ReplaceStrategy::PostFirst => {
+                result.post = Some(self.post_order(order).await);
+                if result.posted() {
+                    result.cancel = Some(self.cancel_order(order_id).await);
+                }
+            }
+            ReplaceStrategy::CancelFirst => {
+                result.cancel = Some(self.cancel_order(order_id).await);
+                if result.canceled() {
+                    result.post = Some(self.post_order(order).await);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Cancels multiple orders by their order IDs in a single request.
     ///
     /// This is the batch version of [`Self::cancel_order`], allowing efficient
@@ -2089,13 +2512,18 @@ impl<K: Kind> Client<Authenticated<K>> {
     }
 
     async fn create_headers(&self, request: &Request) -> Result<HeaderMap> {
-        let timestamp = if self.inner.config.use_server_time {
-            self.server_time().await?
-        } else {
-            Utc::now().timestamp()
-        };
+        self.create_headers_with(request, &self.state().credentials())
+            .await
+    }
+
+    async fn create_headers_with(
+        &self,
+        request: &Request,
+        credentials: &Credentials,
+    ) -> Result<HeaderMap> {
+        let timestamp = self.inner.timestamp().await?;
 
-        auth::l2::create_headers(self.state(), request, timestamp).await
+        auth::l2::create_headers(self.state(), credentials, request, timestamp).await
     }
 
     fn order_builder<OrderKind>(&self) -> OrderBuilder<OrderKind, K> {
@@ -2103,7 +2531,7 @@ impl<K: Kind> Client<Authenticated<K>> {
             signer: self.address(),
             signature_type: self.inner.signature_type,
             funder: self.inner.funder,
-            salt_generator: self.inner.salt_generator,
+            salt_generator: Arc::clone(&self.inner.salt_generator),
             token_id: None,
             price: None,
             size: None,
@@ -2114,6 +2542,8 @@ impl<K: Kind> Client<Authenticated<K>> {
             taker: None,
             order_type: None,
             post_only: Some(false),
+            book: None,
+            price_limit: None,
             client: Client {
                 inner: Arc::clone(&self.inner),
                 #[cfg(feature = "heartbeats")]
@@ -2148,14 +2578,14 @@ impl Client<Authenticated<Normal>> {
 
         let inner = Arc::into_inner(self.inner).ok_or(Synchronization)?;
 
-        let state = Authenticated {
-            address: inner.state.address,
-            credentials: inner.state.credentials,
-            kind: Builder {
+        let state = Authenticated::new(
+            inner.state.address,
+            inner.state.credentials(),
+            Builder {
                 config,
-                client: inner.client.clone(),
+                client: Arc::clone(inner.client.transport()),
             },
-        };
+        );
 
         let new_inner = ClientInner {
             config: inner.config,
@@ -2166,9 +2596,12 @@ impl Client<Authenticated<Normal>> {
             tick_sizes: inner.tick_sizes,
             neg_risk: inner.neg_risk,
             fee_rate_bps: inner.fee_rate_bps,
+            #[cfg(feature = "ctf")]
+            nonces: inner.nonces,
             funder: inner.funder,
             signature_type: inner.signature_type,
             salt_generator: inner.salt_generator,
+            clock: inner.clock,
         };
 
         #[cfg_attr(
@@ -0,0 +1,201 @@
+#![expect(
+    clippy::module_name_repetitions,
+    reason = "ClockSync reads better than a bare Sync, which would shadow the marker trait"
+)]
+
+//! Tracking of the offset between the local clock and the CLOB server's.
+//!
+//! Authentication headers carry a timestamp the server checks against its own clock, so a host
+//! whose clock drifted has its requests rejected. [`Config`]'s `use_server_time` works around this
+//! by fetching the server time before every authenticated request, which doubles its latency.
+//!
+//! With a [`ClockSync`] set on the [`Config`], the client instead measures the skew between the
+//! two clocks at most once every [`ClockSync::interval`], and shifts local timestamps by it for
+//! L1, L2 and builder headers alike. Only the first authenticated request waits for a
+//! measurement: once the skew is older than the interval, requests keep using it while it is
+//! measured again in the background, and keep it if that measurement fails. The last measured
+//! skew is returned by [`Client::clock_skew`] for monitoring and, with the `tracing` feature, a
+//! warning is logged whenever it exceeds [`ClockSync::warn_threshold`].
+//!
+//! The server reports its time in whole seconds, so the skew is only accurate to about half a
+//! second, well within what the server tolerates.
+//!
+//! # Examples
+//!
+//! ```rust, no_run
+//! use std::time::Duration;
+//!
+//! use polymarket_client_sdk::clob::clock::ClockSync;
+//! use polymarket_client_sdk::clob::{Client, Config};
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = Config::builder()
+//!     .clock_sync(
+//!         ClockSync::builder()
+//!             .interval(Duration::from_secs(300))
+//!             .build(),
+//!     )
+//!     .build();
+//! let client = Client::new("https://clob.polymarket.com", config)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Config`]: crate::clob::Config
+//! [`Client::clock_skew`]: crate::clob::Client::clock_skew
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use bon::Builder;
+use chrono::{TimeDelta, Utc};
+
+use crate::{Result, Timestamp};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_WARN_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Policy for measuring the skew between the local and the server clocks.
+#[non_exhaustive]
+#[derive(Clone, Debug, Builder)]
+pub struct ClockSync {
+    /// How long a measured skew is used before it is measured again. Defaults to one minute.
+    #[builder(default = DEFAULT_INTERVAL)]
+    pub interval: Duration,
+    /// Skew, in either direction, above which a warning is logged. Defaults to five seconds.
+    #[builder(default = DEFAULT_WARN_THRESHOLD)]
+    pub warn_threshold: Duration,
+}
+
+impl Default for ClockSync {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// The last skew measured by a client.
+#[derive(Debug, Default)]
+pub(crate) struct Clock {
+    /// Shared with the background task measuring the skew again.
+    state: Arc<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Server minus local time, and when it was measured.
+    last: Mutex<Option<(TimeDelta, Instant)>>,
+    /// Held while measuring the first skew, so concurrent requests wait for one measurement
+    /// instead of each making their own.
+    measuring: tokio::sync::Mutex<()>,
+    /// Whether a background task is measuring the skew again.
+    refreshing: AtomicBool,
+}
+
+impl Clock {
+    /// The last measured skew, if any.
+    pub(crate) fn skew(&self) -> Option<TimeDelta> {
+        self.state.last().map(|(skew, _)| skew)
+    }
+
+    /// Current server time, estimated from the local clock and the last skew.
+    ///
+    /// The first skew is measured by calling `server_time` before returning. Afterwards, a skew
+    /// older than [`ClockSync::interval`] is still used, while `server_time` is called in a
+    /// background task to measure it again.
+    pub(crate) async fn now<F, Fut>(&self, sync: &ClockSync, server_time: F) -> Result<Timestamp>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Timestamp>> + Send,
+    {
+        let skew = if let Some((skew, measured_at)) = self.state.last() {
+            if measured_at.elapsed() >= sync.interval {
+                self.refresh(sync, server_time);
+            }
+            skew
+        } else {
+            let _measuring = self.state.measuring.lock().await;
+            // Another request may have measured the skew while this one waited
+            if let Some((skew, _)) = self.state.last() {
+                skew
+            } else {
+                let skew = measure(server_time).await?;
+                self.state.record(skew, sync.warn_threshold);
+                skew
+            }
+        };
+
+        Ok((Utc::now() + skew).timestamp())
+    }
+
+    /// Measures the skew again in a background task, unless one already is.
+    fn refresh<F, Fut>(&self, sync: &ClockSync, server_time: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Timestamp>> + Send,
+    {
+        if self.state.refreshing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let state = Arc::clone(&self.state);
+        let warn_threshold = sync.warn_threshold;
+        tokio::spawn(async move {
+            match measure(server_time).await {
+                Ok(skew) => state.record(skew, warn_threshold),
+                // The last skew stays in use, and is measured again on the next request
+                Err(e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %e, "Failed to measure the clock skew, keeping the last one");
+                    #[cfg(not(feature = "tracing"))]
+                    let _: crate::error::Error = e;
+                }
+            }
+            state.refreshing.store(false, Ordering::Release);
+        });
+    }
+}
+
+impl State {
+    fn last(&self) -> Option<(TimeDelta, Instant)> {
+        *self.last.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[cfg_attr(
+        not(feature = "tracing"),
+        expect(unused_variables, reason = "Only used to log drift")
+    )]
+    fn record(&self, skew: TimeDelta, warn_threshold: Duration) {
+        #[cfg(feature = "tracing")]
+        warn_on_drift(skew, warn_threshold);
+        *self.last.lock().unwrap_or_else(PoisonError::into_inner) = Some((skew, Instant::now()));
+    }
+}
+
+/// Logs a warning if `skew` exceeds `threshold` in either direction.
+#[cfg(feature = "tracing")]
+fn warn_on_drift(skew: TimeDelta, threshold: Duration) {
+    if TimeDelta::from_std(threshold).is_ok_and(|threshold| skew.abs() > threshold) {
+        tracing::warn!(
+            skew_ms = skew.num_milliseconds(),
+            "Local clock differs from the CLOB server clock"
+        );
+    }
+}
+
+/// Measures server minus local time, assuming the server read its clock halfway through the
+/// request, halfway through the second it reports.
+async fn measure<F, Fut>(server_time: F) -> Result<TimeDelta>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<Timestamp>>,
+{
+    let sent = Utc::now();
+    let server = server_time().await?;
+    let received = Utc::now();
+
+    let local = sent + (received - sent) / 2;
+    let server = TimeDelta::seconds(server) + TimeDelta::milliseconds(500);
+
+    Ok(server - TimeDelta::milliseconds(local.timestamp_millis()))
+}
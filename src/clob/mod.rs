@@ -143,10 +143,30 @@
 //!
 //! The default API endpoint is `https://clob.polymarket.com`.
 
+pub mod analytics;
 pub mod client;
+pub mod clock;
+#[cfg(feature = "ctf")]
+pub mod nonce_manager;
+pub mod order_batch;
 pub mod order_builder;
+#[cfg(feature = "ws")]
+pub mod order_manager;
+pub mod positions;
+pub mod rate_limit;
+pub mod risk;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "ws")]
+pub mod tick_size_sync;
 pub mod types;
+mod wallet;
 #[cfg(feature = "ws")]
 pub mod ws;
 
 pub use client::{Client, Config};
+#[cfg(feature = "ws")]
+pub use order_manager::OrderManager;
+pub use positions::PositionBook;
+#[cfg(feature = "ws")]
+pub use tick_size_sync::TickSizeSync;
@@ -0,0 +1,217 @@
+//! On-chain order nonces and mass invalidation through the exchange contracts.
+//!
+//! Every order carries a nonce, and the exchange contracts only accept orders whose nonce equals
+//! the maker's current nonce on that exchange. Incrementing it on-chain with `incrementNonce`
+//! invalidates every order signed with the previous one at once, without going through the CLOB
+//! API. This makes it a kill switch that works even when the API is unreachable.
+//!
+//! A [`NonceManager`] reads the maker's nonces on the regular and the neg risk exchange, and
+//! records them on its [`Client`]. From then on, [`Client::sign`] stamps the matching nonce on
+//! every order built without an explicit one, so orders stay valid after an invalidation.
+//!
+//! **Feature flag:** `ctf`, for the contract bindings and providers
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::str::FromStr as _;
+//!
+//! use alloy::providers::ProviderBuilder;
+//! use polymarket_client_sdk::POLYGON;
+//! use polymarket_client_sdk::auth::{LocalSigner, Signer as _};
+//! use polymarket_client_sdk::clob::nonce_manager::NonceManager;
+//! use polymarket_client_sdk::clob::{Client, Config};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let signer = LocalSigner::from_str("0x...")?.with_chain_id(Some(POLYGON));
+//! let client = Client::new("https://clob.polymarket.com", Config::default())?
+//!     .authentication_builder(&signer)
+//!     .authenticate()
+//!     .await?;
+//!
+//! // The provider must send transactions from the signer's wallet
+//! let provider = ProviderBuilder::new()
+//!     .wallet(signer)
+//!     .connect("https://polygon-rpc.com")
+//!     .await?;
+//! let nonces = NonceManager::new(client.clone(), provider, POLYGON)?;
+//! nonces.sync().await?;
+//!
+//! // Later, cancel every open order without the CLOB API
+//! let [regular, neg_risk] = nonces.invalidate_all_onchain().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+#![allow(
+    clippy::exhaustive_structs,
+    clippy::exhaustive_enums,
+    reason = "Alloy sol! macro generates code that triggers these lints"
+)]
+
+use alloy::network::Ethereum;
+use alloy::primitives::ChainId;
+use alloy::providers::{PendingTransactionBuilder, Provider};
+use alloy::sol;
+
+use super::Client;
+use super::types::SignatureType;
+use crate::auth::state::Authenticated;
+use crate::auth::{Kind, Normal};
+use crate::error::{Error, Kind as ErrorKind};
+use crate::types::{Address, B256, U256};
+use crate::{Result, contract_config};
+
+// Nonce functions of the CTF exchange contract, shared by the regular and the neg risk exchange.
+//
+// Source: https://github.com/Polymarket/ctf-exchange/blob/main/src/exchange/mixins/NonceManager.sol
+sol! {
+    #[sol(rpc)]
+    interface IExchangeNonces {
+        /// Current nonce of `user`. Orders are only valid with this exact nonce.
+        function nonces(address user) external view returns (uint256);
+
+        /// Increments the nonce of the sender, invalidating all of their orders signed with the
+        /// current one.
+        function incrementNonce() external;
+    }
+}
+
+/// Nonce increment on one exchange contract.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Invalidation {
+    /// Exchange contract whose nonce was incremented
+    pub exchange: Address,
+    /// Hash of the `incrementNonce` transaction
+    pub transaction_hash: B256,
+    /// Nonce of the maker on `exchange` after the transaction
+    pub nonce: U256,
+}
+
+/// Tracks the on-chain order nonces of a [`Client`]'s maker and invalidates them on demand.
+#[derive(Debug)]
+pub struct NonceManager<P: Provider, K: Kind = Normal> {
+    client: Client<Authenticated<K>>,
+    exchanges: [IExchangeNonces::IExchangeNoncesInstance<P>; 2],
+}
+
+impl<P: Provider + Clone, K: Kind> NonceManager<P, K> {
+    /// Creates a manager reading and incrementing nonces through `provider` on `chain_id`.
+    ///
+    /// To invalidate orders, `provider` must send transactions from the wallet of `client`'s
+    /// signer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the exchange contracts are not configured for `chain_id`.
+    pub fn new(client: Client<Authenticated<K>>, provider: P, chain_id: ChainId) -> Result<Self> {
+        let exchange = |neg_risk| {
+            contract_config(chain_id, neg_risk)
+                .map(|config| IExchangeNonces::new(config.exchange, provider.clone()))
+                .ok_or(Error::missing_contract_config(chain_id, neg_risk))
+        };
+
+        Ok(Self {
+            exchanges: [exchange(false)?, exchange(true)?],
+            client,
+        })
+    }
+
+    /// Reads the maker's current nonce on each exchange and records it on the client, so that
+    /// orders signed from now on carry it. Returns the nonces of the regular and the neg risk
+    /// exchange, in that order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a contract call fails.
+    pub async fn sync(&self) -> Result<[U256; 2]> {
+        let [regular, neg_risk] = &self.exchanges;
+
+        Ok([
+            self.sync_one(regular).await?,
+            self.sync_one(neg_risk).await?,
+        ])
+    }
+
+    /// Nonce recorded on the client for the regular or the neg risk exchange, if it was synced.
+    #[must_use]
+    pub fn nonce(&self, neg_risk: bool) -> Option<U256> {
+        let exchange = &self.exchanges[usize::from(neg_risk)];
+
+        self.client.nonce(*exchange.address())
+    }
+
+    /// Increments the maker's nonce on both exchanges, invalidating every order signed with the
+    /// current nonces, then records the new nonces on the client. Returns the outcome on the
+    /// regular and the neg risk exchange, in that order.
+    ///
+    /// Both transactions are sent before waiting for either to be mined, and each exchange is
+    /// handled independently, so a transaction that failed to be sent or mined on one exchange
+    /// does not hide the one sent to the other. Orders remain matchable until the transaction for
+    /// their exchange is mined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the maker is a proxy or Safe wallet, whose nonce can only be
+    /// incremented by a transaction from the wallet itself. Each exchange's result is an error if
+    /// its transaction fails to be sent or mined.
+    pub async fn invalidate_all_onchain(&self) -> Result<[Result<Invalidation>; 2]> {
+        if self.client.signature_type() != SignatureType::Eoa {
+            return Err(Error::validation(
+                "On-chain invalidation requires an EOA maker. Proxy and Safe wallets must call \
+                incrementNonce from the wallet",
+            ));
+        }
+
+        let [regular, neg_risk] = &self.exchanges;
+        let regular_tx = send_increment(regular).await;
+        let neg_risk_tx = send_increment(neg_risk).await;
+
+        Ok([
+            self.confirm(regular, regular_tx).await,
+            self.confirm(neg_risk, neg_risk_tx).await,
+        ])
+    }
+
+    async fn confirm(
+        &self,
+        exchange: &IExchangeNonces::IExchangeNoncesInstance<P>,
+        tx: Result<PendingTransactionBuilder<Ethereum>>,
+    ) -> Result<Invalidation> {
+        let transaction_hash = tx?
+            .watch()
+            .await
+            .map_err(|e| Error::with_source(ErrorKind::Internal, e))?;
+
+        Ok(Invalidation {
+            exchange: *exchange.address(),
+            transaction_hash,
+            nonce: self.sync_one(exchange).await?,
+        })
+    }
+
+    async fn sync_one(
+        &self,
+        exchange: &IExchangeNonces::IExchangeNoncesInstance<P>,
+    ) -> Result<U256> {
+        let nonce = exchange
+            .nonces(self.client.maker())
+            .call()
+            .await
+            .map_err(|e| Error::with_source(ErrorKind::Internal, e))?;
+        self.client.set_nonce(*exchange.address(), nonce);
+
+        Ok(nonce)
+    }
+}
+
+async fn send_increment<P: Provider>(
+    exchange: &IExchangeNonces::IExchangeNoncesInstance<P>,
+) -> Result<PendingTransactionBuilder<Ethereum>> {
+    exchange
+        .incrementNonce()
+        .send()
+        .await
+        .map_err(|e| Error::with_source(ErrorKind::Internal, e))
+}
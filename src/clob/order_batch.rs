@@ -0,0 +1,279 @@
+//! Ladders of limit orders built and validated together.
+//!
+//! An [`OrderBatchBuilder`], created by [`Client::order_ladder`], generates a grid of limit orders
+//! on one token from a [`Ladder`] per side and builds them all against a single tick size and fee
+//! rate, looked up once. Validation is atomic: if any order of the grid is invalid, e.g. because a
+//! level falls off the price range or the bid and ask ladders cross, none is returned.
+//!
+//! The orders can then be signed concurrently with [`Client::sign_orders`] and placed with
+//! [`Client::post_order_batches`], which splits them into requests of at most
+//! [`MAX_ORDERS_PER_BATCH`](crate::clob::client::MAX_ORDERS_PER_BATCH) orders.
+//!
+//! # Examples
+//!
+//! ```rust, no_run
+//! use std::str::FromStr as _;
+//!
+//! use polymarket_client_sdk::POLYGON;
+//! use polymarket_client_sdk::auth::{LocalSigner, Signer as _};
+//! use polymarket_client_sdk::clob::order_batch::Ladder;
+//! use polymarket_client_sdk::clob::{Client, Config};
+//! use polymarket_client_sdk::types::{U256, dec};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let signer = LocalSigner::from_str("0x...")?.with_chain_id(Some(POLYGON));
+//! let client = Client::new("https://clob.polymarket.com", Config::default())?
+//!     .authentication_builder(&signer)
+//!     .authenticate()
+//!     .await?;
+//!
+//! // Ten bids from 0.48 down to 0.39 and ten asks from 0.52 up to 0.61, one tick apart, sized
+//! // 20 shares at the top and 10% more at each level further out
+//! let ladder = Ladder::builder()
+//!     .levels(10)
+//!     .size(dec!(20))
+//!     .skew(dec!(0.1));
+//! let orders = client
+//!     .order_ladder()
+//!     .token_id(U256::from(1))
+//!     .bids(ladder.clone().start(dec!(0.48)).build())
+//!     .asks(ladder.start(dec!(0.52)).build())
+//!     .build()
+//!     .await?;
+//!
+//! let signed = client.sign_orders(&signer, orders).await?;
+//! for batch in client.post_order_batches(signed).await? {
+//!     let responses = batch?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use bon::Builder;
+use chrono::{DateTime, Utc};
+
+use crate::Result;
+use crate::auth::Kind as AuthKind;
+use crate::auth::state::Authenticated;
+use crate::clob::Client;
+use crate::clob::order_builder::LOT_SIZE_SCALE;
+use crate::clob::types::{MarketParams, OrderType, Side, SignableOrder, TickSize};
+use crate::error::Error;
+use crate::types::{Decimal, U256};
+
+/// Levels of a ladder on one side of the book.
+///
+/// The first level is the closest to the spread. Each following level is [`Self::spacing`] ticks
+/// further away from it: lower for bids, higher for asks.
+#[non_exhaustive]
+#[derive(Clone, Debug, Builder)]
+#[builder(derive(Clone))]
+pub struct Ladder {
+    /// Price of the first level
+    pub start: Decimal,
+    /// Number of orders in the ladder
+    pub levels: u32,
+    /// Size of the first level, in shares
+    pub size: Decimal,
+    /// Ticks between consecutive levels. Defaults to 1.
+    #[builder(default = 1)]
+    pub spacing: u32,
+    /// Relative size change from one level to the next, so that level `i` has
+    /// `size * (1 + skew)^i` shares, truncated to the lot size. Defaults to 0, the same size at
+    /// every level.
+    #[builder(default)]
+    pub skew: Decimal,
+}
+
+impl Ladder {
+    /// Price and size of every level, best price first.
+    fn grid(&self, side: Side, tick_size: Decimal) -> Result<Vec<(Decimal, Decimal)>> {
+        if self.levels == 0 {
+            return Err(Error::validation("Ladder must have at least one level"));
+        }
+        if self.skew <= -Decimal::ONE {
+            return Err(Error::validation(format!(
+                "Ladder skew {} must be greater than -1",
+                self.skew
+            )));
+        }
+
+        let step = tick_size * Decimal::from(self.spacing);
+        let growth = Decimal::ONE + self.skew;
+        let mut price = self.start;
+        let mut size = self.size;
+        let mut grid = Vec::new();
+
+        for _ in 0..self.levels {
+            grid.push((price, size.trunc_with_scale(LOT_SIZE_SCALE)));
+
+            price = match side {
+                Side::Buy => price - step,
+                _ => price + step,
+            };
+            size *= growth;
+        }
+
+        Ok(grid)
+    }
+}
+
+/// Builds a ladder of limit orders on one token. Created by [`Client::order_ladder`].
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "OrderBatchBuilder mirrors the naming of OrderBuilder"
+)]
+#[derive(Debug)]
+pub struct OrderBatchBuilder<K: AuthKind> {
+    pub(crate) client: Client<Authenticated<K>>,
+    pub(crate) token_id: Option<U256>,
+    pub(crate) bids: Option<Ladder>,
+    pub(crate) asks: Option<Ladder>,
+    pub(crate) order_type: Option<OrderType>,
+    pub(crate) post_only: Option<bool>,
+    pub(crate) expiration: Option<DateTime<Utc>>,
+}
+
+impl<K: AuthKind> OrderBatchBuilder<K> {
+    /// Sets the `token_id` of every order. This is a required field.
+    #[must_use]
+    pub fn token_id(mut self, token_id: U256) -> Self {
+        self.token_id = Some(token_id);
+        self
+    }
+
+    /// Sets the ladder of buy orders.
+    #[must_use]
+    pub fn bids(mut self, ladder: Ladder) -> Self {
+        self.bids = Some(ladder);
+        self
+    }
+
+    /// Sets the ladder of sell orders.
+    #[must_use]
+    pub fn asks(mut self, ladder: Ladder) -> Self {
+        self.asks = Some(ladder);
+        self
+    }
+
+    /// Sets the [`OrderType`] of every order. Defaults to [`OrderType::GTC`].
+    #[must_use]
+    pub fn order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = Some(order_type);
+        self
+    }
+
+    /// Sets the `postOnly` flag of every order.
+    #[must_use]
+    pub fn post_only(mut self, post_only: bool) -> Self {
+        self.post_only = Some(post_only);
+        self
+    }
+
+    /// Sets the expiration of every order, for [`OrderType::GTD`] orders.
+    #[must_use]
+    pub fn expiration(mut self, expiration: DateTime<Utc>) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+
+    /// Validates and transforms every level into a [`SignableOrder`], bids first, looking up the
+    /// fee rate and tick size of the token once unless they are cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lookups fail or any order is invalid, in which case no order is
+    /// returned.
+    pub async fn build(self) -> Result<Vec<SignableOrder>> {
+        let token_id = self.required()?;
+
+        let fee_rate_bps = self.client.fee_rate_bps(token_id).await?.base_fee;
+        let tick_size = self.client.tick_size(token_id).await?.minimum_tick_size;
+
+        self.build_orders(tick_size, fee_rate_bps)
+    }
+
+    /// Like [`Self::build`], using `params` instead of looking them up, without any network
+    /// access.
+    ///
+    /// `params.neg_risk` is carried by the returned orders, so that [`Client::sign_orders`] does
+    /// not look it up either.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any order is invalid, in which case no order is returned.
+    pub fn build_offline(self, params: &MarketParams) -> Result<Vec<SignableOrder>> {
+        let mut orders = self.build_orders(params.tick_size, params.fee_rate_bps)?;
+        for order in &mut orders {
+            order.neg_risk = Some(params.neg_risk);
+        }
+
+        Ok(orders)
+    }
+
+    fn required(&self) -> Result<U256> {
+        let Some(token_id) = self.token_id else {
+            return Err(Error::validation(
+                "Unable to build Orders due to missing token ID",
+            ));
+        };
+
+        if self.bids.is_none() && self.asks.is_none() {
+            return Err(Error::validation(
+                "Unable to build Orders due to missing bid and ask ladders",
+            ));
+        }
+
+        Ok(token_id)
+    }
+
+    fn build_orders(self, tick_size: TickSize, fee_rate_bps: u32) -> Result<Vec<SignableOrder>> {
+        let token_id = self.required()?;
+        let tick = tick_size.as_decimal();
+
+        let bids = self
+            .bids
+            .as_ref()
+            .map(|ladder| ladder.grid(Side::Buy, tick))
+            .transpose()?
+            .unwrap_or_default();
+        let asks = self
+            .asks
+            .as_ref()
+            .map(|ladder| ladder.grid(Side::Sell, tick))
+            .transpose()?
+            .unwrap_or_default();
+
+        if let (Some((bid, _)), Some((ask, _))) = (bids.first(), asks.first())
+            && bid >= ask
+        {
+            return Err(Error::validation(format!(
+                "Bid ladder at {bid} crosses ask ladder at {ask}"
+            )));
+        }
+
+        let levels = bids
+            .into_iter()
+            .map(|level| (Side::Buy, level))
+            .chain(asks.into_iter().map(|level| (Side::Sell, level)));
+
+        levels
+            .map(|(side, (price, size))| {
+                let mut order = self
+                    .client
+                    .limit_order()
+                    .token_id(token_id)
+                    .side(side)
+                    .price(price)
+                    .size(size);
+                order.order_type.clone_from(&self.order_type);
+                if let Some(post_only) = self.post_only {
+                    order.post_only = Some(post_only);
+                }
+                order.expiration = self.expiration;
+
+                order.build_order(tick_size, fee_rate_bps)
+            })
+            .collect()
+    }
+}
@@ -1,19 +1,30 @@
+use std::fmt;
 use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use alloy::primitives::U256;
 use chrono::{DateTime, Utc};
-use rand::RngExt as _;
+use rand::rngs::StdRng;
+use rand::{RngExt as _, SeedableRng as _};
 use rust_decimal::prelude::ToPrimitive as _;
 
 use crate::Result;
 use crate::auth::Kind as AuthKind;
 use crate::auth::state::Authenticated;
 use crate::clob::Client;
+use crate::clob::analytics::impact;
 use crate::clob::types::request::OrderBookSummaryRequest;
+use crate::clob::types::response::{OrderBookSummaryResponse, OrderSummary};
 use crate::clob::types::{
-    Amount, AmountInner, Order, OrderType, Side, SignableOrder, SignatureType,
+    Amount, AmountInner, MarketParams, Order, OrderType, Side, SignableOrder, SignatureType,
+    TickSize,
 };
+#[cfg(feature = "ws")]
+use crate::clob::ws::LocalOrderBook;
+#[cfg(feature = "ws")]
+use crate::clob::ws::types::response::OrderBookLevel;
 use crate::error::Error;
 use crate::types::{Address, Decimal};
 
@@ -38,7 +49,7 @@ pub struct OrderBuilder<OrderKind, K: AuthKind> {
     pub(crate) client: Client<Authenticated<K>>,
     pub(crate) signer: Address,
     pub(crate) signature_type: SignatureType,
-    pub(crate) salt_generator: fn() -> u64,
+    pub(crate) salt_generator: Arc<dyn SaltGenerator>,
     pub(crate) token_id: Option<U256>,
     pub(crate) price: Option<Decimal>,
     pub(crate) size: Option<Decimal>,
@@ -50,6 +61,8 @@ pub struct OrderBuilder<OrderKind, K: AuthKind> {
     pub(crate) order_type: Option<OrderType>,
     pub(crate) post_only: Option<bool>,
     pub(crate) funder: Option<Address>,
+    pub(crate) book: Option<PricingBook>,
+    pub(crate) price_limit: Option<Decimal>,
     pub(crate) _kind: PhantomData<OrderKind>,
 }
 
@@ -68,7 +81,8 @@ impl<OrderKind, K: AuthKind> OrderBuilder<OrderKind, K> {
         self
     }
 
-    /// Sets the nonce for this builder.
+    /// Sets the nonce for this builder, which is then used as is. Defaults to 0, or with the `ctf`
+    /// feature, to the nonce synced by a `NonceManager` when the order is signed.
     #[must_use]
     pub fn nonce(mut self, nonce: u64) -> Self {
         self.nonce = Some(nonce);
@@ -116,12 +130,39 @@ impl<K: AuthKind> OrderBuilder<Limit, K> {
         self
     }
 
-    /// Validates and transforms this limit builder into a [`SignableOrder`]
+    /// Validates and transforms this limit builder into a [`SignableOrder`], looking up the fee
+    /// rate and tick size of the token unless they are cached.
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(skip(self), err(level = "warn"))
     )]
     pub async fn build(self) -> Result<SignableOrder> {
+        let (token_id, ..) = self.required()?;
+
+        let fee_rate_bps = self.client.fee_rate_bps(token_id).await?.base_fee;
+        let tick_size = self.client.tick_size(token_id).await?.minimum_tick_size;
+
+        self.build_order(tick_size, fee_rate_bps)
+    }
+
+    /// Validates and transforms this limit builder into a [`SignableOrder`] using `params`
+    /// instead of looking them up, without any network access.
+    ///
+    /// `params.neg_risk` is carried by the returned order, so that [`Client::sign`] does not look
+    /// it up either.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(self), err(level = "warn"))
+    )]
+    pub fn build_offline(self, params: &MarketParams) -> Result<SignableOrder> {
+        let mut order = self.build_order(params.tick_size, params.fee_rate_bps)?;
+        order.neg_risk = Some(params.neg_risk);
+
+        Ok(order)
+    }
+
+    /// The token ID, side and price of the order, which must be set before anything is looked up.
+    fn required(&self) -> Result<(U256, Side, Decimal)> {
         let Some(token_id) = self.token_id else {
             return Err(Error::validation(
                 "Unable to build Order due to missing token ID",
@@ -146,13 +187,16 @@ impl<K: AuthKind> OrderBuilder<Limit, K> {
             )));
         }
 
-        let fee_rate = self.client.fee_rate_bps(token_id).await?;
-        let minimum_tick_size = self
-            .client
-            .tick_size(token_id)
-            .await?
-            .minimum_tick_size
-            .as_decimal();
+        Ok((token_id, side, price))
+    }
+
+    pub(crate) fn build_order(
+        self,
+        tick_size: TickSize,
+        fee_rate_bps: u32,
+    ) -> Result<SignableOrder> {
+        let (token_id, side, price) = self.required()?;
+        let minimum_tick_size = tick_size.as_decimal();
 
         let decimals = minimum_tick_size.scale();
 
@@ -190,6 +234,7 @@ impl<K: AuthKind> OrderBuilder<Limit, K> {
             )));
         }
 
+        let managed_nonce = self.nonce.is_none();
         let nonce = self.nonce.unwrap_or(0);
         let expiration = self.expiration.unwrap_or(DateTime::<Utc>::UNIX_EPOCH);
         let taker = self.taker.unwrap_or(Address::ZERO);
@@ -229,7 +274,7 @@ impl<K: AuthKind> OrderBuilder<Limit, K> {
             side => return Err(Error::validation(format!("Invalid side: {side}"))),
         };
 
-        let salt = to_ieee_754_int((self.salt_generator)());
+        let salt = to_ieee_754_int(self.salt_generator.generate());
 
         let order = Order {
             salt: U256::from(salt),
@@ -239,7 +284,7 @@ impl<K: AuthKind> OrderBuilder<Limit, K> {
             makerAmount: U256::from(to_fixed_u128(maker_amount)),
             takerAmount: U256::from(to_fixed_u128(taker_amount)),
             side: side as u8,
-            feeRateBps: U256::from(fee_rate.base_fee),
+            feeRateBps: U256::from(fee_rate_bps),
             nonce: U256::from(nonce),
             signer: self.signer,
             expiration: U256::from(expiration.timestamp().to_u64().ok_or(Error::validation(
@@ -255,6 +300,8 @@ impl<K: AuthKind> OrderBuilder<Limit, K> {
             order,
             order_type,
             post_only,
+            neg_risk: None,
+            managed_nonce,
         })
     }
 }
@@ -274,12 +321,87 @@ impl<K: AuthKind> OrderBuilder<Market, K> {
         self
     }
 
+    /// Prices this order against `book` instead of fetching the order book when it is built.
+    ///
+    /// The levels are expected in the order the API returns them, best price last.
+    #[must_use]
+    pub fn order_book(mut self, book: &OrderBookSummaryResponse) -> Self {
+        self.book = Some(PricingBook::from_summary(book));
+        self
+    }
+
+    /// Prices this order against a book maintained from the market channel instead of fetching
+    /// the order book when it is built.
+    #[cfg(feature = "ws")]
+    #[must_use]
+    pub fn local_order_book(mut self, book: &LocalOrderBook) -> Self {
+        self.book = Some(PricingBook::from_local(book));
+        self
+    }
+
+    /// Caps the slippage of this order: the highest price a buy may pay, or the lowest price a
+    /// sell may accept. Levels beyond the limit are ignored when pricing the order from the book,
+    /// and an explicit [`price`](Self::price) beyond it is rejected.
+    #[must_use]
+    pub fn price_limit(mut self, price_limit: Decimal) -> Self {
+        self.price_limit = Some(price_limit);
+        self
+    }
+
+    /// The price of this order: the explicit one if set, otherwise the cutoff price from the held
+    /// book, fetching it if needed.
+    async fn resolve_price(&self, token_id: U256, order_type: &OrderType) -> Result<Decimal> {
+        if self.price.is_some() || self.book.is_some() {
+            return self.resolve_price_offline(order_type);
+        }
+
+        let book = self
+            .client
+            .order_book(&OrderBookSummaryRequest {
+                token_id,
+                side: None,
+            })
+            .await?;
+
+        self.calculate_price(&PricingBook::from_summary(&book), order_type)
+    }
+
+    /// Like [`Self::resolve_price`], without fetching the book.
+    fn resolve_price_offline(&self, order_type: &OrderType) -> Result<Decimal> {
+        match (self.price, &self.book) {
+            (Some(price), _) => {
+                self.check_price_limit(price)?;
+                Ok(price)
+            }
+            (None, Some(book)) => self.calculate_price(book, order_type),
+            (None, None) => Err(Error::validation(
+                "Unable to build Order offline due to missing price or order book",
+            )),
+        }
+    }
+
+    fn check_price_limit(&self, price: Decimal) -> Result<()> {
+        let (Some(side), Some(limit)) = (self.side, self.price_limit) else {
+            return Ok(());
+        };
+
+        if !impact::within_limit(side, price, Some(limit)) {
+            return Err(Error::validation(format!(
+                "Price {price} is beyond the price limit {limit}"
+            )));
+        }
+
+        Ok(())
+    }
+
     // Attempts to calculate the market price from the top of the book for the particular token.
     // - Uses an orderbook depth search to find the cutoff price:
     //   - BUY + USDC: walk asks until notional >= USDC
     //   - BUY + Shares: walk asks until shares >= N
     //   - SELL + Shares: walk bids until shares >= N
-    async fn calculate_price(&self, order_type: OrderType) -> Result<Decimal> {
+    // - Stops at the price limit, if any, as if the book ended there.
+    // - Prices the order at the last level taken, see `impact::walk`.
+    fn calculate_price(&self, book: &PricingBook, order_type: &OrderType) -> Result<Decimal> {
         let token_id = self
             .token_id
             .expect("Token ID was already validated in `build`");
@@ -289,13 +411,12 @@ impl<K: AuthKind> OrderBuilder<Market, K> {
             .as_ref()
             .expect("Amount was already validated in `build`");
 
-        let book = self
-            .client
-            .order_book(&OrderBookSummaryRequest {
-                token_id,
-                side: None,
-            })
-            .await?;
+        if book.asset_id != token_id {
+            return Err(Error::validation(format!(
+                "Order book is for {}, not for the order's token {token_id}",
+                book.asset_id
+            )));
+        }
 
         if !matches!(order_type, OrderType::FAK | OrderType::FOK) {
             return Err(Error::validation(
@@ -304,9 +425,9 @@ impl<K: AuthKind> OrderBuilder<Market, K> {
         }
 
         let (levels, amount) = match side {
-            Side::Buy => (book.asks, amount.0),
+            Side::Buy => (&book.asks, amount.0),
             Side::Sell => match amount.0 {
-                a @ AmountInner::Shares(_) => (book.bids, a),
+                a @ AmountInner::Shares(_) => (&book.bids, a),
                 AmountInner::Usdc(_) => {
                     return Err(Error::validation(
                         "Sell Orders must specify their `amount`s in shares",
@@ -317,35 +438,83 @@ impl<K: AuthKind> OrderBuilder<Market, K> {
             side => return Err(Error::validation(format!("Invalid side: {side}"))),
         };
 
-        let first = levels.first().ok_or(Error::validation(format!(
-            "No opposing orders for {token_id} which means there is no market price"
-        )))?;
-
-        let mut sum = Decimal::ZERO;
-        let cutoff_price = levels.iter().rev().find_map(|level| {
-            match amount {
-                AmountInner::Usdc(_) => sum += level.size * level.price,
-                AmountInner::Shares(_) => sum += level.size,
-            }
-            (sum >= amount.as_inner()).then_some(level.price)
-        });
+        let fill = impact::walk(
+            levels
+                .iter()
+                .take_while(|level| impact::within_limit(side, level.price, self.price_limit)),
+            amount,
+        );
+
+        let Some(worst_price) = fill.worst_price else {
+            return Err(match self.price_limit {
+                Some(limit) if !book.is_empty(side) => Error::validation(format!(
+                    "No opposing orders for {token_id} within the price limit {limit}"
+                )),
+                _ => Error::validation(format!(
+                    "No opposing orders for {token_id} which means there is no market price"
+                )),
+            });
+        };
 
-        match cutoff_price {
-            Some(price) => Ok(price),
-            None if matches!(order_type, OrderType::FOK) => Err(Error::validation(format!(
+        if !fill.remaining.is_zero() && matches!(order_type, OrderType::FOK) {
+            return Err(Error::validation(format!(
                 "Insufficient liquidity to fill order for {token_id} at {}",
                 amount.as_inner()
-            ))),
-            None => Ok(first.price),
+            )));
         }
+
+        Ok(worst_price)
     }
 
-    /// Validates and transforms this market builder into a [`SignableOrder`]
+    /// Validates and transforms this market builder into a [`SignableOrder`], looking up the tick
+    /// size and fee rate of the token unless they are cached. If neither a price nor an
+    /// [`order_book`](Self::order_book) was set, the order book is fetched to find the price at
+    /// which the order would fill.
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(skip(self), err(level = "warn"))
     )]
     pub async fn build(self) -> Result<SignableOrder> {
+        let (token_id, ..) = self.required()?;
+
+        let price = self
+            .resolve_price(token_id, &self.order_type_or_default())
+            .await?;
+
+        let tick_size = self.client.tick_size(token_id).await?.minimum_tick_size;
+        let fee_rate_bps = self.client.fee_rate_bps(token_id).await?.base_fee;
+
+        self.build_order(price, tick_size, fee_rate_bps)
+    }
+
+    /// Validates and transforms this market builder into a [`SignableOrder`] using `params`
+    /// instead of looking them up, without any network access. Either the price or the
+    /// [`order_book`](Self::order_book) to price the order against must have been set, since the
+    /// order book cannot be fetched.
+    ///
+    /// `params.neg_risk` is carried by the returned order, so that [`Client::sign`] does not look
+    /// it up either.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(self), err(level = "warn"))
+    )]
+    pub fn build_offline(self, params: &MarketParams) -> Result<SignableOrder> {
+        self.required()?;
+
+        let price = self.resolve_price_offline(&self.order_type_or_default())?;
+        let mut order = self.build_order(price, params.tick_size, params.fee_rate_bps)?;
+        order.neg_risk = Some(params.neg_risk);
+
+        Ok(order)
+    }
+
+    fn order_type_or_default(&self) -> OrderType {
+        self.order_type.clone().unwrap_or(OrderType::FAK)
+    }
+
+    /// The token ID, side and amount of the order, which must be set before anything is looked
+    /// up.
+    fn required(&self) -> Result<(U256, Side, Amount)> {
         let Some(token_id) = self.token_id else {
             return Err(Error::validation(
                 "Unable to build Order due to missing token ID",
@@ -362,28 +531,27 @@ impl<K: AuthKind> OrderBuilder<Market, K> {
             .amount
             .ok_or_else(|| Error::validation("Unable to build Order due to missing amount"))?;
 
-        let nonce = self.nonce.unwrap_or(0);
-        let taker = self.taker.unwrap_or(Address::ZERO);
-
-        let order_type = self.order_type.clone().unwrap_or(OrderType::FAK);
-        let post_only = self.post_only;
-        if post_only == Some(true) {
+        if self.post_only == Some(true) {
             return Err(Error::validation(
                 "postOnly is only supported for limit orders",
             ));
         }
-        let price = match self.price {
-            Some(price) => price,
-            None => self.calculate_price(order_type.clone()).await?,
-        };
 
-        let minimum_tick_size = self
-            .client
-            .tick_size(token_id)
-            .await?
-            .minimum_tick_size
-            .as_decimal();
-        let fee_rate = self.client.fee_rate_bps(token_id).await?;
+        Ok((token_id, side, amount))
+    }
+
+    fn build_order(
+        self,
+        price: Decimal,
+        tick_size: TickSize,
+        fee_rate_bps: u32,
+    ) -> Result<SignableOrder> {
+        let (token_id, side, amount) = self.required()?;
+        let managed_nonce = self.nonce.is_none();
+        let nonce = self.nonce.unwrap_or(0);
+        let taker = self.taker.unwrap_or(Address::ZERO);
+        let order_type = self.order_type_or_default();
+        let minimum_tick_size = tick_size.as_decimal();
 
         let decimals = minimum_tick_size.scale();
 
@@ -441,7 +609,7 @@ impl<K: AuthKind> OrderBuilder<Market, K> {
             (side, _) => return Err(Error::validation(format!("Invalid side: {side}"))),
         };
 
-        let salt = to_ieee_754_int((self.salt_generator)());
+        let salt = to_ieee_754_int(self.salt_generator.generate());
 
         let order = Order {
             salt: U256::from(salt),
@@ -451,7 +619,7 @@ impl<K: AuthKind> OrderBuilder<Market, K> {
             makerAmount: U256::from(to_fixed_u128(maker_amount)),
             takerAmount: U256::from(to_fixed_u128(taker_amount)),
             side: side as u8,
-            feeRateBps: U256::from(fee_rate.base_fee),
+            feeRateBps: U256::from(fee_rate_bps),
             nonce: U256::from(nonce),
             signer: self.signer,
             expiration: U256::ZERO,
@@ -465,10 +633,53 @@ impl<K: AuthKind> OrderBuilder<Market, K> {
             order,
             order_type,
             post_only: None,
+            neg_risk: None,
+            managed_nonce,
         })
     }
 }
 
+/// Order book a market order is priced against, with the levels of each side best price first.
+#[derive(Clone, Debug)]
+pub(crate) struct PricingBook {
+    asset_id: U256,
+    bids: Vec<OrderSummary>,
+    asks: Vec<OrderSummary>,
+}
+
+impl PricingBook {
+    /// The API lists both sides of the book best price last.
+    fn from_summary(book: &OrderBookSummaryResponse) -> Self {
+        Self {
+            asset_id: book.asset_id,
+            bids: book.bids.iter().rev().cloned().collect(),
+            asks: book.asks.iter().rev().cloned().collect(),
+        }
+    }
+
+    #[cfg(feature = "ws")]
+    fn from_local(book: &LocalOrderBook) -> Self {
+        let summary = |level: OrderBookLevel| OrderSummary {
+            price: level.price,
+            size: level.size,
+        };
+
+        Self {
+            asset_id: book.asset_id(),
+            bids: book.bids(usize::MAX).into_iter().map(summary).collect(),
+            asks: book.asks(usize::MAX).into_iter().map(summary).collect(),
+        }
+    }
+
+    /// Whether the side of the book a `side` order would take from is empty.
+    fn is_empty(&self, side: Side) -> bool {
+        match side {
+            Side::Buy => self.asks.is_empty(),
+            _ => self.bids.is_empty(),
+        }
+    }
+}
+
 /// Removes trailing zeros, truncates to [`USDC_DECIMALS`] decimal places, and quanitizes as an
 /// integer.
 fn to_fixed_u128(d: Decimal) -> u128 {
@@ -484,33 +695,130 @@ fn to_ieee_754_int(salt: u64) -> u64 {
     salt & ((1 << 53) - 1)
 }
 
-#[must_use]
-#[expect(
-    clippy::float_arithmetic,
-    reason = "We are not concerned with precision for the seed"
-)]
-#[expect(
-    clippy::cast_possible_truncation,
-    reason = "We are not concerned with truncation for a seed"
-)]
-#[expect(clippy::cast_sign_loss, reason = "We only need positive integers")]
-pub(crate) fn generate_seed() -> u64 {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("time went backwards");
-
-    let seconds = now.as_secs_f64();
-    let rand = rand::rng().random::<f64>();
-
-    (seconds * rand).round() as u64
+/// Generates the salt of each order, which makes otherwise identical orders distinct.
+///
+/// Salts are masked to 53 bits before use, as the CLOB parses them as IEEE 754 doubles. Any
+/// `Fn() -> u64` closure can be used as a generator.
+pub trait SaltGenerator: Send + Sync {
+    /// Returns the salt for the next order.
+    fn generate(&self) -> u64;
+}
+
+impl<F: Fn() -> u64 + Send + Sync> SaltGenerator for F {
+    fn generate(&self) -> u64 {
+        self()
+    }
+}
+
+impl fmt::Debug for dyn SaltGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SaltGenerator")
+    }
+}
+
+/// Draws every salt from the thread-local CSPRNG. This is the default generator.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomSalt;
+
+impl SaltGenerator for RandomSalt {
+    fn generate(&self) -> u64 {
+        rand::rng().random()
+    }
+}
+
+/// Counts up from a starting salt, so that no two orders signed through it share a salt.
+#[derive(Debug)]
+pub struct CounterSalt(AtomicU64);
+
+impl CounterSalt {
+    /// Creates a counter whose first salt is `start`.
+    #[must_use]
+    pub const fn starting_at(start: u64) -> Self {
+        Self(AtomicU64::new(start))
+    }
+}
+
+impl Default for CounterSalt {
+    /// Starts at the current Unix time in microseconds, so that counters created by successive
+    /// runs do not overlap.
+    fn default() -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time went backwards");
+
+        Self::starting_at(u64::try_from(now.as_micros()).unwrap_or(u64::MAX))
+    }
+}
+
+impl SaltGenerator for CounterSalt {
+    fn generate(&self) -> u64 {
+        self.0.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+/// Draws salts from a PRNG seeded by the caller, so that a sequence of orders can be reproduced.
+#[derive(Debug)]
+pub struct SeededSalt(Mutex<StdRng>);
+
+impl SeededSalt {
+    /// Creates a generator that yields the same salts for the same `seed`.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self(Mutex::new(StdRng::seed_from_u64(seed)))
+    }
+}
+
+impl SaltGenerator for SeededSalt {
+    fn generate(&self) -> u64 {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .random()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::iter;
+
     use rust_decimal_macros::dec;
 
     use super::*;
 
+    #[test]
+    fn counter_salt_should_count_up() {
+        let salts = CounterSalt::starting_at(7);
+
+        assert_eq!(salts.generate(), 7);
+        assert_eq!(salts.generate(), 8);
+        assert_eq!(salts.generate(), 9);
+    }
+
+    #[test]
+    fn seeded_salt_should_be_reproducible() {
+        let first = SeededSalt::new(42);
+        let second = SeededSalt::new(42);
+
+        let sequence: Vec<_> = iter::repeat_with(|| first.generate()).take(3).collect();
+        let replayed: Vec<_> = iter::repeat_with(|| second.generate()).take(3).collect();
+        assert_eq!(sequence, replayed);
+        assert_ne!(sequence[0], sequence[1]);
+        assert_ne!(
+            SeededSalt::new(43).generate(),
+            sequence[0],
+            "different seeds should yield different salts"
+        );
+    }
+
+    #[test]
+    fn closures_should_be_salt_generators() {
+        let salts: Arc<dyn SaltGenerator> = Arc::new(|| 5);
+
+        assert_eq!(salts.generate(), 5);
+        assert_eq!(format!("{salts:?}"), "SaltGenerator");
+    }
+
     #[test]
     fn to_fixed_u128_should_succeed() {
         assert_eq!(to_fixed_u128(dec!(123.456)), 123_456_000);
@@ -0,0 +1,1092 @@
+//! Order lifecycle tracking across REST responses and the user channel.
+//!
+//! An [`OrderManager`] follows every order from the moment it is posted until it reaches a final
+//! [`OrderState`]. It starts tracking an order from the [`PostOrderResponse`] returned by
+//! [`OrderManager::post_order`], then applies the [`OrderMessage`] and [`TradeMessage`] events of
+//! the user channel as they arrive: placements, partial fills, full fills and cancellations.
+//!
+//! The user channel may drop messages while it reconnects, so after every reconnect (and after
+//! the stream lags) the manager resyncs its state from [`Client::orders`], [`Client::order`] and
+//! [`Client::trades`]. Applying the same event twice has no effect, so REST and WebSocket updates
+//! can overlap freely.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::str::FromStr as _;
+//!
+//! use polymarket_client_sdk::POLYGON;
+//! use polymarket_client_sdk::auth::{LocalSigner, Signer as _};
+//! use polymarket_client_sdk::clob::order_manager::OrderManager;
+//! use polymarket_client_sdk::clob::types::Side;
+//! use polymarket_client_sdk::clob::{Client, Config, ws};
+//! use polymarket_client_sdk::types::{Decimal, U256};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let signer = LocalSigner::from_str("0x...")?.with_chain_id(Some(POLYGON));
+//! let client = Client::new("https://clob.polymarket.com", Config::default())?
+//!     .authentication_builder(&signer)
+//!     .authenticate()
+//!     .await?;
+//! let ws = ws::Client::default().authenticate(client.credentials(), client.address())?;
+//!
+//! let mut manager = OrderManager::new(client.clone());
+//! manager.listen(&ws, Vec::new())?;
+//!
+//! let order = client
+//!     .limit_order()
+//!     .token_id(U256::from_str("1234")?)
+//!     .price(Decimal::from_str("0.5")?)
+//!     .size(Decimal::TEN)
+//!     .side(Side::Buy)
+//!     .build()
+//!     .await?;
+//! let response = manager.post_order(client.sign(&signer, order).await?).await?;
+//!
+//! let mut updates = manager.subscribe();
+//! while let Ok(order) = updates.recv().await {
+//!     if order.id == response.order_id && order.is_final() {
+//!         println!("{:?} at {:?}", order.state, order.average_fill_price());
+//!         break;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use futures::StreamExt as _;
+use futures::TryStreamExt as _;
+use futures::stream::BoxStream;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+
+use super::Client;
+use super::types::request::{OrdersRequest, TradesRequest};
+use super::types::response::{OpenOrderResponse, PostOrderResponse, TradeResponse};
+use super::types::{OrderStatusType, Side, SignedOrder, TradeStatusType, TraderSide};
+use super::ws::types::response::OrderMessageType;
+use super::ws::{self, ChannelType, OrderMessage, TradeMessage, WsMessage};
+use crate::Result;
+use crate::auth::state::Authenticated;
+use crate::auth::{ApiKey, Kind, Normal};
+use crate::error::Error;
+use crate::types::{B256, Decimal, U256};
+use crate::ws::connection::ConnectionState;
+
+const UPDATES_CAPACITY: usize = 1024;
+
+/// Where an order is in its lifecycle.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OrderState {
+    /// Posted, but not yet acknowledged as resting on the book
+    Pending,
+    /// Resting on the book without any fills
+    Live,
+    /// Resting on the book with some of its size matched
+    PartiallyFilled,
+    /// Entirely matched. Reopened if a trade that matched it fails
+    Filled,
+    /// Canceled before being entirely matched
+    Canceled,
+    /// Canceled by the exchange upon reaching its expiration
+    Expired,
+    /// Rejected by the exchange when posted
+    Rejected,
+}
+
+impl OrderState {
+    /// Whether no further updates are expected for an order in this state.
+    #[must_use]
+    pub const fn is_final(self) -> bool {
+        matches!(
+            self,
+            Self::Filled | Self::Canceled | Self::Expired | Self::Rejected
+        )
+    }
+}
+
+/// A single trade that (partially) filled a tracked order.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fill {
+    /// Trade identifier
+    pub trade_id: String,
+    /// Price the order was matched at
+    pub price: Decimal,
+    /// Size of the order matched by this trade
+    pub size: Decimal,
+    /// Settlement status of the trade
+    pub status: TradeStatusType,
+}
+
+/// The manager's view of a single order.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackedOrder {
+    /// Order identifier
+    pub id: String,
+    /// Market condition ID, once known
+    pub market: Option<B256>,
+    /// Asset/token identifier
+    pub asset_id: U256,
+    /// Side of the order, [`Side::Unknown`] until the order itself has been seen
+    pub side: Side,
+    /// Limit price of the order
+    pub price: Decimal,
+    /// Original size of the order, zero until the order itself has been seen
+    pub original_size: Decimal,
+    /// Size matched so far
+    pub size_matched: Decimal,
+    /// Current lifecycle state
+    pub state: OrderState,
+    /// When the order expires, if it was placed with an expiration
+    pub expiration: Option<DateTime<Utc>>,
+    /// Trades that matched this order, in the order they were first seen
+    pub fills: Vec<Fill>,
+    /// Reason given by the exchange when the order was rejected
+    pub error_msg: Option<String>,
+    /// Matched size last reported by the exchange
+    reported_matched: Decimal,
+    /// Size of the fills that failed after being counted as matched, taken back from
+    /// `reported_matched`
+    reversed: Decimal,
+}
+
+impl TrackedOrder {
+    /// Whether no further updates are expected for this order.
+    #[must_use]
+    pub const fn is_final(&self) -> bool {
+        self.state.is_final()
+    }
+
+    /// Size still open on the book.
+    #[must_use]
+    pub fn remaining_size(&self) -> Decimal {
+        (self.original_size - self.size_matched).max(Decimal::ZERO)
+    }
+
+    /// Size filled by trades that have not failed.
+    #[must_use]
+    pub fn filled_size(&self) -> Decimal {
+        self.settled_fills().map(|fill| fill.size).sum()
+    }
+
+    /// Size-weighted average price of the fills that have not failed, or `None` without fills.
+    #[must_use]
+    pub fn average_fill_price(&self) -> Option<Decimal> {
+        let size = self.filled_size();
+        if size.is_zero() {
+            return None;
+        }
+
+        let notional: Decimal = self
+            .settled_fills()
+            .map(|fill| fill.price * fill.size)
+            .sum();
+        Some((notional / size).normalize())
+    }
+
+    fn settled_fills(&self) -> impl Iterator<Item = &Fill> {
+        self.fills
+            .iter()
+            .filter(|fill| fill.status != TradeStatusType::Failed)
+    }
+
+    /// Builds an order from a signed order before it is posted. The `id` is set once the exchange
+    /// acknowledges it.
+    fn from_signed(order: &SignedOrder) -> Self {
+        let expiration = u64::try_from(order.order.expiration)
+            .ok()
+            .and_then(|seconds| i64::try_from(seconds).ok())
+            .and_then(expiration_from_timestamp);
+
+        Self {
+            price: order.price(),
+            original_size: order.size(),
+            side: order.side(),
+            expiration,
+            ..Self::new(String::new(), order.order.tokenId)
+        }
+    }
+
+    /// An order about which nothing but its identifiers is known yet.
+    fn new(id: String, asset_id: U256) -> Self {
+        Self {
+            id,
+            market: None,
+            asset_id,
+            side: Side::Unknown,
+            price: Decimal::ZERO,
+            original_size: Decimal::ZERO,
+            size_matched: Decimal::ZERO,
+            state: OrderState::Pending,
+            expiration: None,
+            fills: Vec::new(),
+            error_msg: None,
+            reported_matched: Decimal::ZERO,
+            reversed: Decimal::ZERO,
+        }
+    }
+
+    fn apply_details(&mut self, details: &Self) {
+        self.asset_id = details.asset_id;
+        self.side = details.side;
+        self.price = details.price;
+        self.original_size = details.original_size;
+        self.expiration = details.expiration;
+    }
+
+    fn apply_post_response(&mut self, response: &PostOrderResponse) {
+        if !response.success {
+            self.state = OrderState::Rejected;
+            self.error_msg.clone_from(&response.error_msg);
+            return;
+        }
+
+        self.apply_status(&response.status);
+    }
+
+    fn apply_order_message(&mut self, message: &OrderMessage) {
+        self.market = Some(message.market);
+        self.asset_id = message.asset_id;
+        self.side = message.side;
+        self.price = message.price;
+        if let Some(original_size) = message.original_size {
+            self.original_size = original_size;
+        }
+        if let Some(size_matched) = message.size_matched {
+            self.reported_matched = self.reported_matched.max(size_matched);
+        }
+
+        match message.msg_type {
+            Some(OrderMessageType::Cancellation) => self.cancel(),
+            Some(OrderMessageType::Placement | OrderMessageType::Update) => self.go_live(),
+            _ => {}
+        }
+        if let Some(status) = &message.status {
+            self.apply_status(status);
+        }
+    }
+
+    fn apply_open_order(&mut self, order: &OpenOrderResponse) {
+        self.market = Some(order.market);
+        self.asset_id = order.asset_id;
+        self.side = order.side;
+        self.price = order.price;
+        self.original_size = order.original_size;
+        self.reported_matched = self.reported_matched.max(order.size_matched);
+        self.expiration = expiration_from_timestamp(order.expiration.timestamp());
+
+        self.apply_status(&order.status);
+    }
+
+    /// Applies an order status reported by the exchange.
+    ///
+    /// Only a cancellation is final: `matched` does not say how much of the order matched, and
+    /// `unmatched` is a successful placement that did not cross, so whether the order is filled is
+    /// left to [`Self::refresh_state`], from its matched and original sizes.
+    fn apply_status(&mut self, status: &OrderStatusType) {
+        match status {
+            OrderStatusType::Live | OrderStatusType::Matched | OrderStatusType::Unmatched => {
+                self.go_live();
+            }
+            OrderStatusType::Canceled => self.cancel(),
+            _ => {}
+        }
+    }
+
+    /// Records a fill, or updates the status of a fill that was already recorded. A fill that
+    /// fails after being counted as matched takes its size back.
+    fn record_fill(
+        &mut self,
+        trade_id: &str,
+        price: Decimal,
+        size: Decimal,
+        status: TradeStatusType,
+    ) {
+        if let Some(fill) = self.fills.iter_mut().find(|fill| fill.trade_id == trade_id) {
+            match (&fill.status, &status) {
+                (TradeStatusType::Failed, TradeStatusType::Failed) => {}
+                (_, TradeStatusType::Failed) => self.reversed += fill.size,
+                (TradeStatusType::Failed, _) => self.reversed -= fill.size,
+                _ => {}
+            }
+            fill.status = status;
+        } else {
+            self.fills.push(Fill {
+                trade_id: trade_id.to_owned(),
+                price,
+                size,
+                status,
+            });
+        }
+    }
+
+    fn go_live(&mut self) {
+        if self.state == OrderState::Pending {
+            self.state = OrderState::Live;
+        }
+    }
+
+    fn cancel(&mut self) {
+        let expired = self
+            .expiration
+            .is_some_and(|expiration| expiration <= Utc::now());
+        self.finish(if expired {
+            OrderState::Expired
+        } else {
+            OrderState::Canceled
+        });
+    }
+
+    fn finish(&mut self, state: OrderState) {
+        if !self.is_final() {
+            self.state = state;
+        }
+    }
+
+    /// Derives the matched size and the fill state, unless the order is already final. A filled
+    /// order is reopened when a failed trade takes back some of its matched size.
+    fn refresh_state(&mut self) {
+        self.size_matched = (self.reported_matched - self.reversed)
+            .max(self.filled_size())
+            .max(Decimal::ZERO);
+
+        if self.is_final() && self.state != OrderState::Filled {
+            return;
+        }
+        if !self.original_size.is_zero() && self.size_matched >= self.original_size {
+            self.state = OrderState::Filled;
+        } else if !self.size_matched.is_zero() {
+            self.state = OrderState::PartiallyFilled;
+        } else if self.state == OrderState::Filled {
+            self.state = OrderState::Live;
+        }
+    }
+}
+
+/// Tracks the lifecycle of orders by reconciling REST responses with user channel events.
+///
+/// Orders posted through [`Self::post_order`] or [`Self::post_orders`] are tracked from the
+/// exchange's response onwards. Orders placed elsewhere are picked up from the user channel, or by
+/// [`Self::track`] and [`Self::resync`]. Call [`Self::listen`] to apply user channel events as they
+/// arrive; events from another source can be applied with [`Self::apply_order_message`] and
+/// [`Self::apply_trade_message`].
+///
+/// Dropping the manager stops the background task started by [`Self::listen`].
+#[derive(Debug)]
+pub struct OrderManager<K: Kind = Normal> {
+    client: Client<Authenticated<K>>,
+    registry: Arc<Registry>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl<K: Kind> OrderManager<K> {
+    /// Creates a manager placing and resyncing orders through `client`.
+    #[must_use]
+    pub fn new(client: Client<Authenticated<K>>) -> Self {
+        let registry = Registry {
+            orders: DashMap::new(),
+            updates: broadcast::Sender::new(UPDATES_CAPACITY),
+        };
+
+        Self {
+            client,
+            registry: Arc::new(registry),
+            task: None,
+        }
+    }
+
+    /// Applies the events of the user channel for `markets` (or every market, if empty) in the
+    /// background, resyncing from REST whenever the channel reconnects or the stream lags.
+    ///
+    /// Replaces the background task of any previous call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the subscription cannot be created.
+    pub fn listen<W: Kind>(
+        &mut self,
+        ws: &ws::Client<Authenticated<W>>,
+        markets: Vec<B256>,
+    ) -> Result<()> {
+        let stream = ws.subscribe_user_events(markets.clone())?.boxed();
+        let state = ws
+            .connection_state_receiver(ChannelType::User)
+            .ok_or(Error::validation("User channel is not initialized"))?;
+
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.task = Some(tokio::spawn(run(
+            Arc::clone(&self.registry),
+            self.client.clone(),
+            ws.clone(),
+            markets,
+            stream,
+            state,
+        )));
+
+        Ok(())
+    }
+
+    /// Posts `order` and starts tracking it.
+    pub async fn post_order(&self, order: SignedOrder) -> Result<PostOrderResponse> {
+        let details = TrackedOrder::from_signed(&order);
+        let response = self.client.post_order(order).await?;
+
+        self.registry.track(&details, &response);
+
+        Ok(response)
+    }
+
+    /// Posts `orders` and starts tracking each of them.
+    pub async fn post_orders(&self, orders: Vec<SignedOrder>) -> Result<Vec<PostOrderResponse>> {
+        let details: Vec<_> = orders.iter().map(TrackedOrder::from_signed).collect();
+        let responses = self.client.post_orders(orders).await?;
+
+        for (details, response) in details.iter().zip(&responses) {
+            self.registry.track(details, response);
+        }
+
+        Ok(responses)
+    }
+
+    /// Starts tracking an order placed elsewhere, fetching its current state from the exchange.
+    pub async fn track(&self, order_id: &str) -> Result<TrackedOrder> {
+        let order = self.client.order(order_id).await?;
+
+        Ok(self.registry.apply_open_order(&order))
+    }
+
+    /// Reconciles every tracked order with the exchange.
+    ///
+    /// Fetches all open orders, the final state of tracked orders that are no longer open, and the
+    /// trades made since the oldest tracked order that was still open.
+    pub async fn resync(&self) -> Result<()> {
+        resync(&self.registry, &self.client).await
+    }
+
+    /// Applies an order event from the user channel.
+    pub fn apply_order_message(&self, message: &OrderMessage) {
+        self.registry.apply_order_message(message);
+    }
+
+    /// Applies a trade event from the user channel.
+    pub fn apply_trade_message(&self, message: &TradeMessage) {
+        self.registry
+            .apply_trade_message(message, self.client.state().api_key());
+    }
+
+    /// Current state of the order with `order_id`, if tracked.
+    #[must_use]
+    pub fn order(&self, order_id: &str) -> Option<TrackedOrder> {
+        self.registry
+            .orders
+            .get(order_id)
+            .map(|order| order.value().clone())
+    }
+
+    /// Every tracked order, including the ones in a final state.
+    #[must_use]
+    pub fn orders(&self) -> Vec<TrackedOrder> {
+        self.registry
+            .orders
+            .iter()
+            .map(|order| order.value().clone())
+            .collect()
+    }
+
+    /// Tracked orders that have not reached a final state.
+    #[must_use]
+    pub fn open_orders(&self) -> Vec<TrackedOrder> {
+        self.registry
+            .orders
+            .iter()
+            .filter(|order| !order.is_final())
+            .map(|order| order.value().clone())
+            .collect()
+    }
+
+    /// Stops tracking every order in a final state.
+    pub fn remove_final(&self) {
+        self.registry.orders.retain(|_, order| !order.is_final());
+    }
+
+    /// Subscribes to order updates. Every change to a tracked order is sent as a snapshot of the
+    /// order after the change.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<TrackedOrder> {
+        self.registry.updates.subscribe()
+    }
+}
+
+impl<K: Kind> Drop for OrderManager<K> {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+}
+
+/// Tracked orders, shared between an [`OrderManager`] and its background task.
+#[derive(Debug)]
+struct Registry {
+    orders: DashMap<String, TrackedOrder>,
+    updates: broadcast::Sender<TrackedOrder>,
+}
+
+impl Registry {
+    /// Applies `update` to the order with `id`, inserting `init()` first if it is not tracked,
+    /// and publishes the order if it changed.
+    fn upsert<I, U>(&self, id: &str, init: I, update: U) -> TrackedOrder
+    where
+        I: FnOnce() -> TrackedOrder,
+        U: FnOnce(&mut TrackedOrder),
+    {
+        let (mut order, before) = match self.orders.entry(id.to_owned()) {
+            Entry::Occupied(entry) => {
+                let before = entry.get().clone();
+                (entry.into_ref(), Some(before))
+            }
+            Entry::Vacant(entry) => (entry.insert(init()), None),
+        };
+
+        update(&mut order);
+        order.refresh_state();
+
+        let order = order.clone();
+        if before.as_ref() != Some(&order) {
+            // Sending only fails when nobody is subscribed
+            let _: std::result::Result<_, _> = self.updates.send(order.clone());
+        }
+
+        order
+    }
+
+    fn track(&self, details: &TrackedOrder, response: &PostOrderResponse) {
+        if response.order_id.is_empty() {
+            return;
+        }
+
+        self.upsert(
+            &response.order_id,
+            || TrackedOrder::new(response.order_id.clone(), details.asset_id),
+            |order| {
+                order.apply_details(details);
+                order.apply_post_response(response);
+            },
+        );
+    }
+
+    fn apply_order_message(&self, message: &OrderMessage) {
+        self.upsert(
+            &message.id,
+            || TrackedOrder::new(message.id.clone(), message.asset_id),
+            |order| order.apply_order_message(message),
+        );
+    }
+
+    fn apply_open_order(&self, order: &OpenOrderResponse) -> TrackedOrder {
+        self.upsert(
+            &order.id,
+            || TrackedOrder::new(order.id.clone(), order.asset_id),
+            |tracked| tracked.apply_open_order(order),
+        )
+    }
+
+    /// Applies the account's side of `trade`, telling it from the counterparty's by `api_key`, the
+    /// current key of the account.
+    fn apply_trade_message(&self, trade: &TradeMessage, api_key: ApiKey) {
+        let status = TradeStatusType::from(&trade.status);
+
+        if let Some(taker_order_id) = &trade.taker_order_id {
+            // The trade owner is set on the maker's copy of a trade too, only the side tells whose
+            // order the taker order is
+            self.record_fill(
+                taker_order_id,
+                trade.trader_side == Some(TraderSide::Taker),
+                trade.market,
+                trade.asset_id,
+                Fill {
+                    trade_id: trade.id.clone(),
+                    price: trade.price,
+                    size: trade.size,
+                    status: status.clone(),
+                },
+            );
+        }
+
+        for maker in &trade.maker_orders {
+            self.record_fill(
+                &maker.order_id,
+                maker.owner == api_key,
+                trade.market,
+                maker.asset_id,
+                Fill {
+                    trade_id: trade.id.clone(),
+                    price: maker.price,
+                    size: maker.matched_amount,
+                    status: status.clone(),
+                },
+            );
+        }
+    }
+
+    /// Same as [`Self::apply_trade_message`], for a trade fetched from REST.
+    fn apply_trade_response(&self, trade: &TradeResponse, api_key: ApiKey) {
+        self.record_fill(
+            &trade.taker_order_id,
+            trade.trader_side == TraderSide::Taker,
+            trade.market,
+            trade.asset_id,
+            Fill {
+                trade_id: trade.id.clone(),
+                price: trade.price,
+                size: trade.size,
+                status: trade.status.clone(),
+            },
+        );
+
+        for maker in &trade.maker_orders {
+            self.record_fill(
+                &maker.order_id,
+                maker.owner == api_key,
+                trade.market,
+                maker.asset_id,
+                Fill {
+                    trade_id: trade.id.clone(),
+                    price: maker.price,
+                    size: maker.matched_amount,
+                    status: trade.status.clone(),
+                },
+            );
+        }
+    }
+
+    /// Records `fill` against the order with `order_id` if it is tracked or `owned` by the
+    /// account. Trades list the counterparties' orders too, which are ignored.
+    fn record_fill(&self, order_id: &str, owned: bool, market: B256, asset_id: U256, fill: Fill) {
+        if !owned && !self.orders.contains_key(order_id) {
+            return;
+        }
+
+        self.upsert(
+            order_id,
+            || TrackedOrder::new(order_id.to_owned(), asset_id),
+            |order| {
+                order.market.get_or_insert(market);
+                order.record_fill(&fill.trade_id, fill.price, fill.size, fill.status);
+            },
+        );
+    }
+}
+
+async fn run<K: Kind, W: Kind>(
+    registry: Arc<Registry>,
+    client: Client<Authenticated<K>>,
+    ws: ws::Client<Authenticated<W>>,
+    markets: Vec<B256>,
+    mut stream: BoxStream<'static, Result<WsMessage>>,
+    mut state: watch::Receiver<ConnectionState>,
+) {
+    let mut interrupted = false;
+
+    loop {
+        tokio::select! {
+            message = stream.next() => match message {
+                Some(Ok(WsMessage::Order(message))) => registry.apply_order_message(&message),
+                Some(Ok(WsMessage::Trade(message))) => {
+                    // Read on every event, as the key changes when the credentials are rotated
+                    registry.apply_trade_message(&message, client.state().api_key());
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %e, "User channel stream interrupted, resyncing orders");
+                    #[cfg(not(feature = "tracing"))]
+                    let _: &_ = &e;
+
+                    // Subscribing before unsubscribing keeps the reference count above zero, so no
+                    // request is sent to the server and the connection stays open.
+                    let Ok(next) = ws.subscribe_user_events(markets.clone()) else {
+                        return;
+                    };
+                    if ws.unsubscribe_user_events(&markets).is_err() {
+                        return;
+                    }
+                    stream = next.boxed();
+
+                    resync_logged(&registry, &client).await;
+                }
+                None => return,
+            },
+            Ok(()) = state.changed() => {
+                let connected = state.borrow_and_update().is_connected();
+                if !connected {
+                    interrupted = true;
+                } else if interrupted {
+                    interrupted = false;
+                    resync_logged(&registry, &client).await;
+                }
+            }
+        }
+    }
+}
+
+async fn resync_logged<K: Kind>(registry: &Registry, client: &Client<Authenticated<K>>) {
+    if let Err(e) = resync(registry, client).await {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = %e, "Failed to resync orders");
+        #[cfg(not(feature = "tracing"))]
+        let _: &_ = &e;
+    }
+}
+
+async fn resync<K: Kind>(registry: &Registry, client: &Client<Authenticated<K>>) -> Result<()> {
+    let pending: HashSet<String> = registry
+        .orders
+        .iter()
+        .filter(|order| !order.is_final())
+        .map(|order| order.key().clone())
+        .collect();
+
+    let request = OrdersRequest::default();
+    let open: Vec<OpenOrderResponse> = client
+        .stream_data(|client, cursor| client.orders(&request, cursor))
+        .try_collect()
+        .await?;
+
+    // Fills may have been missed for any order that was pending, so trades are fetched from the
+    // creation of the oldest one
+    let mut oldest: Option<i64> = None;
+    let mut open_ids = HashSet::with_capacity(open.len());
+    for order in &open {
+        if pending.contains(&order.id) {
+            let created_at = order.created_at.timestamp();
+            oldest = Some(oldest.map_or(created_at, |oldest| oldest.min(created_at)));
+        }
+        open_ids.insert(order.id.as_str());
+        registry.apply_open_order(order);
+    }
+
+    // Orders that are no longer open have been filled, canceled or expired in the meantime
+    for id in pending.iter().filter(|id| !open_ids.contains(id.as_str())) {
+        let order = client.order(id).await?;
+        let created_at = order.created_at.timestamp();
+        oldest = Some(oldest.map_or(created_at, |oldest| oldest.min(created_at)));
+        registry.apply_open_order(&order);
+    }
+
+    let Some(after) = oldest else {
+        return Ok(());
+    };
+    let request = TradesRequest::builder().after(after).build();
+    let trades: Vec<TradeResponse> = client
+        .stream_data(|client, cursor| client.trades(&request, cursor))
+        .try_collect()
+        .await?;
+    for trade in &trades {
+        registry.apply_trade_response(trade, client.state().api_key());
+    }
+
+    Ok(())
+}
+
+/// Orders placed without an expiration report it as zero.
+fn expiration_from_timestamp(seconds: i64) -> Option<DateTime<Utc>> {
+    if seconds == 0 {
+        return None;
+    }
+
+    DateTime::from_timestamp(seconds, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::POLYGON;
+    use crate::clob::types::{Order, OrderType};
+    use crate::types::{Signature, dec};
+
+    const ORDER_ID: &str = "0xorder";
+    const MARKET: &str = "0x5f65177b394277fd294cd75650044e32ba009a95022d88a0c1d565897d72f8f1";
+    const ASSET_ID: &str =
+        "15871154585880608648532107628464183779895785213830018178010423617714102767076";
+
+    fn registry() -> Registry {
+        Registry {
+            orders: DashMap::new(),
+            updates: broadcast::Sender::new(UPDATES_CAPACITY),
+        }
+    }
+
+    fn signed_order(side: Side, maker_amount: u64, taker_amount: u64) -> SignedOrder {
+        SignedOrder {
+            order: Order {
+                tokenId: U256::from(1),
+                makerAmount: U256::from(maker_amount),
+                takerAmount: U256::from(taker_amount),
+                side: side as u8,
+                ..Order::default()
+            },
+            signature: Signature::new(U256::ZERO, U256::ZERO, false),
+            order_type: OrderType::GTC,
+            owner: ApiKey::nil(),
+            post_only: None,
+            chain_id: POLYGON,
+            neg_risk: false,
+        }
+    }
+
+    fn order_message(msg_type: &str, size_matched: &str) -> OrderMessage {
+        serde_json::from_value(json!({
+            "event_type": "order",
+            "id": ORDER_ID,
+            "market": MARKET,
+            "asset_id": ASSET_ID,
+            "side": "BUY",
+            "price": "0.5",
+            "type": msg_type,
+            "original_size": "10",
+            "size_matched": size_matched,
+        }))
+        .unwrap()
+    }
+
+    fn trade_message(id: &str, status: &str, maker_order_id: &str, owner: ApiKey) -> TradeMessage {
+        serde_json::from_value(json!({
+            "event_type": "trade",
+            "id": id,
+            "market": MARKET,
+            "asset_id": ASSET_ID,
+            "side": "SELL",
+            "size": "4",
+            "price": "0.5",
+            "status": status,
+            "taker_order_id": "0xtaker",
+            "trader_side": "MAKER",
+            "maker_orders": [
+                {
+                    "asset_id": ASSET_ID,
+                    "matched_amount": "4",
+                    "order_id": maker_order_id,
+                    "outcome": "Yes",
+                    "owner": owner,
+                    "price": "0.5",
+                }
+            ],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn from_signed_derives_price_and_size() {
+        let buy = TrackedOrder::from_signed(&signed_order(Side::Buy, 5_700_000, 10_000_000));
+        assert_eq!(buy.side, Side::Buy);
+        assert_eq!(buy.price, dec!(0.57));
+        assert_eq!(buy.original_size, dec!(10));
+        assert_eq!(buy.expiration, None);
+
+        let sell = TrackedOrder::from_signed(&signed_order(Side::Sell, 10_000_000, 4_200_000));
+        assert_eq!(sell.side, Side::Sell);
+        assert_eq!(sell.price, dec!(0.42));
+        assert_eq!(sell.original_size, dec!(10));
+    }
+
+    #[test]
+    fn order_messages_drive_lifecycle() {
+        let registry = registry();
+        let mut updates = registry.updates.subscribe();
+
+        registry.apply_order_message(&order_message("PLACEMENT", "0"));
+        let order = registry.orders.get(ORDER_ID).unwrap().clone();
+        assert_eq!(order.state, OrderState::Live);
+        assert_eq!(order.remaining_size(), dec!(10));
+        assert_eq!(updates.try_recv().unwrap(), order);
+
+        registry.apply_order_message(&order_message("UPDATE", "4"));
+        registry.apply_order_message(&order_message("UPDATE", "4"));
+        let order = registry.orders.get(ORDER_ID).unwrap().clone();
+        assert_eq!(order.state, OrderState::PartiallyFilled);
+        assert_eq!(order.size_matched, dec!(4));
+        assert_eq!(updates.try_recv().unwrap(), order);
+        assert!(
+            updates.try_recv().is_err(),
+            "an unchanged order must not be published"
+        );
+
+        registry.apply_order_message(&order_message("CANCELLATION", "4"));
+        let order = registry.orders.get(ORDER_ID).unwrap().clone();
+        assert_eq!(order.state, OrderState::Canceled);
+        assert!(order.is_final(), "canceled orders are final");
+
+        registry.apply_order_message(&order_message("UPDATE", "10"));
+        assert_eq!(
+            registry.orders.get(ORDER_ID).unwrap().state,
+            OrderState::Canceled,
+            "final states never change"
+        );
+    }
+
+    #[test]
+    fn unmatched_placement_stays_open() {
+        let mut order = TrackedOrder::new(ORDER_ID.to_owned(), U256::from(1));
+
+        order.apply_status(&OrderStatusType::Unmatched);
+        order.refresh_state();
+
+        assert_eq!(order.state, OrderState::Live);
+        assert!(!order.is_final(), "unmatched orders are still open");
+    }
+
+    #[test]
+    fn partially_matched_order_stays_open_until_canceled() {
+        let registry = registry();
+        registry.apply_order_message(&order_message("PLACEMENT", "0"));
+        registry
+            .orders
+            .get_mut(ORDER_ID)
+            .unwrap()
+            .apply_status(&OrderStatusType::Matched);
+        registry.apply_order_message(&order_message("UPDATE", "4"));
+
+        let order = registry.orders.get(ORDER_ID).unwrap().clone();
+        assert_eq!(order.state, OrderState::PartiallyFilled);
+
+        registry.apply_order_message(&order_message("UPDATE", "10"));
+        assert_eq!(
+            registry.orders.get(ORDER_ID).unwrap().state,
+            OrderState::Filled
+        );
+    }
+
+    #[test]
+    fn partially_matched_order_can_be_canceled() {
+        let registry = registry();
+        registry.apply_order_message(&order_message("PLACEMENT", "4"));
+        registry
+            .orders
+            .get_mut(ORDER_ID)
+            .unwrap()
+            .apply_status(&OrderStatusType::Matched);
+        registry.apply_order_message(&order_message("CANCELLATION", "4"));
+
+        let order = registry.orders.get(ORDER_ID).unwrap().clone();
+        assert_eq!(order.state, OrderState::Canceled);
+        assert_eq!(order.size_matched, dec!(4));
+    }
+
+    #[test]
+    fn trade_owner_does_not_make_the_taker_order_ours() {
+        let registry = registry();
+        let mut trade = trade_message("trade-1", "MATCHED", "0xother", ApiKey::max());
+        trade.trade_owner = Some(ApiKey::nil());
+
+        registry.apply_trade_message(&trade, ApiKey::nil());
+
+        assert!(
+            !registry.orders.contains_key("0xtaker"),
+            "the taker order belongs to the counterparty"
+        );
+    }
+
+    #[test]
+    fn cancellation_after_expiration_is_expired() {
+        let mut order = TrackedOrder::new(ORDER_ID.to_owned(), U256::from(1));
+        order.expiration = DateTime::from_timestamp(1, 0);
+
+        order.apply_order_message(&order_message("CANCELLATION", "0"));
+
+        assert_eq!(order.state, OrderState::Expired);
+    }
+
+    #[test]
+    fn trade_messages_record_own_fills_once() {
+        let registry = registry();
+        registry.apply_order_message(&order_message("PLACEMENT", "0"));
+
+        // Counterparty orders listed in a trade are not tracked
+        registry.apply_trade_message(
+            &trade_message("trade-0", "MATCHED", "0xother", ApiKey::max()),
+            ApiKey::nil(),
+        );
+        assert!(!registry.orders.contains_key("0xother"));
+
+        registry.apply_trade_message(
+            &trade_message("trade-1", "MATCHED", ORDER_ID, ApiKey::nil()),
+            ApiKey::nil(),
+        );
+        registry.apply_trade_message(
+            &trade_message("trade-1", "MINED", ORDER_ID, ApiKey::nil()),
+            ApiKey::nil(),
+        );
+        registry.apply_trade_message(
+            &trade_message("trade-2", "MATCHED", ORDER_ID, ApiKey::nil()),
+            ApiKey::nil(),
+        );
+
+        let order = registry.orders.get(ORDER_ID).unwrap().clone();
+        assert_eq!(order.fills.len(), 2);
+        assert_eq!(order.fills[0].status, TradeStatusType::Mined);
+        assert_eq!(order.filled_size(), dec!(8));
+        assert_eq!(order.size_matched, dec!(8));
+        assert_eq!(order.average_fill_price(), Some(dec!(0.5)));
+        assert_eq!(order.state, OrderState::PartiallyFilled);
+
+        registry.apply_trade_message(
+            &trade_message("trade-2", "FAILED", ORDER_ID, ApiKey::nil()),
+            ApiKey::nil(),
+        );
+        let order = registry.orders.get(ORDER_ID).unwrap().clone();
+        assert_eq!(order.fills[1].status, TradeStatusType::Failed);
+        assert_eq!(order.filled_size(), dec!(4));
+        assert_eq!(order.size_matched, dec!(4));
+    }
+
+    #[test]
+    fn failed_trade_should_take_back_its_matched_size() {
+        let registry = registry();
+        registry.apply_order_message(&order_message("PLACEMENT", "0"));
+
+        for trade_id in ["trade-1", "trade-2"] {
+            registry.apply_trade_message(
+                &trade_message(trade_id, "MATCHED", ORDER_ID, ApiKey::nil()),
+                ApiKey::nil(),
+            );
+        }
+        // The exchange also reports both trades in the order's matched size
+        registry.apply_order_message(&order_message("UPDATE", "8"));
+        registry.apply_trade_message(
+            &trade_message("trade-3", "MATCHED", ORDER_ID, ApiKey::nil()),
+            ApiKey::nil(),
+        );
+        let order = registry.orders.get(ORDER_ID).unwrap().clone();
+        assert_eq!(order.size_matched, dec!(12));
+        assert_eq!(order.state, OrderState::Filled);
+
+        registry.apply_trade_message(
+            &trade_message("trade-2", "FAILED", ORDER_ID, ApiKey::nil()),
+            ApiKey::nil(),
+        );
+        // A repeated status does not take the size back twice
+        registry.apply_trade_message(
+            &trade_message("trade-2", "FAILED", ORDER_ID, ApiKey::nil()),
+            ApiKey::nil(),
+        );
+
+        let order = registry.orders.get(ORDER_ID).unwrap().clone();
+        assert_eq!(order.size_matched, dec!(8));
+        assert_eq!(order.remaining_size(), dec!(2));
+        assert_eq!(order.state, OrderState::PartiallyFilled);
+    }
+}
@@ -0,0 +1,744 @@
+//! Positions and profit and loss derived from the account's own fills.
+//!
+//! A [`PositionBook`] consumes trades, either as [`TradeMessage`]s from the user channel or as
+//! [`TradeResponse`]s from [`Client::trades`], and keeps the net position of every token using
+//! average cost accounting:
+//!
+//! - buying into a position raises its size and blends the fill price into the average cost,
+//! - selling out of a position realizes `(price - average cost) * size` and leaves the average
+//!   cost of the remainder unchanged.
+//!
+//! Unrealized profit and loss is marked to a price per token, typically the midpoint, set with
+//! [`PositionBook::set_mark`] or fetched with [`PositionBook::mark_to_midpoints`].
+//!
+//! Each trade is only counted once, however many status updates arrive for it. When a trade
+//! fails to settle, the positions it touched are rebuilt from the remaining fills. Fees are not
+//! accounted for.
+//!
+//! With the `data` feature, [`PositionBook::reconcile`] compares the book against the positions
+//! reported by the [data API](crate::data) and returns every [`PositionDrift`].
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use super::Client;
+use super::types::request::MidpointRequest;
+use super::types::response::TradeResponse;
+use super::types::{Side, TradeStatusType, TraderSide};
+#[cfg(feature = "ws")]
+use super::ws::TradeMessage;
+use crate::Result;
+use crate::auth::ApiKey;
+use crate::auth::state::State;
+#[cfg(feature = "data")]
+use crate::data;
+#[cfg(feature = "data")]
+use crate::data::types::response::Position;
+#[cfg(feature = "data")]
+use crate::types::Address;
+use crate::types::{B256, Decimal, U256};
+
+/// Size difference below which a local and a remote position are considered equal.
+#[cfg(feature = "data")]
+const DEFAULT_DRIFT_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 6);
+
+#[cfg(feature = "data")]
+const POSITIONS_PAGE_SIZE: i32 = 500;
+#[cfg(feature = "data")]
+const POSITIONS_MAX_OFFSET: i32 = 10_000;
+
+/// Net position in a single token.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenPosition {
+    /// Asset/token identifier
+    pub asset_id: U256,
+    /// Market condition ID
+    pub market: B256,
+    /// Net number of shares held. Negative only if sells were seen without the buys before them.
+    pub size: Decimal,
+    /// Average price paid per share of the current size
+    pub average_cost: Decimal,
+    /// Profit and loss realized by reducing the position
+    pub realized_pnl: Decimal,
+    /// Price the position is marked to, if any
+    pub mark: Option<Decimal>,
+}
+
+impl TokenPosition {
+    fn new(asset_id: U256, market: B256) -> Self {
+        Self {
+            asset_id,
+            market,
+            size: Decimal::ZERO,
+            average_cost: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            mark: None,
+        }
+    }
+
+    /// Amount paid for the current size.
+    #[must_use]
+    pub fn cost_basis(&self) -> Decimal {
+        self.size * self.average_cost
+    }
+
+    /// Value of the current size at the mark price.
+    #[must_use]
+    pub fn market_value(&self) -> Option<Decimal> {
+        self.mark.map(|mark| self.size * mark)
+    }
+
+    /// Profit and loss of the current size at the mark price.
+    #[must_use]
+    pub fn unrealized_pnl(&self) -> Option<Decimal> {
+        self.mark.map(|mark| (mark - self.average_cost) * self.size)
+    }
+
+    fn apply(&mut self, side: Side, size: Decimal, price: Decimal) {
+        let signed = match side {
+            Side::Sell => -size,
+            _ => size,
+        };
+
+        if self.size.is_zero() || self.size.is_sign_positive() == signed.is_sign_positive() {
+            let total = self.size.abs() + size;
+            self.average_cost = (self.average_cost * self.size.abs() + price * size) / total;
+            self.size += signed;
+            return;
+        }
+
+        let closed = size.min(self.size.abs());
+        let direction = if self.size.is_sign_positive() {
+            Decimal::ONE
+        } else {
+            Decimal::NEGATIVE_ONE
+        };
+        self.realized_pnl += (price - self.average_cost) * closed * direction;
+        self.size += signed;
+
+        if self.size.is_zero() {
+            self.average_cost = Decimal::ZERO;
+        } else if self.size.is_sign_positive() != direction.is_sign_positive() {
+            // The fill closed the position and opened one on the other side at its price
+            self.average_cost = price;
+        }
+    }
+}
+
+/// Difference between a position in the [`PositionBook`] and the one reported by the data API.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PositionDrift {
+    /// Asset/token identifier
+    pub asset_id: U256,
+    /// Size held according to the [`PositionBook`]
+    pub local_size: Decimal,
+    /// Size held according to the data API
+    pub remote_size: Decimal,
+    /// Average cost according to the [`PositionBook`], if it tracks the token
+    pub local_average_cost: Option<Decimal>,
+    /// Average price according to the data API, if it reports the token
+    pub remote_average_price: Option<Decimal>,
+}
+
+impl PositionDrift {
+    /// Size missing from the local book, negative when the book holds more than reported.
+    #[must_use]
+    pub fn size_difference(&self) -> Decimal {
+        self.remote_size - self.local_size
+    }
+}
+
+/// A fill of one of the account's orders.
+#[derive(Clone, Copy, Debug)]
+struct Fill {
+    asset_id: U256,
+    market: B256,
+    side: Side,
+    size: Decimal,
+    price: Decimal,
+    failed: bool,
+}
+
+/// Tracks positions and profit and loss from the account's fills.
+///
+/// Trades list the orders of every party involved; only the account's side of each trade is
+/// applied. The account is identified by the API keys its orders were placed with, so after
+/// [`Client::rotate_credentials`] the new key has to be added with [`Self::add_api_key`].
+#[derive(Clone, Debug)]
+pub struct PositionBook {
+    api_keys: Vec<ApiKey>,
+    #[cfg(feature = "data")]
+    tolerance: Decimal,
+    positions: HashMap<U256, TokenPosition>,
+    /// Every fill applied so far, in order, to rebuild positions when a trade fails
+    fills: Vec<Fill>,
+    /// Index into `fills` by trade and order ID
+    seen: HashMap<(String, String), usize>,
+}
+
+impl PositionBook {
+    /// Creates an empty book for the account using `api_key`, typically
+    /// [`Client::credentials`]`().key()`.
+    #[must_use]
+    pub fn new(api_key: ApiKey) -> Self {
+        Self {
+            api_keys: vec![api_key],
+            #[cfg(feature = "data")]
+            tolerance: DEFAULT_DRIFT_TOLERANCE,
+            positions: HashMap::new(),
+            fills: Vec::new(),
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Also counts the fills of orders placed with `api_key`, typically the key returned by
+    /// [`Client::rotate_credentials`]. Orders placed with the keys added before keep counting, as
+    /// they may still be resting on the book.
+    pub fn add_api_key(&mut self, api_key: ApiKey) {
+        if !self.api_keys.contains(&api_key) {
+            self.api_keys.push(api_key);
+        }
+    }
+
+    /// Sets the size difference below which [`Self::reconcile`] considers positions equal.
+    /// Defaults to `0.000001`.
+    #[cfg(feature = "data")]
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: Decimal) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Applies the account's side of a trade from the user channel.
+    #[cfg(feature = "ws")]
+    pub fn apply_trade_message(&mut self, trade: &TradeMessage) {
+        let status = TradeStatusType::from(&trade.status);
+
+        if trade.trader_side == Some(TraderSide::Taker)
+            && let Some(taker_order_id) = &trade.taker_order_id
+        {
+            self.record(
+                &trade.id,
+                taker_order_id,
+                &status,
+                Fill {
+                    asset_id: trade.asset_id,
+                    market: trade.market,
+                    side: trade.side,
+                    size: trade.size,
+                    price: trade.price,
+                    failed: false,
+                },
+            );
+        }
+
+        for maker in &trade.maker_orders {
+            if !self.api_keys.contains(&maker.owner) {
+                continue;
+            }
+
+            // A maker order either takes the other side of the same token, or the same side of
+            // the complementary token
+            let side = match (maker.asset_id == trade.asset_id, trade.side) {
+                (true, Side::Buy) => Side::Sell,
+                (true, Side::Sell) => Side::Buy,
+                (_, side) => side,
+            };
+            self.record(
+                &trade.id,
+                &maker.order_id,
+                &status,
+                Fill {
+                    asset_id: maker.asset_id,
+                    market: trade.market,
+                    side,
+                    size: maker.matched_amount,
+                    price: maker.price,
+                    failed: false,
+                },
+            );
+        }
+    }
+
+    /// Applies the account's side of a trade returned by [`Client::trades`].
+    pub fn apply_trade_response(&mut self, trade: &TradeResponse) {
+        if trade.trader_side == TraderSide::Taker {
+            self.record(
+                &trade.id,
+                &trade.taker_order_id,
+                &trade.status,
+                Fill {
+                    asset_id: trade.asset_id,
+                    market: trade.market,
+                    side: trade.side,
+                    size: trade.size,
+                    price: trade.price,
+                    failed: false,
+                },
+            );
+        }
+
+        for maker in &trade.maker_orders {
+            if !self.api_keys.contains(&maker.owner) {
+                continue;
+            }
+
+            self.record(
+                &trade.id,
+                &maker.order_id,
+                &trade.status,
+                Fill {
+                    asset_id: maker.asset_id,
+                    market: trade.market,
+                    side: maker.side,
+                    size: maker.matched_amount,
+                    price: maker.price,
+                    failed: false,
+                },
+            );
+        }
+    }
+
+    fn record(&mut self, trade_id: &str, order_id: &str, status: &TradeStatusType, fill: Fill) {
+        let failed = *status == TradeStatusType::Failed;
+
+        match self.seen.entry((trade_id.to_owned(), order_id.to_owned())) {
+            Entry::Occupied(entry) => {
+                let known = &mut self.fills[*entry.get()];
+                if failed && !known.failed {
+                    known.failed = true;
+                    let asset_id = known.asset_id;
+                    self.rebuild(asset_id);
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(self.fills.len());
+                if !failed {
+                    self.positions
+                        .entry(fill.asset_id)
+                        .or_insert_with(|| TokenPosition::new(fill.asset_id, fill.market))
+                        .apply(fill.side, fill.size, fill.price);
+                }
+                self.fills.push(Fill { failed, ..fill });
+            }
+        }
+    }
+
+    /// Replays every fill of `asset_id` that has not failed.
+    fn rebuild(&mut self, asset_id: U256) {
+        let Some(position) = self.positions.get_mut(&asset_id) else {
+            return;
+        };
+
+        let mut rebuilt = TokenPosition {
+            mark: position.mark,
+            ..TokenPosition::new(asset_id, position.market)
+        };
+        for fill in self
+            .fills
+            .iter()
+            .filter(|fill| fill.asset_id == asset_id && !fill.failed)
+        {
+            rebuilt.apply(fill.side, fill.size, fill.price);
+        }
+
+        *position = rebuilt;
+    }
+
+    /// Position in `asset_id`, if any fill for it has been applied.
+    #[must_use]
+    pub fn position(&self, asset_id: U256) -> Option<&TokenPosition> {
+        self.positions.get(&asset_id)
+    }
+
+    /// Every position with at least one fill, including closed ones.
+    pub fn positions(&self) -> impl Iterator<Item = &TokenPosition> {
+        self.positions.values()
+    }
+
+    /// Marks the position in `asset_id` to `price`.
+    pub fn set_mark(&mut self, asset_id: U256, price: Decimal) {
+        if let Some(position) = self.positions.get_mut(&asset_id) {
+            position.mark = Some(price);
+        }
+    }
+
+    /// Marks every open position to its current midpoint.
+    pub async fn mark_to_midpoints<S: State>(&mut self, client: &Client<S>) -> Result<()> {
+        let requests: Vec<_> = self
+            .positions
+            .values()
+            .filter(|position| !position.size.is_zero())
+            .map(|position| {
+                MidpointRequest::builder()
+                    .token_id(position.asset_id)
+                    .build()
+            })
+            .collect();
+        if requests.is_empty() {
+            return Ok(());
+        }
+
+        let response = client.midpoints(&requests).await?;
+        for (asset_id, midpoint) in response.midpoints {
+            self.set_mark(asset_id, midpoint);
+        }
+
+        Ok(())
+    }
+
+    /// Profit and loss realized across all positions.
+    #[must_use]
+    pub fn realized_pnl(&self) -> Decimal {
+        self.positions
+            .values()
+            .map(|position| position.realized_pnl)
+            .sum()
+    }
+
+    /// Unrealized profit and loss across all marked positions.
+    #[must_use]
+    pub fn unrealized_pnl(&self) -> Decimal {
+        self.positions
+            .values()
+            .filter_map(TokenPosition::unrealized_pnl)
+            .sum()
+    }
+
+    /// Compares the book with `positions` as returned by [`data::Client::positions`], returning
+    /// every token whose size differs by more than the tolerance.
+    #[cfg(feature = "data")]
+    #[must_use]
+    pub fn drift(&self, positions: &[Position]) -> Vec<PositionDrift> {
+        let remote: HashMap<U256, &Position> = positions
+            .iter()
+            .map(|position| (position.asset, position))
+            .collect();
+
+        let local_only = self
+            .positions
+            .values()
+            .filter(|position| !remote.contains_key(&position.asset_id))
+            .map(|position| PositionDrift {
+                asset_id: position.asset_id,
+                local_size: position.size,
+                remote_size: Decimal::ZERO,
+                local_average_cost: Some(position.average_cost),
+                remote_average_price: None,
+            });
+
+        let both = remote.values().map(|position| {
+            let local = self.positions.get(&position.asset);
+            PositionDrift {
+                asset_id: position.asset,
+                local_size: local.map_or(Decimal::ZERO, |local| local.size),
+                remote_size: position.size,
+                local_average_cost: local.map(|local| local.average_cost),
+                remote_average_price: Some(position.avg_price),
+            }
+        });
+
+        local_only
+            .chain(both)
+            .filter(|drift| drift.size_difference().abs() > self.tolerance)
+            .collect()
+    }
+
+    /// Fetches every position of `user` from the data API and compares it with the book. See
+    /// [`Self::drift`].
+    #[cfg(feature = "data")]
+    pub async fn reconcile(
+        &self,
+        client: &data::Client,
+        user: Address,
+    ) -> Result<Vec<PositionDrift>> {
+        let mut positions = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let request = data::types::request::PositionsRequest {
+                user,
+                filter: None,
+                size_threshold: Some(Decimal::ZERO),
+                redeemable: None,
+                mergeable: None,
+                limit: Some(POSITIONS_PAGE_SIZE),
+                offset: Some(offset),
+                sort_by: None,
+                sort_direction: None,
+                title: None,
+            };
+            let page = client.positions(&request).await?;
+            let done = page.len() < usize::try_from(POSITIONS_PAGE_SIZE).unwrap_or_default();
+            positions.extend(page);
+
+            offset += POSITIONS_PAGE_SIZE;
+            if done || offset > POSITIONS_MAX_OFFSET {
+                break;
+            }
+        }
+
+        Ok(self.drift(&positions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::types::dec;
+
+    const MARKET: &str = "0x5f65177b394277fd294cd75650044e32ba009a95022d88a0c1d565897d72f8f1";
+    const YES: u64 = 1;
+    const NO: u64 = 2;
+
+    fn trade(id: &str, trader_side: &str, side: &str, size: &str, price: &str) -> Value {
+        json!({
+            "id": id,
+            "taker_order_id": format!("taker-{id}"),
+            "market": MARKET,
+            "asset_id": YES.to_string(),
+            "side": side,
+            "size": size,
+            "fee_rate_bps": "0",
+            "price": price,
+            "status": "MATCHED",
+            "match_time": "1705322096",
+            "last_update": "1705322096",
+            "outcome": "Yes",
+            "bucket_index": 0,
+            "owner": ApiKey::nil(),
+            "maker_address": "0x2222222222222222222222222222222222222222",
+            "maker_orders": [],
+            "transaction_hash": "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcd",
+            "trader_side": trader_side,
+        })
+    }
+
+    fn trade_response(value: Value) -> TradeResponse {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn average_cost_and_pnl_should_succeed() {
+        let mut position = TokenPosition::new(U256::from(YES), B256::ZERO);
+
+        position.apply(Side::Buy, dec!(10), dec!(0.4));
+        position.apply(Side::Buy, dec!(10), dec!(0.6));
+        assert_eq!(position.size, dec!(20));
+        assert_eq!(position.average_cost, dec!(0.5));
+
+        position.apply(Side::Sell, dec!(5), dec!(0.7));
+        assert_eq!(position.size, dec!(15));
+        assert_eq!(position.average_cost, dec!(0.5));
+        assert_eq!(position.realized_pnl, dec!(1.0));
+        assert_eq!(position.unrealized_pnl(), None);
+
+        position.mark = Some(dec!(0.6));
+        assert_eq!(position.unrealized_pnl(), Some(dec!(1.5)));
+        assert_eq!(position.market_value(), Some(dec!(9)));
+
+        position.apply(Side::Sell, dec!(15), dec!(0.3));
+        assert!(position.size.is_zero(), "position should be closed");
+        assert!(
+            position.average_cost.is_zero(),
+            "closed positions have no cost"
+        );
+        assert_eq!(position.realized_pnl, dec!(-2.0));
+    }
+
+    #[test]
+    fn trade_responses_apply_own_side_once() {
+        let mut book = PositionBook::new(ApiKey::nil());
+
+        let buy = trade("1", "TAKER", "BUY", "10", "0.4");
+        book.apply_trade_response(&trade_response(buy.clone()));
+        let mut confirmed = buy;
+        confirmed["status"] = json!("CONFIRMED");
+        book.apply_trade_response(&trade_response(confirmed));
+
+        // As a maker, only the account's own maker orders count
+        let mut sell = trade("2", "MAKER", "BUY", "10", "0.5");
+        sell["maker_orders"] = json!([
+            {
+                "order_id": "mine",
+                "owner": ApiKey::nil(),
+                "maker_address": "0x2222222222222222222222222222222222222222",
+                "matched_amount": "4",
+                "price": "0.5",
+                "fee_rate_bps": "0",
+                "asset_id": YES.to_string(),
+                "outcome": "Yes",
+                "side": "SELL"
+            },
+            {
+                "order_id": "theirs",
+                "owner": ApiKey::max(),
+                "maker_address": "0x4444444444444444444444444444444444444444",
+                "matched_amount": "6",
+                "price": "0.5",
+                "fee_rate_bps": "0",
+                "asset_id": NO.to_string(),
+                "outcome": "No",
+                "side": "BUY"
+            }
+        ]);
+        book.apply_trade_response(&trade_response(sell));
+
+        let position = book.position(U256::from(YES)).unwrap();
+        assert_eq!(position.size, dec!(6));
+        assert_eq!(position.realized_pnl, dec!(0.4));
+        assert!(
+            book.position(U256::from(NO)).is_none(),
+            "counterparty fills must be ignored"
+        );
+        assert_eq!(book.realized_pnl(), dec!(0.4));
+    }
+
+    #[test]
+    fn added_api_keys_should_count_as_own() {
+        let maker = |id: &str, owner: ApiKey| {
+            let mut sell = trade(id, "MAKER", "BUY", "5", "0.5");
+            sell["maker_orders"] = json!([
+                {
+                    "order_id": format!("maker-{id}"),
+                    "owner": owner,
+                    "maker_address": "0x2222222222222222222222222222222222222222",
+                    "matched_amount": "5",
+                    "price": "0.5",
+                    "fee_rate_bps": "0",
+                    "asset_id": YES.to_string(),
+                    "outcome": "Yes",
+                    "side": "SELL"
+                }
+            ]);
+            trade_response(sell)
+        };
+        let mut book = PositionBook::new(ApiKey::nil());
+
+        book.apply_trade_response(&maker("1", ApiKey::max()));
+        assert!(book.position(U256::from(YES)).is_none());
+
+        book.add_api_key(ApiKey::max());
+        book.apply_trade_response(&maker("2", ApiKey::max()));
+        book.apply_trade_response(&maker("3", ApiKey::nil()));
+        assert_eq!(book.position(U256::from(YES)).unwrap().size, dec!(-10));
+    }
+
+    #[test]
+    fn failed_trades_are_rolled_back() {
+        let mut book = PositionBook::new(ApiKey::nil());
+
+        book.apply_trade_response(&trade_response(trade("1", "TAKER", "BUY", "10", "0.4")));
+        let mut failed = trade("2", "TAKER", "BUY", "10", "0.6");
+        book.apply_trade_response(&trade_response(failed.clone()));
+        assert_eq!(book.position(U256::from(YES)).unwrap().size, dec!(20));
+
+        book.set_mark(U256::from(YES), dec!(0.5));
+        failed["status"] = json!("FAILED");
+        book.apply_trade_response(&trade_response(failed));
+
+        let position = book.position(U256::from(YES)).unwrap();
+        assert_eq!(position.size, dec!(10));
+        assert_eq!(position.average_cost, dec!(0.4));
+        assert_eq!(position.mark, Some(dec!(0.5)), "marks survive a rebuild");
+        assert_eq!(book.unrealized_pnl(), dec!(1.0));
+    }
+
+    #[cfg(feature = "ws")]
+    #[test]
+    fn trade_messages_infer_maker_side() {
+        let mut book = PositionBook::new(ApiKey::nil());
+        let message = |maker_asset: u64| -> TradeMessage {
+            serde_json::from_value(json!({
+                "id": format!("trade-{maker_asset}"),
+                "market": MARKET,
+                "asset_id": YES.to_string(),
+                "side": "BUY",
+                "size": "5",
+                "price": "0.4",
+                "status": "MATCHED",
+                "taker_order_id": "taker",
+                "trader_side": "MAKER",
+                "maker_orders": [
+                    {
+                        "asset_id": maker_asset.to_string(),
+                        "matched_amount": "5",
+                        "order_id": format!("maker-{maker_asset}"),
+                        "outcome": "Yes",
+                        "owner": ApiKey::nil(),
+                        "price": "0.6",
+                    }
+                ],
+            }))
+            .unwrap()
+        };
+
+        // Selling the same token to the taker, and buying the complementary token alongside them
+        book.apply_trade_message(&message(YES));
+        book.apply_trade_message(&message(NO));
+
+        assert_eq!(book.position(U256::from(YES)).unwrap().size, dec!(-5));
+        assert_eq!(book.position(U256::from(NO)).unwrap().size, dec!(5));
+    }
+
+    #[cfg(feature = "data")]
+    #[test]
+    fn drift_reports_mismatched_sizes() {
+        let mut book = PositionBook::new(ApiKey::nil());
+        book.apply_trade_response(&trade_response(trade("1", "TAKER", "BUY", "10", "0.4")));
+        let mut other = trade("2", "TAKER", "BUY", "3", "0.4");
+        other["asset_id"] = json!("3");
+        book.apply_trade_response(&trade_response(other));
+
+        let remote = |asset: u64, size: f64| -> Position {
+            serde_json::from_value(json!({
+                "proxyWallet": "0x1234567890abcdef1234567890abcdef12345678",
+                "asset": asset.to_string(),
+                "conditionId": MARKET,
+                "size": size,
+                "avgPrice": 0.4,
+                "initialValue": 4.0,
+                "currentValue": 4.0,
+                "cashPnl": 0.0,
+                "percentPnl": 0.0,
+                "totalBought": 10.0,
+                "realizedPnl": 0.0,
+                "percentRealizedPnl": 0.0,
+                "curPrice": 0.4,
+                "redeemable": false,
+                "mergeable": false,
+                "title": "Title",
+                "slug": "slug",
+                "icon": "icon",
+                "eventSlug": "event",
+                "outcome": "Yes",
+                "outcomeIndex": 0,
+                "oppositeOutcome": "No",
+                "oppositeAsset": NO.to_string(),
+                "endDate": "2025-12-31",
+                "negativeRisk": false
+            }))
+            .unwrap()
+        };
+
+        let drift = book.drift(&[remote(YES, 10.0), remote(NO, 2.0)]);
+
+        assert_eq!(drift.len(), 2);
+        let missing = drift
+            .iter()
+            .find(|drift| drift.asset_id == U256::from(NO))
+            .unwrap();
+        assert_eq!(missing.size_difference(), dec!(2));
+        assert_eq!(missing.local_average_cost, None);
+        let unreported = drift
+            .iter()
+            .find(|drift| drift.asset_id == U256::from(3))
+            .unwrap();
+        assert_eq!(unreported.size_difference(), dec!(-3));
+        assert_eq!(unreported.remote_average_price, None);
+    }
+}
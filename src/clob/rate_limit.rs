@@ -0,0 +1,465 @@
+#![expect(
+    clippy::module_name_repetitions,
+    reason = "Rate limit types intentionally mirror the module name for clarity"
+)]
+
+//! Client-side rate limiting for the CLOB API.
+//!
+//! Polymarket enforces rate limits per group of endpoints. When [`RateLimits`] are set on the
+//! [`Config`](super::Config), the [`Client`](super::Client) throttles its own requests with a token
+//! bucket per [`EndpointGroup`]: a request that would exceed its group's quota waits until capacity
+//! frees up instead of being sent and rejected with `429 Too Many Requests`. Requests are admitted
+//! in the order they arrive, and a request cancelled while waiting, e.g. by a timeout, gives its
+//! slot back.
+//!
+//! Requests outside of the configured groups are never delayed.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use bon::Builder;
+use reqwest::{Method, Request};
+use tokio::time::Instant;
+
+use crate::auth::l2::POLY_ADDRESS;
+
+/// Group of CLOB endpoints sharing a rate limit.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EndpointGroup {
+    /// Order placement: `POST /order` and `POST /orders`
+    Orders,
+    /// Order cancellation: `DELETE /order`, `DELETE /orders`, `DELETE /cancel-all` and
+    /// `DELETE /cancel-market-orders`
+    Cancels,
+    /// Public market data: every request sent without authentication headers
+    MarketData,
+    /// API key management: `/auth/*`
+    Auth,
+}
+
+impl EndpointGroup {
+    /// Determines the group `request` belongs to, if any.
+    #[must_use]
+    pub fn of(request: &Request) -> Option<Self> {
+        let path = request.url().path().trim_end_matches('/');
+        let method = request.method();
+
+        if path.contains("/auth/") {
+            return Some(Self::Auth);
+        }
+
+        let endpoint = path.rsplit('/').next().unwrap_or_default();
+        match (method, endpoint) {
+            (&Method::POST, "order" | "orders") => Some(Self::Orders),
+            (&Method::DELETE, "order" | "orders" | "cancel-all" | "cancel-market-orders") => {
+                Some(Self::Cancels)
+            }
+            _ if !request.headers().contains_key(POLY_ADDRESS) => Some(Self::MarketData),
+            _ => None,
+        }
+    }
+}
+
+/// A rate limit of `requests` per `period`, allowing bursts of up to `burst` requests.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quota {
+    /// Number of requests replenished over `period`
+    pub requests: u32,
+    /// Period over which `requests` are replenished
+    pub period: Duration,
+    /// Maximum number of requests that may be sent back to back. Defaults to `requests`.
+    pub burst: u32,
+}
+
+impl Quota {
+    /// A quota of `requests` per `period`, with a burst of `requests`.
+    #[must_use]
+    pub const fn new(requests: u32, period: Duration) -> Self {
+        Self {
+            requests,
+            period,
+            burst: requests,
+        }
+    }
+
+    /// Sets the maximum number of requests that may be sent back to back.
+    #[must_use]
+    pub const fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// Time between two requests at the sustained rate.
+    fn interval(&self) -> Duration {
+        self.period / self.requests.max(1)
+    }
+
+    /// How far ahead of the sustained rate a burst may run.
+    fn tolerance(&self) -> Duration {
+        self.interval()
+            .saturating_mul(self.burst.max(1).saturating_sub(1))
+    }
+}
+
+/// Rate limits applied by the [`Client`](super::Client), per [`EndpointGroup`].
+///
+/// Groups without a [`Quota`] are not throttled.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use polymarket_client_sdk::clob::Config;
+/// use polymarket_client_sdk::clob::rate_limit::{Quota, RateLimits};
+///
+/// let rate_limits = RateLimits::builder()
+///     .orders(Quota::new(240, Duration::from_secs(1)))
+///     .cancels(Quota::new(200, Duration::from_secs(1)).with_burst(50))
+///     .build();
+///
+/// let config = Config::builder().rate_limits(rate_limits).build();
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Builder)]
+pub struct RateLimits {
+    /// Quota for [`EndpointGroup::Orders`]
+    pub orders: Option<Quota>,
+    /// Quota for [`EndpointGroup::Cancels`]
+    pub cancels: Option<Quota>,
+    /// Quota for [`EndpointGroup::MarketData`]
+    pub market_data: Option<Quota>,
+    /// Quota for [`EndpointGroup::Auth`]
+    pub auth: Option<Quota>,
+}
+
+/// Snapshot of the queue in front of an [`EndpointGroup`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RateLimitStats {
+    /// Number of requests currently waiting for capacity
+    pub queue_depth: usize,
+    /// Total number of requests that had to wait
+    pub delayed: u64,
+    /// Total time spent waiting, across all requests
+    pub total_wait: Duration,
+    /// Longest time any single request waited
+    pub max_wait: Duration,
+    /// Time the most recently delayed request waited
+    pub last_wait: Duration,
+}
+
+/// Token buckets for every configured [`EndpointGroup`].
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    orders: Option<Bucket>,
+    cancels: Option<Bucket>,
+    market_data: Option<Bucket>,
+    auth: Option<Bucket>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limits: &RateLimits) -> Self {
+        Self {
+            orders: limits.orders.map(Bucket::new),
+            cancels: limits.cancels.map(Bucket::new),
+            market_data: limits.market_data.map(Bucket::new),
+            auth: limits.auth.map(Bucket::new),
+        }
+    }
+
+    /// Waits until `request` may be sent under its group's quota.
+    pub(crate) async fn acquire(&self, request: &Request) {
+        let Some(group) = EndpointGroup::of(request) else {
+            return;
+        };
+        let Some(bucket) = self.bucket(group) else {
+            return;
+        };
+
+        let (wait, arrival) = bucket.reserve();
+        if wait.is_zero() {
+            return;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            ?group,
+            ?wait,
+            queue_depth = bucket.queued.load(Ordering::Relaxed) + 1,
+            "Rate limit reached, queueing request"
+        );
+
+        // Gives the slot back if the request is cancelled before it is admitted
+        let reservation = Reservation(Some((bucket, arrival)));
+        bucket.queued.fetch_add(1, Ordering::Relaxed);
+        let _guard = QueueGuard(&bucket.queued);
+        tokio::time::sleep(wait).await;
+        reservation.admit();
+        bucket.record_wait(wait);
+    }
+
+    pub(crate) fn stats(&self, group: EndpointGroup) -> Option<RateLimitStats> {
+        self.bucket(group).map(Bucket::stats)
+    }
+
+    fn bucket(&self, group: EndpointGroup) -> Option<&Bucket> {
+        match group {
+            EndpointGroup::Orders => self.orders.as_ref(),
+            EndpointGroup::Cancels => self.cancels.as_ref(),
+            EndpointGroup::MarketData => self.market_data.as_ref(),
+            EndpointGroup::Auth => self.auth.as_ref(),
+        }
+    }
+}
+
+/// Token bucket implemented as a generic cell rate algorithm: instead of counting tokens, it
+/// tracks the theoretical arrival time of the next request at the sustained rate.
+#[derive(Debug)]
+struct Bucket {
+    quota: Quota,
+    next_arrival: Mutex<Option<Instant>>,
+    queued: AtomicUsize,
+    delayed: AtomicU64,
+    wait_stats: Mutex<(Duration, Duration, Duration)>,
+}
+
+impl Bucket {
+    fn new(quota: Quota) -> Self {
+        Self {
+            quota,
+            next_arrival: Mutex::new(None),
+            queued: AtomicUsize::new(0),
+            delayed: AtomicU64::new(0),
+            wait_stats: Mutex::default(),
+        }
+    }
+
+    /// Reserves the next slot and returns how long to wait before using it, and the theoretical
+    /// arrival time identifying it.
+    fn reserve(&self) -> (Duration, Instant) {
+        let now = Instant::now();
+        // The bucket state is always consistent, so recovering from a poisoned lock is sound
+        let mut next_arrival = self
+            .next_arrival
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        let arrival = next_arrival.map_or(now, |at| at.max(now));
+        let earliest = arrival
+            .checked_sub(self.quota.tolerance())
+            .unwrap_or(now)
+            .max(now);
+
+        *next_arrival = Some(arrival + self.quota.interval());
+
+        (earliest - now, arrival)
+    }
+
+    /// Releases the slot reserved at `arrival` by a request that was never sent, so it does not
+    /// count against the quota.
+    ///
+    /// Only the last reserved slot can be given back: the slots of requests queued behind an
+    /// earlier one are still held, so handing it out again would admit two requests at once.
+    fn release(&self, arrival: Instant) {
+        let mut next_arrival = self
+            .next_arrival
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        if *next_arrival == Some(arrival + self.quota.interval()) {
+            *next_arrival = Some(arrival);
+        }
+    }
+
+    fn record_wait(&self, wait: Duration) {
+        self.delayed.fetch_add(1, Ordering::Relaxed);
+
+        let mut stats = self
+            .wait_stats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let (total, max, last) = &mut *stats;
+        *total = total.saturating_add(wait);
+        *max = (*max).max(wait);
+        *last = wait;
+    }
+
+    fn stats(&self) -> RateLimitStats {
+        let (total_wait, max_wait, last_wait) = *self
+            .wait_stats
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        RateLimitStats {
+            queue_depth: self.queued.load(Ordering::Relaxed),
+            delayed: self.delayed.load(Ordering::Relaxed),
+            total_wait,
+            max_wait,
+            last_wait,
+        }
+    }
+}
+
+/// A slot reserved in a [`Bucket`], released back to it when dropped before being admitted.
+struct Reservation<'bucket>(Option<(&'bucket Bucket, Instant)>);
+
+impl Reservation<'_> {
+    fn admit(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if let Some((bucket, arrival)) = self.0 {
+            bucket.release(arrival);
+        }
+    }
+}
+
+/// Decrements the queue depth when a waiting request is admitted or cancelled.
+struct QueueGuard<'counter>(&'counter AtomicUsize);
+
+impl Drop for QueueGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    fn request(method: Method, path: &str) -> Request {
+        Request::new(
+            method,
+            format!("https://clob.polymarket.com{path}")
+                .parse()
+                .unwrap(),
+        )
+    }
+
+    fn authenticated(method: Method, path: &str) -> Request {
+        let mut request = request(method, path);
+        request
+            .headers_mut()
+            .insert(POLY_ADDRESS, HeaderValue::from_static("0x0"));
+        request
+    }
+
+    #[test]
+    fn endpoint_group_should_classify_requests() {
+        assert_eq!(
+            EndpointGroup::of(&authenticated(Method::POST, "/order")),
+            Some(EndpointGroup::Orders)
+        );
+        assert_eq!(
+            EndpointGroup::of(&authenticated(Method::POST, "/orders")),
+            Some(EndpointGroup::Orders)
+        );
+        assert_eq!(
+            EndpointGroup::of(&authenticated(Method::DELETE, "/cancel-all")),
+            Some(EndpointGroup::Cancels)
+        );
+        assert_eq!(
+            EndpointGroup::of(&authenticated(Method::GET, "/auth/derive-api-key")),
+            Some(EndpointGroup::Auth)
+        );
+        assert_eq!(
+            EndpointGroup::of(&request(Method::GET, "/book")),
+            Some(EndpointGroup::MarketData)
+        );
+        assert_eq!(
+            EndpointGroup::of(&authenticated(Method::GET, "/data/trades")),
+            None
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn bucket_should_allow_burst_then_throttle() {
+        let bucket = Bucket::new(Quota::new(10, Duration::from_secs(1)).with_burst(2));
+
+        assert_eq!(bucket.reserve().0, Duration::ZERO);
+        assert_eq!(bucket.reserve().0, Duration::ZERO);
+        assert_eq!(bucket.reserve().0, Duration::from_millis(100));
+        assert_eq!(bucket.reserve().0, Duration::from_millis(200));
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+
+        assert_eq!(bucket.reserve().0, Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn releasing_queued_slot_should_not_share_later_slot() {
+        let bucket = Bucket::new(Quota::new(1, Duration::from_secs(1)));
+
+        let (first, _) = bucket.reserve();
+        let (_, cancelled) = bucket.reserve();
+        let (queued, _) = bucket.reserve();
+        bucket.release(cancelled);
+        let (next, last) = bucket.reserve();
+
+        assert_eq!(first, Duration::ZERO);
+        assert_eq!(queued, Duration::from_secs(2));
+        assert_eq!(next, Duration::from_secs(3));
+
+        // The last slot reserved is given back
+        bucket.release(last);
+        assert_eq!(bucket.reserve().0, Duration::from_secs(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_should_queue_and_report_stats() {
+        let limiter = RateLimiter::new(
+            &RateLimits::builder()
+                .orders(Quota::new(1, Duration::from_secs(1)))
+                .build(),
+        );
+        let order = authenticated(Method::POST, "/order");
+
+        limiter.acquire(&order).await;
+        let start = Instant::now();
+        limiter.acquire(&order).await;
+
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+
+        let stats = limiter.stats(EndpointGroup::Orders).unwrap();
+        assert_eq!(stats.queue_depth, 0);
+        assert_eq!(stats.delayed, 1);
+        assert_eq!(stats.max_wait, Duration::from_secs(1));
+        assert!(
+            limiter.stats(EndpointGroup::Cancels).is_none(),
+            "cancels are not limited"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancelled_acquire_should_release_its_slot() {
+        let limiter = RateLimiter::new(
+            &RateLimits::builder()
+                .orders(Quota::new(1, Duration::from_secs(1)))
+                .build(),
+        );
+        let order = authenticated(Method::POST, "/order");
+
+        limiter.acquire(&order).await;
+        for _ in 0..3 {
+            tokio::time::timeout(Duration::from_millis(10), limiter.acquire(&order))
+                .await
+                .unwrap_err();
+        }
+
+        let start = Instant::now();
+        limiter.acquire(&order).await;
+
+        // Only the admitted request counts, 30ms of the one second interval have passed
+        assert_eq!(start.elapsed(), Duration::from_millis(970));
+        assert_eq!(limiter.stats(EndpointGroup::Orders).unwrap().queue_depth, 0);
+    }
+}
@@ -0,0 +1,555 @@
+#![expect(
+    clippy::module_name_repetitions,
+    reason = "Risk check types intentionally mirror the module name for clarity"
+)]
+
+//! Pre-trade risk checks for order placement.
+//!
+//! Every [`RiskCheck`] set on the [`Config`](super::Config) runs on each [`SignedOrder`] before
+//! [`Client::post_order`] or [`Client::post_orders`] sends it. The first check to fail rejects the
+//! order with an [`Error`](crate::error::Error) of kind [`Kind::Risk`](crate::error::Kind::Risk)
+//! carrying a [`RiskRejection`], and nothing is sent. For [`Client::post_orders`], a single
+//! rejected order rejects the whole batch.
+//!
+//! The built-in checks are:
+//! - [`MaxNotional`]: caps the USDC amount of a single order
+//! - [`MaxOrderSize`]: caps the number of shares of a single order, catching mis-scaled sizes
+//! - [`MaxMarketExposure`]: caps the USDC amount resting in open orders on a market
+//! - [`PriceBand`]: rejects orders priced too far from the current midpoint
+//!
+//! Checks that need market data fetch it through the [`RiskContext`], which caches responses for
+//! the duration of a single `post_order` or `post_orders` call. If such a request fails, the order
+//! is not sent and the request error is returned.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::sync::Arc;
+//!
+//! use polymarket_client_sdk::clob::Config;
+//! use polymarket_client_sdk::clob::risk::{MaxNotional, MaxOrderSize, PriceBand};
+//! use rust_decimal_macros::dec;
+//!
+//! let config = Config::builder()
+//!     .risk_checks(vec![
+//!         Arc::new(MaxNotional::new(dec!(500))),
+//!         Arc::new(MaxOrderSize::new(dec!(2_000))),
+//!         Arc::new(PriceBand::new(dec!(0.05))),
+//!     ])
+//!     .build();
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use futures::TryStreamExt as _;
+
+use super::Client;
+use super::types::SignedOrder;
+use super::types::request::{MidpointRequest, OrderBookSummaryRequest, OrdersRequest};
+use super::types::response::OpenOrderResponse;
+use crate::Result;
+use crate::auth::Kind;
+use crate::auth::state::Authenticated;
+pub use crate::error::RiskRejection;
+use crate::types::{B256, Decimal, U256};
+
+/// A check run on every [`SignedOrder`] before it is posted.
+///
+/// Returning an error prevents the order from being sent. Rejections should be reported as a
+/// [`RiskRejection`] so that callers can tell them apart from request failures.
+#[async_trait]
+pub trait RiskCheck: fmt::Debug + Send + Sync {
+    /// Checks `order`, returning an error if it must not be sent.
+    async fn check(&self, order: &SignedOrder, context: &RiskContext<'_>) -> Result<()>;
+}
+
+/// Rejects orders exchanging more than `limit` USDC.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaxNotional {
+    /// Largest USDC amount a single order may exchange
+    pub limit: Decimal,
+}
+
+impl MaxNotional {
+    #[must_use]
+    pub const fn new(limit: Decimal) -> Self {
+        Self { limit }
+    }
+}
+
+#[async_trait]
+impl RiskCheck for MaxNotional {
+    async fn check(&self, order: &SignedOrder, _context: &RiskContext<'_>) -> Result<()> {
+        let notional = order.notional();
+        if notional > self.limit {
+            return Err(RiskRejection::new(
+                "max_notional",
+                format!("notional {notional} exceeds limit of {}", self.limit),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Rejects orders for more than `limit` shares. Guards against fat-fingered or mis-scaled sizes.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaxOrderSize {
+    /// Largest number of shares a single order may exchange
+    pub limit: Decimal,
+}
+
+impl MaxOrderSize {
+    #[must_use]
+    pub const fn new(limit: Decimal) -> Self {
+        Self { limit }
+    }
+}
+
+#[async_trait]
+impl RiskCheck for MaxOrderSize {
+    async fn check(&self, order: &SignedOrder, _context: &RiskContext<'_>) -> Result<()> {
+        let size = order.size();
+        if size > self.limit {
+            return Err(RiskRejection::new(
+                "max_order_size",
+                format!("size {size} exceeds limit of {}", self.limit),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Rejects orders that would bring the USDC amount resting in open orders on a market above
+/// `limit`.
+///
+/// Exposure is the sum of `price * remaining size` over the account's open orders on the market of
+/// the order's token, both outcomes included, plus the orders ahead of it in the same batch and the
+/// order itself.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaxMarketExposure {
+    /// Largest USDC amount that may rest in open orders on a single market
+    pub limit: Decimal,
+}
+
+impl MaxMarketExposure {
+    #[must_use]
+    pub const fn new(limit: Decimal) -> Self {
+        Self { limit }
+    }
+}
+
+#[async_trait]
+impl RiskCheck for MaxMarketExposure {
+    async fn check(&self, order: &SignedOrder, context: &RiskContext<'_>) -> Result<()> {
+        let market = context.market(order.order.tokenId).await?;
+
+        let mut exposure: Decimal = context
+            .open_orders(market)
+            .await?
+            .iter()
+            .map(|open| open.price * (open.original_size - open.size_matched))
+            .sum();
+        for pending in context.batch() {
+            if context.market(pending.order.tokenId).await? == market {
+                exposure += pending.notional();
+            }
+        }
+        exposure += order.notional();
+
+        if exposure > self.limit {
+            return Err(RiskRejection::new(
+                "max_market_exposure",
+                format!(
+                    "open exposure of {exposure} on market {market} exceeds limit of {}",
+                    self.limit
+                ),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Rejects orders priced more than `max_deviation` away from the token's current midpoint.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceBand {
+    /// Largest absolute difference allowed between the order price and the midpoint
+    pub max_deviation: Decimal,
+}
+
+impl PriceBand {
+    #[must_use]
+    pub const fn new(max_deviation: Decimal) -> Self {
+        Self { max_deviation }
+    }
+}
+
+#[async_trait]
+impl RiskCheck for PriceBand {
+    async fn check(&self, order: &SignedOrder, context: &RiskContext<'_>) -> Result<()> {
+        let price = order.price();
+        let midpoint = context.midpoint(order.order.tokenId).await?;
+
+        if (price - midpoint).abs() > self.max_deviation {
+            return Err(RiskRejection::new(
+                "price_band",
+                format!(
+                    "price {price} is more than {} away from midpoint {midpoint}",
+                    self.max_deviation
+                ),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Market data available to a [`RiskCheck`].
+///
+/// Responses are cached for the duration of a single `post_order` or `post_orders` call, so
+/// checking every order of a batch against the same token or market only fetches it once.
+pub struct RiskContext<'client> {
+    source: &'client dyn Source,
+    batch: &'client [SignedOrder],
+    midpoints: Mutex<HashMap<U256, Decimal>>,
+    markets: Mutex<HashMap<U256, B256>>,
+    open_orders: Mutex<HashMap<B256, Vec<OpenOrderResponse>>>,
+}
+
+impl fmt::Debug for RiskContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RiskContext")
+            .field("batch", &self.batch.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'client> RiskContext<'client> {
+    fn new(source: &'client dyn Source) -> Self {
+        Self {
+            source,
+            batch: &[],
+            midpoints: Mutex::default(),
+            markets: Mutex::default(),
+            open_orders: Mutex::default(),
+        }
+    }
+
+    /// Orders ahead of the one being checked in the same `post_orders` call. These have passed
+    /// every check and will be sent along with it.
+    #[must_use]
+    pub fn batch(&self) -> &[SignedOrder] {
+        self.batch
+    }
+
+    /// The current midpoint of `token_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the midpoint could not be fetched.
+    pub async fn midpoint(&self, token_id: U256) -> Result<Decimal> {
+        if let Some(midpoint) = lock(&self.midpoints).get(&token_id) {
+            return Ok(*midpoint);
+        }
+
+        let midpoint = self.source.fetch_midpoint(token_id).await?;
+        lock(&self.midpoints).insert(token_id, midpoint);
+        Ok(midpoint)
+    }
+
+    /// The condition ID of the market `token_id` belongs to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the market could not be fetched.
+    pub async fn market(&self, token_id: U256) -> Result<B256> {
+        if let Some(market) = lock(&self.markets).get(&token_id) {
+            return Ok(*market);
+        }
+
+        let market = self.source.fetch_market(token_id).await?;
+        lock(&self.markets).insert(token_id, market);
+        Ok(market)
+    }
+
+    /// The account's open orders on `market`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the open orders could not be fetched.
+    pub async fn open_orders(&self, market: B256) -> Result<Vec<OpenOrderResponse>> {
+        if let Some(orders) = lock(&self.open_orders).get(&market) {
+            return Ok(orders.clone());
+        }
+
+        let orders = self.source.fetch_open_orders(market).await?;
+        lock(&self.open_orders).insert(market, orders.clone());
+        Ok(orders)
+    }
+}
+
+/// Runs `checks` on each of `orders`, in order, stopping at the first failure.
+pub(crate) async fn evaluate<K: Kind>(
+    checks: &[Arc<dyn RiskCheck>],
+    client: &Client<Authenticated<K>>,
+    orders: &[SignedOrder],
+) -> Result<()> {
+    if checks.is_empty() {
+        return Ok(());
+    }
+
+    let mut context = RiskContext::new(client);
+    for (index, order) in orders.iter().enumerate() {
+        context.batch = &orders[..index];
+        for check in checks {
+            let result = check.check(order, &context).await;
+            #[cfg(feature = "tracing")]
+            if let Err(e) = &result {
+                tracing::warn!(token_id = %order.order.tokenId, error = %e, "Order failed risk check");
+            }
+            result?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The cache never holds partial updates, so recovering from a poisoned lock is sound.
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Where a [`RiskContext`] fetches market data from.
+#[async_trait]
+trait Source: Send + Sync {
+    async fn fetch_midpoint(&self, token_id: U256) -> Result<Decimal>;
+
+    async fn fetch_market(&self, token_id: U256) -> Result<B256>;
+
+    async fn fetch_open_orders(&self, market: B256) -> Result<Vec<OpenOrderResponse>>;
+}
+
+#[async_trait]
+impl<K: Kind> Source for Client<Authenticated<K>> {
+    async fn fetch_midpoint(&self, token_id: U256) -> Result<Decimal> {
+        let request = MidpointRequest::builder().token_id(token_id).build();
+        Ok(self.midpoint(&request).await?.mid)
+    }
+
+    async fn fetch_market(&self, token_id: U256) -> Result<B256> {
+        let request = OrderBookSummaryRequest::builder()
+            .token_id(token_id)
+            .build();
+        Ok(self.order_book(&request).await?.market)
+    }
+
+    async fn fetch_open_orders(&self, market: B256) -> Result<Vec<OpenOrderResponse>> {
+        let request = OrdersRequest::builder().market(market).build();
+        self.stream_data(|client, cursor| client.orders(&request, cursor))
+            .try_collect()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use alloy::primitives::Signature;
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::POLYGON;
+    use crate::auth::ApiKey;
+    use crate::clob::types::{Order, OrderStatusType, OrderType, Side};
+
+    const MARKET: B256 = B256::repeat_byte(1);
+
+    #[derive(Default)]
+    struct StaticSource {
+        open_orders: Vec<OpenOrderResponse>,
+        requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Source for StaticSource {
+        async fn fetch_midpoint(&self, _token_id: U256) -> Result<Decimal> {
+            self.requests.fetch_add(1, Ordering::Relaxed);
+            Ok(dec!(0.5))
+        }
+
+        async fn fetch_market(&self, _token_id: U256) -> Result<B256> {
+            self.requests.fetch_add(1, Ordering::Relaxed);
+            Ok(MARKET)
+        }
+
+        async fn fetch_open_orders(&self, _market: B256) -> Result<Vec<OpenOrderResponse>> {
+            self.requests.fetch_add(1, Ordering::Relaxed);
+            Ok(self.open_orders.clone())
+        }
+    }
+
+    /// A signed order for `size` shares at `price`, with amounts scaled to six decimals.
+    fn signed_order(side: Side, price: Decimal, size: Decimal) -> SignedOrder {
+        let scale = |amount: Decimal| U256::from((amount * dec!(1_000_000)).normalize().mantissa());
+        let (maker_amount, taker_amount) = match side {
+            Side::Sell => (scale(size), scale(size * price)),
+            _ => (scale(size * price), scale(size)),
+        };
+
+        SignedOrder::builder()
+            .order(Order {
+                tokenId: U256::from(1),
+                makerAmount: maker_amount,
+                takerAmount: taker_amount,
+                side: side as u8,
+                ..Order::default()
+            })
+            .signature(Signature::new(U256::ZERO, U256::ZERO, false))
+            .order_type(OrderType::GTC)
+            .owner(ApiKey::nil())
+            .chain_id(POLYGON)
+            .neg_risk(false)
+            .build()
+    }
+
+    fn open_order(
+        price: Decimal,
+        original_size: Decimal,
+        size_matched: Decimal,
+    ) -> OpenOrderResponse {
+        OpenOrderResponse::builder()
+            .id("0x1")
+            .status(OrderStatusType::Live)
+            .owner(ApiKey::nil())
+            .maker_address(crate::types::Address::ZERO)
+            .market(MARKET)
+            .asset_id(U256::from(2))
+            .side(Side::Buy)
+            .original_size(original_size)
+            .size_matched(size_matched)
+            .price(price)
+            .associate_trades(Vec::new())
+            .outcome("No")
+            .created_at(chrono::DateTime::UNIX_EPOCH)
+            .expiration(chrono::DateTime::UNIX_EPOCH)
+            .order_type(OrderType::GTC)
+            .build()
+    }
+
+    async fn run(check: &dyn RiskCheck, source: &StaticSource, order: &SignedOrder) -> Result<()> {
+        check.check(order, &RiskContext::new(source)).await
+    }
+
+    #[tokio::test]
+    async fn size_and_notional_limits_should_reject_oversized_orders() {
+        let source = StaticSource::default();
+        let order = signed_order(Side::Buy, dec!(0.5), dec!(1_000));
+
+        run(&MaxOrderSize::new(dec!(1_000)), &source, &order)
+            .await
+            .unwrap();
+        let err = run(&MaxOrderSize::new(dec!(10)), &source, &order)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::error::Kind::Risk);
+        assert_eq!(
+            err.downcast_ref::<RiskRejection>().unwrap().check,
+            "max_order_size"
+        );
+
+        let sell = signed_order(Side::Sell, dec!(0.5), dec!(1_000));
+        run(&MaxNotional::new(dec!(500)), &source, &sell)
+            .await
+            .unwrap();
+        let err = run(&MaxNotional::new(dec!(499)), &source, &sell)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<RiskRejection>().unwrap().check,
+            "max_notional"
+        );
+        assert_eq!(source.requests.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn price_band_should_reject_orders_far_from_midpoint() {
+        let source = StaticSource::default();
+        let band = PriceBand::new(dec!(0.05));
+
+        run(
+            &band,
+            &source,
+            &signed_order(Side::Buy, dec!(0.55), dec!(10)),
+        )
+        .await
+        .unwrap();
+        run(
+            &band,
+            &source,
+            &signed_order(Side::Sell, dec!(0.45), dec!(10)),
+        )
+        .await
+        .unwrap();
+        let err = run(
+            &band,
+            &source,
+            &signed_order(Side::Buy, dec!(0.56), dec!(10)),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<RiskRejection>().unwrap().check,
+            "price_band"
+        );
+    }
+
+    #[tokio::test]
+    async fn market_exposure_should_include_open_orders_and_batch() {
+        let source = StaticSource {
+            // 0.4 * (100 - 25) = 30 USDC resting on the market
+            open_orders: vec![open_order(dec!(0.4), dec!(100), dec!(25))],
+            ..StaticSource::default()
+        };
+        let check = MaxMarketExposure::new(dec!(50));
+
+        let order = || signed_order(Side::Buy, dec!(0.5), dec!(40));
+        run(&check, &source, &order()).await.unwrap();
+
+        let mut context = RiskContext::new(&source);
+        let batch = [order(), order()];
+        context.batch = &batch[..1];
+        let err = check.check(&batch[1], &context).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<RiskRejection>().unwrap().check,
+            "max_market_exposure"
+        );
+    }
+
+    #[tokio::test]
+    async fn context_should_cache_responses() {
+        let source = StaticSource::default();
+        let context = RiskContext::new(&source);
+
+        for _ in 0..3 {
+            context.midpoint(U256::from(1)).await.unwrap();
+            context.market(U256::from(1)).await.unwrap();
+            context.open_orders(MARKET).await.unwrap();
+        }
+
+        assert_eq!(source.requests.load(Ordering::Relaxed), 3);
+    }
+}
@@ -0,0 +1,816 @@
+//! Order matching and account state behind a [`MockExchange`](super::MockExchange).
+
+use std::collections::{BTreeMap, HashMap};
+
+use alloy::primitives::keccak256;
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use super::MockMarket;
+use crate::auth::ApiKey;
+use crate::clob::types::response::{OrderBookSummaryResponse, OrderSummary};
+use crate::clob::types::{OrderStatusType, OrderType, Side, order_amounts};
+use crate::types::{Address, B256, Decimal, U256};
+
+/// API key owning the liquidity added with [`MockExchange::add_liquidity`](super::MockExchange::add_liquidity).
+pub(super) const HOUSE: ApiKey = Uuid::nil();
+
+/// Something the exchange pushes to WebSocket subscribers.
+#[derive(Clone, Debug)]
+pub(super) enum Event {
+    /// Sent on the market channel to subscribers of `asset_id`
+    Book { asset_id: U256, payload: Value },
+    /// Sent on the user channel to `owner`, if they subscribed to `market`
+    User {
+        owner: ApiKey,
+        market: B256,
+        payload: Value,
+    },
+}
+
+/// A signed order as decoded from a `POST /order` body.
+#[derive(Clone, Debug)]
+pub(super) struct NewOrder {
+    pub(super) id: String,
+    pub(super) token_id: U256,
+    pub(super) maker: Address,
+    pub(super) side: Side,
+    pub(super) maker_amount: U256,
+    pub(super) taker_amount: U256,
+    pub(super) expiration: U256,
+    pub(super) order_type: OrderType,
+    pub(super) post_only: bool,
+}
+
+/// Outcome of an accepted order, as reported by `POST /order`.
+#[derive(Clone, Debug)]
+pub(super) struct Placement {
+    pub(super) order_id: String,
+    pub(super) status: OrderStatusType,
+    pub(super) making_amount: Decimal,
+    pub(super) taking_amount: Decimal,
+    pub(super) trade_ids: Vec<String>,
+    pub(super) transaction_hashes: Vec<B256>,
+}
+
+#[derive(Clone, Debug)]
+struct Order {
+    id: String,
+    owner: ApiKey,
+    maker: Address,
+    token_id: U256,
+    side: Side,
+    price: Decimal,
+    original_size: Decimal,
+    size_matched: Decimal,
+    status: OrderStatusType,
+    time_in_force: OrderType,
+    associate_trades: Vec<String>,
+    created_at: DateTime<Utc>,
+    expiration: DateTime<Utc>,
+    /// Arrival order, for time priority
+    sequence: u64,
+}
+
+impl Order {
+    fn remaining(&self) -> Decimal {
+        self.original_size - self.size_matched
+    }
+
+    fn is_live(&self) -> bool {
+        self.status == OrderStatusType::Live
+    }
+}
+
+/// A taker order matched against a single maker order, at the maker's price.
+#[derive(Clone, Debug)]
+struct Trade {
+    id: String,
+    taker_order_id: String,
+    taker_owner: ApiKey,
+    taker_address: Address,
+    maker_order_id: String,
+    maker_owner: ApiKey,
+    maker_address: Address,
+    token_id: U256,
+    side: Side,
+    size: Decimal,
+    price: Decimal,
+    match_time: DateTime<Utc>,
+    transaction_hash: B256,
+}
+
+/// State of the exchange: markets, orders, trades and known accounts.
+#[derive(Debug, Default)]
+pub(super) struct Engine {
+    markets: HashMap<U256, MockMarket>,
+    orders: HashMap<String, Order>,
+    /// Order ids in the order they were received
+    order_ids: Vec<String>,
+    trades: Vec<Trade>,
+    accounts: HashMap<ApiKey, Address>,
+    events: Vec<Event>,
+}
+
+impl Engine {
+    pub(super) fn add_market(&mut self, market: MockMarket) {
+        self.markets.insert(market.token_id, market);
+    }
+
+    pub(super) fn market(&self, token_id: U256) -> Option<&MockMarket> {
+        self.markets.get(&token_id)
+    }
+
+    /// Registers the account behind `address` and returns its API key, which is the same every
+    /// time for a given address.
+    pub(super) fn register(&mut self, address: Address) -> ApiKey {
+        let hash = keccak256(address);
+        let mut bytes = [0_u8; 16];
+        bytes.copy_from_slice(&hash[..16]);
+        let key = uuid::Builder::from_random_bytes(bytes).into_uuid();
+
+        self.accounts.insert(key, address);
+        key
+    }
+
+    pub(super) fn is_registered(&self, key: &ApiKey) -> bool {
+        self.accounts.contains_key(key)
+    }
+
+    /// Events produced since the last call, oldest first.
+    pub(super) fn take_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Matches `new` against the book and rests any remainder, or returns the reason it was
+    /// rejected. Rejected orders leave no trace.
+    pub(super) fn submit(&mut self, owner: ApiKey, new: NewOrder) -> Result<Placement, String> {
+        let market = self
+            .markets
+            .get(&new.token_id)
+            .ok_or_else(|| format!("market not found for token {}", new.token_id))?;
+        let (size, notional) = order_amounts(new.side, new.maker_amount, new.taker_amount);
+        let min_order_size = market.min_order_size;
+        let tick_size = market.tick_size.as_decimal();
+
+        if self.orders.contains_key(&new.id) {
+            return Err(format!("order {} is invalid. Duplicated.", new.id));
+        }
+        if new.side == Side::Unknown || size.is_zero() {
+            return Err(format!("order {} is invalid", new.id));
+        }
+        if size < min_order_size {
+            return Err(format!(
+                "order size {size} is lower than the minimum: {min_order_size}"
+            ));
+        }
+
+        let price = (notional / size).normalize();
+        let resting = matches!(new.order_type, OrderType::GTC | OrderType::GTD);
+        if price <= Decimal::ZERO
+            || price >= Decimal::ONE
+            || (resting && !(price % tick_size).is_zero())
+        {
+            return Err(format!(
+                "invalid price ({price}), min: {tick_size} - max: {}",
+                Decimal::ONE - tick_size
+            ));
+        }
+
+        let makers = self.crossing(new.token_id, new.side, price);
+        let available: Decimal = makers.iter().map(|id| self.orders[id].remaining()).sum();
+        if new.post_only && !makers.is_empty() {
+            return Err("invalid post-only order: order crosses book".to_owned());
+        }
+        match new.order_type {
+            OrderType::FOK if available < size => {
+                return Err(
+                    "order couldn't be fully filled. FOK orders are fully filled or \
+                            killed."
+                        .to_owned(),
+                );
+            }
+            OrderType::FAK if available.is_zero() => {
+                return Err(
+                    "no orders found to match with FAK order. FAK orders are partially \
+                            filled or killed if no match is found."
+                        .to_owned(),
+                );
+            }
+            _ => {}
+        }
+
+        let now = Utc::now();
+        let sequence = self.order_ids.len() as u64;
+        let mut taker = Order {
+            id: new.id.clone(),
+            owner,
+            maker: new.maker,
+            token_id: new.token_id,
+            side: new.side,
+            price,
+            original_size: size,
+            size_matched: Decimal::ZERO,
+            status: OrderStatusType::Live,
+            time_in_force: new.order_type,
+            associate_trades: Vec::new(),
+            created_at: now,
+            expiration: expiration(new.expiration),
+            sequence,
+        };
+
+        let mut placement = Placement {
+            order_id: new.id.clone(),
+            status: OrderStatusType::Live,
+            making_amount: Decimal::ZERO,
+            taking_amount: Decimal::ZERO,
+            trade_ids: Vec::new(),
+            transaction_hashes: Vec::new(),
+        };
+
+        for maker_id in makers {
+            if taker.remaining().is_zero() {
+                break;
+            }
+
+            let maker = self
+                .orders
+                .get_mut(&maker_id)
+                .ok_or_else(|| format!("order {maker_id} not found"))?;
+            let fill = taker.remaining().min(maker.remaining());
+            let trade_id = Uuid::new_v4().to_string();
+
+            maker.size_matched += fill;
+            maker.associate_trades.push(trade_id.clone());
+            if maker.remaining().is_zero() {
+                maker.status = OrderStatusType::Matched;
+            }
+            taker.size_matched += fill;
+            taker.associate_trades.push(trade_id.clone());
+
+            let trade = Trade {
+                transaction_hash: keccak256(trade_id.as_bytes()),
+                id: trade_id,
+                taker_order_id: taker.id.clone(),
+                taker_owner: taker.owner,
+                taker_address: taker.maker,
+                maker_order_id: maker.id.clone(),
+                maker_owner: maker.owner,
+                maker_address: maker.maker,
+                token_id: taker.token_id,
+                side: taker.side,
+                size: fill,
+                price: maker.price,
+                match_time: now,
+            };
+
+            let usdc = fill * trade.price;
+            let (making, taking) = match taker.side {
+                Side::Sell => (fill, usdc),
+                _ => (usdc, fill),
+            };
+            placement.making_amount += making;
+            placement.taking_amount += taking;
+            placement.trade_ids.push(trade.id.clone());
+            placement.transaction_hashes.push(trade.transaction_hash);
+
+            let maker = maker.clone();
+            self.push_trade_events(&trade);
+            self.push_order_event(&maker, "UPDATE");
+            self.trades.push(trade);
+        }
+
+        taker.status = if taker.remaining().is_zero() {
+            OrderStatusType::Matched
+        } else if resting {
+            OrderStatusType::Live
+        } else {
+            // Whatever a FAK order could not fill right away is canceled
+            OrderStatusType::Canceled
+        };
+        placement.status = match taker.status {
+            OrderStatusType::Live => OrderStatusType::Live,
+            _ => OrderStatusType::Matched,
+        };
+
+        self.push_order_event(&taker, "PLACEMENT");
+        self.order_ids.push(taker.id.clone());
+        self.orders.insert(taker.id.clone(), taker);
+        self.push_book_event(new.token_id);
+
+        Ok(placement)
+    }
+
+    /// Cancels the live order `id` if it belongs to `owner`, or returns why it could not be.
+    pub(super) fn cancel(&mut self, owner: ApiKey, id: &str) -> Result<(), String> {
+        let order = self
+            .orders
+            .get_mut(id)
+            .filter(|order| order.owner == owner)
+            .ok_or_else(|| "order not found".to_owned())?;
+        if !order.is_live() {
+            return Err("order can't be found - already canceled or matched".to_owned());
+        }
+
+        order.status = OrderStatusType::Canceled;
+        let order = order.clone();
+        self.push_order_event(&order, "CANCELLATION");
+        self.push_book_event(order.token_id);
+
+        Ok(())
+    }
+
+    /// Ids of the live orders of `owner`, optionally restricted to a market or token.
+    pub(super) fn live_order_ids(
+        &self,
+        owner: ApiKey,
+        market: Option<B256>,
+        asset_id: Option<U256>,
+    ) -> Vec<String> {
+        self.order_ids
+            .iter()
+            .filter_map(|id| self.orders.get(id))
+            .filter(|order| order.owner == owner && order.is_live())
+            .filter(|order| asset_id.is_none_or(|asset_id| order.token_id == asset_id))
+            .filter(|order| market.is_none_or(|market| self.condition_id(order.token_id) == market))
+            .map(|order| order.id.clone())
+            .collect()
+    }
+
+    /// Every order, in the order it was received, as returned by `GET /data/order/{id}`.
+    pub(super) fn orders_json(&self) -> Vec<Value> {
+        self.order_ids
+            .iter()
+            .filter_map(|id| self.order_json(id))
+            .collect()
+    }
+
+    /// `GET /data/order/{id}` body for `id`.
+    pub(super) fn order_json(&self, id: &str) -> Option<Value> {
+        self.orders.get(id).map(|order| self.order_value(order))
+    }
+
+    fn order_value(&self, order: &Order) -> Value {
+        json!({
+            "id": order.id,
+            "status": order.status,
+            "owner": order.owner,
+            "maker_address": order.maker,
+            "market": self.condition_id(order.token_id),
+            "asset_id": order.token_id.to_string(),
+            "side": order.side,
+            "original_size": order.original_size,
+            "size_matched": order.size_matched,
+            "price": order.price,
+            "associate_trades": order.associate_trades,
+            "outcome": self.outcome(order.token_id),
+            "created_at": order.created_at.timestamp(),
+            "expiration": order.expiration.timestamp().to_string(),
+            "order_type": order.time_in_force,
+        })
+    }
+
+    /// Every trade, in the order it was matched, as seen by its taker.
+    pub(super) fn trades_json(&self) -> Vec<Value> {
+        self.trades
+            .iter()
+            .map(|trade| self.trade_json(trade, trade.taker_owner))
+            .collect()
+    }
+
+    /// The trades `owner` took part in, as returned by `GET /data/trades`.
+    pub(super) fn trades_json_for(
+        &self,
+        owner: ApiKey,
+        market: Option<B256>,
+        asset_id: Option<U256>,
+    ) -> Vec<Value> {
+        self.trades
+            .iter()
+            .filter(|trade| trade.taker_owner == owner || trade.maker_owner == owner)
+            .filter(|trade| asset_id.is_none_or(|asset_id| trade.token_id == asset_id))
+            .filter(|trade| market.is_none_or(|market| self.condition_id(trade.token_id) == market))
+            .map(|trade| self.trade_json(trade, owner))
+            .collect()
+    }
+
+    fn trade_json(&self, trade: &Trade, viewer: ApiKey) -> Value {
+        let trader_side = if viewer == trade.taker_owner {
+            "TAKER"
+        } else {
+            "MAKER"
+        };
+
+        json!({
+            "id": trade.id,
+            "taker_order_id": trade.taker_order_id,
+            "market": self.condition_id(trade.token_id),
+            "asset_id": trade.token_id.to_string(),
+            "side": trade.side,
+            "size": trade.size,
+            "fee_rate_bps": self.fee_rate_bps(trade.token_id),
+            "price": trade.price,
+            "status": "MATCHED",
+            "match_time": trade.match_time.timestamp().to_string(),
+            "last_update": trade.match_time.timestamp().to_string(),
+            "outcome": self.outcome(trade.token_id),
+            "bucket_index": 0,
+            "owner": trade.taker_owner,
+            "maker_address": trade.taker_address,
+            "maker_orders": [self.maker_order_json(trade)],
+            "transaction_hash": trade.transaction_hash,
+            "trader_side": trader_side,
+            "error_msg": null,
+        })
+    }
+
+    fn maker_order_json(&self, trade: &Trade) -> Value {
+        json!({
+            "order_id": trade.maker_order_id,
+            "owner": trade.maker_owner,
+            "maker_address": trade.maker_address,
+            "matched_amount": trade.size,
+            "price": trade.price,
+            "fee_rate_bps": self.fee_rate_bps(trade.token_id),
+            "asset_id": trade.token_id.to_string(),
+            "outcome": self.outcome(trade.token_id),
+            "side": opposite(trade.side),
+        })
+    }
+
+    /// Current book of `token_id`, best levels first, or `None` for an unknown token.
+    pub(super) fn book(&self, token_id: U256) -> Option<OrderBookSummaryResponse> {
+        let market = self.markets.get(&token_id)?;
+        let mut bids = BTreeMap::<Decimal, Decimal>::new();
+        let mut asks = BTreeMap::<Decimal, Decimal>::new();
+
+        for order in self.orders.values() {
+            if order.token_id == token_id && order.is_live() {
+                let levels = match order.side {
+                    Side::Buy => &mut bids,
+                    _ => &mut asks,
+                };
+                *levels.entry(order.price).or_default() += order.remaining();
+            }
+        }
+
+        let level = |(price, size): (&Decimal, &Decimal)| {
+            OrderSummary::builder().price(*price).size(*size).build()
+        };
+        let mut book = OrderBookSummaryResponse::builder()
+            .market(market.condition_id)
+            .asset_id(token_id)
+            .timestamp(Utc::now())
+            .bids(bids.iter().rev().map(level).collect())
+            .asks(asks.iter().map(level).collect())
+            .min_order_size(market.min_order_size)
+            .neg_risk(market.neg_risk)
+            .tick_size(market.tick_size)
+            .maybe_last_trade_price(
+                self.trades
+                    .iter()
+                    .rfind(|trade| trade.token_id == token_id)
+                    .map(|trade| trade.price),
+            )
+            .build();
+        book.hash = book.hash().ok();
+
+        Some(book)
+    }
+
+    /// `book` message for the market channel.
+    pub(super) fn book_json(&self, token_id: U256) -> Option<Value> {
+        let mut payload = serde_json::to_value(self.book(token_id)?).ok()?;
+        payload["event_type"] = json!("book");
+        payload["asset_id"] = json!(token_id.to_string());
+
+        Some(payload)
+    }
+
+    /// Live orders on the opposite side of `side` that a `price` limit crosses, best first.
+    fn crossing(&self, token_id: U256, side: Side, price: Decimal) -> Vec<String> {
+        let mut makers: Vec<&Order> = self
+            .orders
+            .values()
+            .filter(|order| order.token_id == token_id && order.is_live())
+            .filter(|order| match side {
+                Side::Buy => order.side == Side::Sell && order.price <= price,
+                _ => order.side == Side::Buy && order.price >= price,
+            })
+            .collect();
+
+        makers.sort_by(|a, b| {
+            let by_price = match side {
+                Side::Buy => a.price.cmp(&b.price),
+                _ => b.price.cmp(&a.price),
+            };
+            by_price.then(a.sequence.cmp(&b.sequence))
+        });
+
+        makers.into_iter().map(|order| order.id.clone()).collect()
+    }
+
+    fn push_book_event(&mut self, token_id: U256) {
+        if let Some(payload) = self.book_json(token_id) {
+            self.events.push(Event::Book {
+                asset_id: token_id,
+                payload,
+            });
+        }
+    }
+
+    fn push_order_event(&mut self, order: &Order, kind: &str) {
+        let mut payload = self.order_value(order);
+        payload["event_type"] = json!("order");
+        payload["type"] = json!(kind);
+        payload["order_owner"] = json!(order.owner);
+        payload["timestamp"] = json!(Utc::now().timestamp_millis().to_string());
+
+        self.events.push(Event::User {
+            owner: order.owner,
+            market: self.condition_id(order.token_id),
+            payload,
+        });
+    }
+
+    fn push_trade_events(&mut self, trade: &Trade) {
+        let market = self.condition_id(trade.token_id);
+
+        for owner in [trade.taker_owner, trade.maker_owner] {
+            let mut payload = self.trade_json(trade, owner);
+            payload["event_type"] = json!("trade");
+            payload["type"] = json!("TRADE");
+            payload["owner"] = json!(owner);
+            payload["trade_owner"] = json!(trade.taker_owner);
+            if let Some(fields) = payload.as_object_mut() {
+                // The user channel names it differently
+                if let Some(match_time) = fields.remove("match_time") {
+                    fields.insert("matchtime".to_owned(), match_time);
+                }
+            }
+            payload["timestamp"] = json!(Utc::now().timestamp_millis().to_string());
+
+            self.events.push(Event::User {
+                owner,
+                market,
+                payload,
+            });
+            if trade.maker_owner == trade.taker_owner {
+                break;
+            }
+        }
+    }
+
+    fn condition_id(&self, token_id: U256) -> B256 {
+        self.markets
+            .get(&token_id)
+            .map_or(B256::ZERO, |market| market.condition_id)
+    }
+
+    fn outcome(&self, token_id: U256) -> String {
+        self.markets
+            .get(&token_id)
+            .map_or_else(String::new, |market| market.outcome.clone())
+    }
+
+    fn fee_rate_bps(&self, token_id: U256) -> Decimal {
+        Decimal::from(
+            self.markets
+                .get(&token_id)
+                .map_or(0, |market| market.fee_rate_bps),
+        )
+    }
+}
+
+/// Expiration of an order signed with `seconds`, where zero (no expiration) maps to the epoch like
+/// it does on the exchange.
+fn expiration(seconds: U256) -> DateTime<Utc> {
+    let seconds = i64::try_from(seconds).unwrap_or(i64::MAX);
+    DateTime::from_timestamp(seconds, 0).unwrap_or(DateTime::UNIX_EPOCH)
+}
+
+fn opposite(side: Side) -> Side {
+    match side {
+        Side::Buy => Side::Sell,
+        Side::Sell => Side::Buy,
+        Side::Unknown => Side::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::clob::types::response::{OpenOrderResponse, TradeResponse};
+    use crate::clob::types::{TickSize, TraderSide};
+
+    fn token() -> U256 {
+        U256::from(1)
+    }
+
+    fn engine() -> Engine {
+        let mut engine = Engine::default();
+        engine.add_market(
+            MockMarket::builder()
+                .token_id(token())
+                .condition_id(B256::repeat_byte(1))
+                .tick_size(TickSize::Hundredth)
+                .build(),
+        );
+        engine
+    }
+
+    fn order(
+        id: &str,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+        order_type: OrderType,
+    ) -> NewOrder {
+        let fixed = |amount: Decimal| U256::from((amount * dec!(1_000_000)).trunc().mantissa());
+        let (maker_amount, taker_amount) = match side {
+            Side::Sell => (fixed(size), fixed(size * price)),
+            _ => (fixed(size * price), fixed(size)),
+        };
+
+        NewOrder {
+            id: id.to_owned(),
+            token_id: token(),
+            maker: Address::ZERO,
+            side,
+            maker_amount,
+            taker_amount,
+            expiration: U256::ZERO,
+            order_type,
+            post_only: false,
+        }
+    }
+
+    #[test]
+    fn submit_should_match_at_maker_prices_with_price_time_priority() {
+        let mut engine = engine();
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        engine
+            .submit(
+                alice,
+                order("a1", Side::Sell, dec!(0.55), dec!(10), OrderType::GTC),
+            )
+            .unwrap();
+        engine
+            .submit(
+                alice,
+                order("a2", Side::Sell, dec!(0.50), dec!(10), OrderType::GTC),
+            )
+            .unwrap();
+        engine
+            .submit(
+                alice,
+                order("a3", Side::Sell, dec!(0.50), dec!(10), OrderType::GTC),
+            )
+            .unwrap();
+
+        let placement = engine
+            .submit(
+                bob,
+                order("b1", Side::Buy, dec!(0.55), dec!(25), OrderType::GTC),
+            )
+            .unwrap();
+
+        assert_eq!(placement.status, OrderStatusType::Matched);
+        assert_eq!(placement.taking_amount, dec!(25));
+        assert_eq!(placement.making_amount, dec!(12.75));
+        assert_eq!(placement.trade_ids.len(), 3);
+
+        let trades = engine.trades_json();
+        let makers: Vec<_> = trades
+            .iter()
+            .map(|trade| trade["maker_orders"][0]["order_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(makers, ["a2", "a3", "a1"]);
+
+        let book = engine.book(token()).unwrap();
+        assert!(book.bids.is_empty());
+        assert_eq!(
+            book.asks,
+            vec![
+                OrderSummary::builder()
+                    .price(dec!(0.55))
+                    .size(dec!(5))
+                    .build()
+            ]
+        );
+    }
+
+    #[test]
+    fn submit_should_enforce_time_in_force() {
+        let mut engine = engine();
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        engine
+            .submit(
+                alice,
+                order("a1", Side::Buy, dec!(0.40), dec!(10), OrderType::GTC),
+            )
+            .unwrap();
+
+        let fok = engine.submit(
+            bob,
+            order("b1", Side::Sell, dec!(0.40), dec!(20), OrderType::FOK),
+        );
+        assert!(fok.unwrap_err().contains("FOK"));
+
+        let fak = engine
+            .submit(
+                bob,
+                order("b2", Side::Sell, dec!(0.40), dec!(20), OrderType::FAK),
+            )
+            .unwrap();
+        assert_eq!(fak.status, OrderStatusType::Matched);
+        assert_eq!(fak.making_amount, dec!(10));
+        assert_eq!(engine.order_json("b2").unwrap()["status"], "CANCELED");
+
+        let mut post_only = order("b3", Side::Buy, dec!(0.60), dec!(10), OrderType::GTC);
+        post_only.post_only = true;
+        engine
+            .submit(
+                bob,
+                order("b4", Side::Sell, dec!(0.60), dec!(10), OrderType::GTC),
+            )
+            .unwrap();
+        assert!(
+            engine
+                .submit(alice, post_only)
+                .unwrap_err()
+                .contains("post-only")
+        );
+
+        let duplicate = engine.submit(
+            bob,
+            order("b4", Side::Sell, dec!(0.60), dec!(10), OrderType::GTC),
+        );
+        assert!(duplicate.unwrap_err().contains("Duplicated"));
+    }
+
+    #[test]
+    fn cancel_should_only_remove_own_live_orders() {
+        let mut engine = engine();
+        let alice = Uuid::new_v4();
+
+        engine
+            .submit(
+                alice,
+                order("a1", Side::Buy, dec!(0.40), dec!(10), OrderType::GTC),
+            )
+            .unwrap();
+        engine.take_events();
+
+        engine.cancel(Uuid::new_v4(), "a1").unwrap_err();
+        engine.cancel(alice, "a1").unwrap();
+        engine.cancel(alice, "a1").unwrap_err();
+
+        assert!(engine.book(token()).unwrap().bids.is_empty());
+        assert!(engine.live_order_ids(alice, None, None).is_empty());
+
+        let events = engine.take_events();
+        assert!(matches!(
+            &events[..],
+            [Event::User { payload, .. }, Event::Book { .. }] if payload["type"] == "CANCELLATION"
+        ));
+    }
+
+    #[test]
+    fn json_bodies_should_deserialize_into_responses() {
+        let mut engine = engine();
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        engine
+            .submit(
+                alice,
+                order("a1", Side::Sell, dec!(0.50), dec!(10), OrderType::GTC),
+            )
+            .unwrap();
+        engine
+            .submit(
+                bob,
+                order("b1", Side::Buy, dec!(0.50), dec!(4), OrderType::FOK),
+            )
+            .unwrap();
+
+        let order: OpenOrderResponse =
+            serde_json::from_value(engine.order_json("a1").unwrap()).unwrap();
+        assert_eq!(order.size_matched, dec!(4));
+        assert_eq!(order.status, OrderStatusType::Live);
+
+        let trades = engine.trades_json_for(alice, None, None);
+        let trade: TradeResponse = serde_json::from_value(trades[0].clone()).unwrap();
+        assert_eq!(trade.trader_side, TraderSide::Maker);
+        assert_eq!(trade.owner, bob);
+        assert_eq!(trade.price, dec!(0.50));
+    }
+}
@@ -0,0 +1,392 @@
+//! REST API of a [`MockExchange`](super::MockExchange).
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use alloy::primitives::keccak256;
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE;
+use chrono::Utc;
+use http_body_util::{BodyExt as _, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{CONTENT_TYPE, HeaderMap};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use serde_with::{DisplayFromStr, serde_as};
+use tokio::net::TcpListener;
+
+use super::State;
+use super::engine::{NewOrder, Placement};
+use crate::auth::ApiKey;
+use crate::auth::l1::POLY_ADDRESS;
+use crate::auth::l2::POLY_API_KEY;
+use crate::clob::types::{OrderType, Side};
+use crate::types::{Address, B256, Decimal, U256};
+
+/// Cursor the API returns on the last page.
+const END_CURSOR: &str = "LTE=";
+
+/// A JSON response body with its status.
+type Reply = (StatusCode, Value);
+
+/// Accepts HTTP/1 connections on `listener` until the task is aborted or the listener fails.
+pub(super) async fn serve(listener: TcpListener, state: Arc<State>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        let state = Arc::clone(&state);
+
+        tokio::spawn(async move {
+            let service = service_fn(move |request| handle(Arc::clone(&state), request));
+            // Clients hanging up mid-request are not interesting to a test
+            _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+async fn handle(
+    state: Arc<State>,
+    request: Request<Incoming>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let (parts, body) = request.into_parts();
+    let body = body
+        .collect()
+        .await
+        .map(http_body_util::Collected::to_bytes)
+        .unwrap_or_default();
+    let query = parts.uri.query().unwrap_or_default();
+
+    let (status, body) = route(
+        &state,
+        &parts.method,
+        parts.uri.path(),
+        query,
+        &parts.headers,
+        &body,
+    )
+    .unwrap_or_else(|reply| reply);
+
+    Ok(Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Full::new(Bytes::from(body.to_string())))
+        .unwrap_or_default())
+}
+
+fn route(
+    state: &State,
+    method: &Method,
+    path: &str,
+    query: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<Reply, Reply> {
+    let ok = |value: Value| Ok((StatusCode::OK, value));
+
+    match (method, path.trim_end_matches('/')) {
+        (&Method::GET, "") => ok(json!("OK")),
+        (&Method::GET, "/time") => ok(json!(Utc::now().timestamp())),
+        (&Method::POST, "/auth/api-key") | (&Method::GET, "/auth/derive-api-key") => {
+            create_api_key(state, headers)
+        }
+        (&Method::GET, "/tick-size") => {
+            let market = market(state, query)?;
+            ok(json!({ "minimum_tick_size": market.tick_size.as_decimal() }))
+        }
+        (&Method::GET, "/neg-risk") => ok(json!({ "neg_risk": market(state, query)?.neg_risk })),
+        (&Method::GET, "/fee-rate") => {
+            ok(json!({ "base_fee": market(state, query)?.fee_rate_bps }))
+        }
+        (&Method::GET, "/book") => {
+            let token_id = token_id(query)?;
+            let book = state.engine().book(token_id).ok_or_else(not_found)?;
+            ok(json!(book))
+        }
+        (&Method::GET, "/midpoint") => {
+            let token_id = token_id(query)?;
+            let book = state.engine().book(token_id).ok_or_else(not_found)?;
+            match (book.bids.first(), book.asks.first()) {
+                (Some(bid), Some(ask)) => {
+                    ok(json!({ "mid": (bid.price + ask.price) / Decimal::TWO }))
+                }
+                _ => Err(error(
+                    StatusCode::NOT_FOUND,
+                    "No orderbook exists for the requested token id",
+                )),
+            }
+        }
+        (&Method::POST, "/order") => {
+            let owner = owner(state, headers)?;
+            let order = new_order(parse(body)?)?;
+            let placement = state
+                .update(|engine| engine.submit(owner, order))
+                .map_err(|e| error(StatusCode::BAD_REQUEST, e))?;
+            ok(placement_json(&placement))
+        }
+        (&Method::POST, "/orders") => {
+            let owner = owner(state, headers)?;
+            // Like the CLOB, a malformed order fails the whole batch before anything is placed
+            let orders = parse::<Vec<OrderBody>>(body)?
+                .into_iter()
+                .map(new_order)
+                .collect::<Result<Vec<_>, _>>()?;
+            let responses: Vec<_> = orders
+                .into_iter()
+                .map(|order| {
+                    let placement = state.update(|engine| engine.submit(owner, order));
+                    match placement {
+                        Ok(placement) => placement_json(&placement),
+                        Err(e) => rejection_json(&e),
+                    }
+                })
+                .collect();
+            ok(json!(responses))
+        }
+        (&Method::DELETE, "/order") => {
+            let owner = owner(state, headers)?;
+            let body: CancelOrderBody = parse(body)?;
+            ok(cancel(state, owner, vec![body.order_id]))
+        }
+        (&Method::DELETE, "/orders") => {
+            let owner = owner(state, headers)?;
+            ok(cancel(state, owner, parse(body)?))
+        }
+        (&Method::DELETE, "/cancel-all") => {
+            let owner = owner(state, headers)?;
+            let ids = state.engine().live_order_ids(owner, None, None);
+            ok(cancel(state, owner, ids))
+        }
+        (&Method::DELETE, "/cancel-market-orders") => {
+            let owner = owner(state, headers)?;
+            let filter: Filter = parse(body)?;
+            let ids = state
+                .engine()
+                .live_order_ids(owner, filter.market, filter.asset_id);
+            ok(cancel(state, owner, ids))
+        }
+        (&Method::GET, "/data/orders") => {
+            let owner = owner(state, headers)?;
+            let filter: Filter = parse_query(query)?;
+            let engine = state.engine();
+            let orders: Vec<_> = engine
+                .live_order_ids(owner, filter.market, filter.asset_id)
+                .into_iter()
+                .filter(|id| filter.id.as_ref().is_none_or(|filter| filter == id))
+                .filter_map(|id| engine.order_json(&id))
+                .collect();
+            ok(page(&orders))
+        }
+        (&Method::GET, "/data/trades") => {
+            let owner = owner(state, headers)?;
+            let filter: Filter = parse_query(query)?;
+            let trades: Vec<_> = state
+                .engine()
+                .trades_json_for(owner, filter.market, filter.asset_id)
+                .into_iter()
+                .filter(|trade| filter.id.as_ref().is_none_or(|id| trade["id"] == **id))
+                .collect();
+            ok(page(&trades))
+        }
+        (&Method::GET, path) if path.starts_with("/data/order/") => {
+            let owner = owner(state, headers)?;
+            let id = path.trim_start_matches("/data/order/");
+            state
+                .engine()
+                .order_json(id)
+                .filter(|order| order["owner"] == json!(owner))
+                .map(|order| (StatusCode::OK, order))
+                .ok_or_else(not_found)
+        }
+        _ => Err(not_found()),
+    }
+}
+
+/// Body of `POST /order`, the JSON form of a [`SignedOrder`](crate::clob::types::SignedOrder).
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderBody {
+    order: Value,
+    order_type: OrderType,
+    #[serde(default)]
+    post_only: Option<bool>,
+}
+
+/// The fields of a signed order the exchange acts on.
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignedOrderBody {
+    #[serde_as(as = "DisplayFromStr")]
+    token_id: U256,
+    maker: Address,
+    #[serde_as(as = "DisplayFromStr")]
+    maker_amount: U256,
+    #[serde_as(as = "DisplayFromStr")]
+    taker_amount: U256,
+    #[serde_as(as = "DisplayFromStr")]
+    expiration: U256,
+    side: Side,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CancelOrderBody {
+    order_id: String,
+}
+
+/// Query of `/data/orders` and `/data/trades`, and body of `DELETE /cancel-market-orders`.
+#[serde_as]
+#[derive(Default, Deserialize)]
+struct Filter {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    market: Option<B256>,
+    #[serde(default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    asset_id: Option<U256>,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+struct TokenQuery {
+    #[serde_as(as = "DisplayFromStr")]
+    token_id: U256,
+}
+
+/// Decodes `body`, identifying the order by the hash of its signed fields.
+fn new_order(body: OrderBody) -> Result<NewOrder, Reply> {
+    let id = keccak256(body.order.to_string()).to_string();
+    let order: SignedOrderBody = serde_json::from_value(body.order)
+        .map_err(|e| error(StatusCode::BAD_REQUEST, format!("invalid order: {e}")))?;
+
+    Ok(NewOrder {
+        id,
+        token_id: order.token_id,
+        maker: order.maker,
+        side: order.side,
+        maker_amount: order.maker_amount,
+        taker_amount: order.taker_amount,
+        expiration: order.expiration,
+        order_type: body.order_type,
+        post_only: body.post_only.unwrap_or_default(),
+    })
+}
+
+/// Registers the address in the `POLY_ADDRESS` header and returns its credentials, which are the
+/// same every time for a given address.
+fn create_api_key(state: &State, headers: &HeaderMap) -> Result<Reply, Reply> {
+    let address: Address = headers
+        .get(POLY_ADDRESS)
+        .and_then(|address| address.to_str().ok())
+        .and_then(|address| address.parse().ok())
+        .ok_or_else(|| error(StatusCode::UNAUTHORIZED, "Invalid L1 Request headers"))?;
+    let key = state.update(|engine| engine.register(address));
+    let secret = keccak256(key.as_bytes());
+
+    Ok((
+        StatusCode::OK,
+        json!({
+            "apiKey": key,
+            "secret": URL_SAFE.encode(secret),
+            "passphrase": keccak256(secret).to_string(),
+        }),
+    ))
+}
+
+/// API key in the `POLY_API_KEY` header, if it belongs to a registered account.
+fn owner(state: &State, headers: &HeaderMap) -> Result<ApiKey, Reply> {
+    headers
+        .get(POLY_API_KEY)
+        .and_then(|key| key.to_str().ok())
+        .and_then(|key| key.parse().ok())
+        .filter(|key| state.engine().is_registered(key))
+        .ok_or_else(|| error(StatusCode::UNAUTHORIZED, "Unauthorized/Invalid api key"))
+}
+
+fn market(state: &State, query: &str) -> Result<super::MockMarket, Reply> {
+    let token_id = token_id(query)?;
+    state
+        .engine()
+        .market(token_id)
+        .cloned()
+        .ok_or_else(not_found)
+}
+
+fn token_id(query: &str) -> Result<U256, Reply> {
+    parse_query::<TokenQuery>(query).map(|query| query.token_id)
+}
+
+fn cancel(state: &State, owner: ApiKey, ids: Vec<String>) -> Value {
+    let mut canceled = Vec::new();
+    let mut not_canceled = serde_json::Map::new();
+
+    state.update(|engine| {
+        for id in ids {
+            match engine.cancel(owner, &id) {
+                Ok(()) => canceled.push(id),
+                Err(e) => {
+                    not_canceled.insert(id, json!(e));
+                }
+            }
+        }
+    });
+
+    json!({ "canceled": canceled, "not_canceled": not_canceled })
+}
+
+fn placement_json(placement: &Placement) -> Value {
+    json!({
+        "errorMsg": "",
+        "makingAmount": placement.making_amount.normalize(),
+        "takingAmount": placement.taking_amount.normalize(),
+        "orderID": placement.order_id,
+        "status": placement.status,
+        "success": true,
+        "transactionsHashes": placement.transaction_hashes,
+        "tradeIds": placement.trade_ids,
+    })
+}
+
+fn rejection_json(message: &str) -> Value {
+    json!({
+        "errorMsg": message,
+        "makingAmount": "",
+        "takingAmount": "",
+        "orderID": "",
+        "status": "UNMATCHED",
+        "success": false,
+    })
+}
+
+fn page(data: &[Value]) -> Value {
+    json!({
+        "limit": data.len().max(1),
+        "count": data.len(),
+        "next_cursor": END_CURSOR,
+        "data": data,
+    })
+}
+
+fn parse<T: DeserializeOwned>(body: &[u8]) -> Result<T, Reply> {
+    serde_json::from_slice(body)
+        .map_err(|e| error(StatusCode::BAD_REQUEST, format!("invalid body: {e}")))
+}
+
+fn parse_query<T: DeserializeOwned>(query: &str) -> Result<T, Reply> {
+    serde_html_form::from_str(query)
+        .map_err(|e| error(StatusCode::BAD_REQUEST, format!("invalid query: {e}")))
+}
+
+fn not_found() -> Reply {
+    error(StatusCode::NOT_FOUND, "not found")
+}
+
+fn error<M: Into<String>>(status: StatusCode, message: M) -> Reply {
+    (status, json!({ "error": message.into() }))
+}
@@ -0,0 +1,309 @@
+//! A fake CLOB exchange for integration tests.
+//!
+//! **Feature flag:** `testing`
+//!
+//! [`MockExchange`] serves the subset of the CLOB REST API and WebSocket channels needed to
+//! authenticate, place, query and cancel orders from a local port, so strategies built on
+//! [`Client`](crate::clob::Client) and [`ws::Client`](crate::clob::ws::Client) can be exercised
+//! end to end without reaching Polymarket.
+//!
+//! The exchange keeps a simple matching book per token: orders cross with price-time priority,
+//! fill at the resting order's price, and honor `postOnly` and the GTC, GTD, FOK and FAK order
+//! types. Responses use the same JSON shapes as the real API, and every change is pushed to
+//! WebSocket subscribers as `book`, `order` and `trade` events.
+//!
+//! Order signatures, expirations, balances and allowances are **not** checked, and every token is
+//! matched on its own: buying one outcome never matches selling the complementary one.
+//!
+//! # Routes
+//!
+//! | Endpoint | Description |
+//! |----------|-------------|
+//! | `/time` | Current server timestamp |
+//! | `/auth/api-key`, `/auth/derive-api-key` | Deterministic credentials per `POLY_ADDRESS` |
+//! | `/tick-size`, `/neg-risk`, `/fee-rate` | Parameters of a [`MockMarket`] |
+//! | `/book`, `/midpoint` | Current book of a token |
+//! | `/order`, `/orders` | Place (`POST`) or cancel (`DELETE`) orders |
+//! | `/cancel-all`, `/cancel-market-orders` | Cancel several orders |
+//! | `/data/order/{id}`, `/data/orders` | Query orders |
+//! | `/data/trades` | Query trades |
+//! | `/ws/market`, `/ws/user` | WebSocket channels on [`MockExchange::ws_endpoint`] |
+//!
+//! # Examples
+//!
+//! ```rust, no_run
+//! use std::str::FromStr as _;
+//!
+//! use polymarket_client_sdk::POLYGON;
+//! use polymarket_client_sdk::auth::{LocalSigner, Signer as _};
+//! use polymarket_client_sdk::clob::testing::{MockExchange, MockMarket};
+//! use polymarket_client_sdk::clob::types::Side;
+//! use polymarket_client_sdk::clob::{Client, Config};
+//! use polymarket_client_sdk::types::{B256, U256, dec};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let token_id = U256::from(1);
+//! let exchange = MockExchange::start().await?;
+//! exchange.add_market(
+//!     MockMarket::builder()
+//!         .token_id(token_id)
+//!         .condition_id(B256::repeat_byte(1))
+//!         .build(),
+//! );
+//! exchange.add_liquidity(token_id, Side::Sell, dec!(0.55), dec!(100))?;
+//!
+//! let signer = LocalSigner::from_str("0x...")?.with_chain_id(Some(POLYGON));
+//! let client = Client::new(&exchange.host(), Config::default())?
+//!     .authentication_builder(&signer)
+//!     .authenticate()
+//!     .await?;
+//!
+//! let order = client
+//!     .limit_order()
+//!     .token_id(token_id)
+//!     .side(Side::Buy)
+//!     .price(dec!(0.55))
+//!     .size(dec!(10))
+//!     .build()
+//!     .await?;
+//! let response = client.post_order(client.sign(&signer, order).await?).await?;
+//!
+//! assert_eq!(exchange.trades()?.len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+mod engine;
+mod http;
+mod ws;
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use alloy::primitives::keccak256;
+use bon::Builder;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use self::engine::{Engine, Event, HOUSE, NewOrder};
+use crate::Result;
+use crate::clob::types::response::{OpenOrderResponse, OrderBookSummaryResponse, TradeResponse};
+use crate::clob::types::{OrderType, Side, TickSize};
+use crate::error::{Error, Kind};
+use crate::types::{Address, B256, Decimal, U256};
+
+/// Events buffered for each WebSocket connection before it starts missing them.
+const EVENT_CAPACITY: usize = 1024;
+
+/// A market the [`MockExchange`] accepts orders for, identified by one of its outcome tokens.
+#[non_exhaustive]
+#[derive(Clone, Debug, Builder)]
+#[builder(on(String, into))]
+pub struct MockMarket {
+    /// Outcome token traded on this book
+    pub token_id: U256,
+    /// Condition ID of the market the token belongs to
+    pub condition_id: B256,
+    /// Outcome represented by the token. Defaults to `"Yes"`.
+    #[builder(default = "Yes".to_owned())]
+    pub outcome: String,
+    /// Defaults to [`TickSize::Hundredth`]
+    #[builder(default = TickSize::Hundredth)]
+    pub tick_size: TickSize,
+    #[builder(default)]
+    pub neg_risk: bool,
+    #[builder(default)]
+    pub fee_rate_bps: u32,
+    /// Smallest accepted order size, in shares. Defaults to 1.
+    #[builder(default = Decimal::ONE)]
+    pub min_order_size: Decimal,
+}
+
+/// Shared between the exchange handle and its servers.
+#[derive(Debug)]
+struct State {
+    engine: Mutex<Engine>,
+    events: broadcast::Sender<Event>,
+}
+
+impl State {
+    fn engine(&self) -> MutexGuard<'_, Engine> {
+        // The engine validates before mutating, so recovering from a poisoned lock is sound
+        self.engine.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Runs `f` on the engine and pushes the events it produced to WebSocket subscribers.
+    fn update<T>(&self, f: impl FnOnce(&mut Engine) -> T) -> T {
+        let mut engine = self.engine();
+        let result = f(&mut engine);
+
+        for event in engine.take_events() {
+            // No subscribers is not an error
+            _ = self.events.send(event);
+        }
+
+        result
+    }
+}
+
+/// A stateful fake of the CLOB REST API and WebSocket channels, listening on local ports.
+///
+/// The servers run on the current Tokio runtime until the exchange is dropped.
+#[derive(Debug)]
+pub struct MockExchange {
+    state: Arc<State>,
+    http_port: u16,
+    ws_port: u16,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MockExchange {
+    /// Starts an exchange with no markets on two free ports of `127.0.0.1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ports cannot be bound.
+    pub async fn start() -> Result<Self> {
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        let state = Arc::new(State {
+            engine: Mutex::new(Engine::default()),
+            events,
+        });
+
+        let http = bind().await?;
+        let ws = bind().await?;
+        let http_port = local_port(&http)?;
+        let ws_port = local_port(&ws)?;
+
+        let tasks = vec![
+            tokio::spawn(http::serve(http, Arc::clone(&state))),
+            tokio::spawn(ws::serve(ws, Arc::clone(&state))),
+        ];
+
+        Ok(Self {
+            state,
+            http_port,
+            ws_port,
+            tasks,
+        })
+    }
+
+    /// Base URL of the REST API, to pass to [`Client::new`](crate::clob::Client::new).
+    #[must_use]
+    pub fn host(&self) -> String {
+        format!("http://127.0.0.1:{}", self.http_port)
+    }
+
+    /// Base URL of the WebSocket channels, to pass to
+    /// [`ws::Client::new`](crate::clob::ws::Client::new).
+    #[must_use]
+    pub fn ws_endpoint(&self) -> String {
+        format!("ws://127.0.0.1:{}", self.ws_port)
+    }
+
+    /// Lists `market` on the exchange, replacing any market with the same token.
+    pub fn add_market(&self, market: MockMarket) {
+        self.state.update(|engine| engine.add_market(market));
+    }
+
+    /// Rests a GTC order of `size` shares at `price` on the book of `token_id`, owned by a house
+    /// account, and returns its ID. The order matches like any other, so it can be used to seed
+    /// a book before the client under test trades against it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token is not listed, or the order would be rejected, e.g. because
+    /// `price` is not a multiple of the tick size or the order crosses the book.
+    pub fn add_liquidity(
+        &self,
+        token_id: U256,
+        side: Side,
+        price: Decimal,
+        size: Decimal,
+    ) -> Result<String> {
+        let fixed = |amount: Decimal| {
+            let amount = (amount * Decimal::from(1_000_000)).trunc();
+            U256::from(u128::try_from(amount).unwrap_or_default())
+        };
+        let (maker_amount, taker_amount) = match side {
+            Side::Sell => (fixed(size), fixed(size * price)),
+            _ => (fixed(size * price), fixed(size)),
+        };
+        let id = keccak256(Uuid::new_v4().as_bytes()).to_string();
+
+        let order = NewOrder {
+            id,
+            token_id,
+            maker: Address::ZERO,
+            side,
+            maker_amount,
+            taker_amount,
+            expiration: U256::ZERO,
+            order_type: OrderType::GTC,
+            post_only: true,
+        };
+
+        self.state
+            .update(|engine| engine.submit(HOUSE, order))
+            .map(|placement| placement.order_id)
+            .map_err(Error::validation)
+    }
+
+    /// Every order the exchange accepted, in the order it received them, including filled and
+    /// canceled ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an order does not decode as an [`OpenOrderResponse`].
+    pub fn orders(&self) -> Result<Vec<OpenOrderResponse>> {
+        parse_all(self.state.engine().orders_json())
+    }
+
+    /// Every trade the exchange matched, in order, as seen by the taker.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a trade does not decode as a [`TradeResponse`].
+    pub fn trades(&self) -> Result<Vec<TradeResponse>> {
+        parse_all(self.state.engine().trades_json())
+    }
+
+    /// Current book of `token_id`, or `None` if the token is not listed.
+    #[must_use]
+    pub fn book(&self, token_id: U256) -> Option<OrderBookSummaryResponse> {
+        self.state.engine().book(token_id)
+    }
+}
+
+impl Drop for MockExchange {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+async fn bind() -> Result<TcpListener> {
+    TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|e| Error::with_source(Kind::Internal, e))
+}
+
+fn local_port(listener: &TcpListener) -> Result<u16> {
+    listener
+        .local_addr()
+        .map(|addr| addr.port())
+        .map_err(|e| Error::with_source(Kind::Internal, e))
+}
+
+/// Bodies are built by the engine to match the response types, so a failure is a bug in the
+/// engine and is reported rather than skipped.
+fn parse_all<T: serde::de::DeserializeOwned>(values: Vec<serde_json::Value>) -> Result<Vec<T>> {
+    values
+        .into_iter()
+        .map(|value| {
+            serde_json::from_value(value).map_err(|e| Error::with_source(Kind::Internal, e))
+        })
+        .collect()
+}
@@ -0,0 +1,151 @@
+//! WebSocket channels of a [`MockExchange`](super::MockExchange).
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use futures::{SinkExt as _, StreamExt as _};
+use serde::Deserialize;
+use serde_json::Value;
+use serde_with::{DisplayFromStr, serde_as};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::error::RecvError;
+use tokio_tungstenite::tungstenite::Message;
+
+use super::State;
+use super::engine::Event;
+use crate::auth::ApiKey;
+use crate::types::{B256, U256};
+
+/// Accepts WebSocket connections on `listener` until the task is aborted or the listener fails.
+/// Both the market and the user channel are served on every path.
+pub(super) async fn serve(listener: TcpListener, state: Arc<State>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(connection(stream, Arc::clone(&state)));
+    }
+}
+
+async fn connection(stream: TcpStream, state: Arc<State>) {
+    let Ok(socket) = tokio_tungstenite::accept_async(stream).await else {
+        return;
+    };
+    let (mut sink, mut source) = socket.split();
+    let mut events = state.events.subscribe();
+    let mut subscriptions = Subscriptions::default();
+
+    loop {
+        let frames = tokio::select! {
+            message = source.next() => match message {
+                Some(Ok(Message::Text(text))) => subscriptions.handle(&state, text.as_str()),
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+            event = events.recv() => match event {
+                Ok(event) => subscriptions.filter(event).into_iter().collect(),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            },
+        };
+
+        for frame in frames {
+            if sink.send(Message::text(frame)).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Subscription message sent by [`ws::Client`](crate::clob::ws::Client).
+#[serde_as]
+#[derive(Deserialize)]
+struct SubscriptionMessage {
+    r#type: Option<String>,
+    operation: Option<String>,
+    #[serde(default)]
+    markets: Vec<B256>,
+    #[serde(default, rename = "assets_ids")]
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    asset_ids: Vec<U256>,
+    auth: Option<Auth>,
+}
+
+#[derive(Deserialize)]
+struct Auth {
+    #[serde(rename = "apiKey")]
+    api_key: ApiKey,
+}
+
+/// What a connection subscribed to.
+#[derive(Default)]
+struct Subscriptions {
+    assets: HashSet<U256>,
+    /// Account of the user channel, once authenticated
+    owner: Option<ApiKey>,
+    /// Markets of the user channel, where none means all of them
+    markets: HashSet<B256>,
+}
+
+impl Subscriptions {
+    /// Applies a message from the client and returns the frames to send back.
+    fn handle(&mut self, state: &State, text: &str) -> Vec<String> {
+        if text == "PING" {
+            return vec!["PONG".to_owned()];
+        }
+        let Ok(message) = serde_json::from_str::<SubscriptionMessage>(text) else {
+            return Vec::new();
+        };
+        let unsubscribe = message.operation.as_deref() == Some("unsubscribe");
+
+        match message.r#type.as_deref() {
+            Some("user") => {
+                if unsubscribe {
+                    for market in &message.markets {
+                        self.markets.remove(market);
+                    }
+                } else {
+                    let owner = message.auth.map(|auth| auth.api_key);
+                    if owner.is_some_and(|owner| state.engine().is_registered(&owner)) {
+                        self.owner = owner;
+                        self.markets.extend(message.markets);
+                    }
+                }
+                Vec::new()
+            }
+            _ if unsubscribe => {
+                for asset_id in &message.asset_ids {
+                    self.assets.remove(asset_id);
+                }
+                Vec::new()
+            }
+            _ => {
+                let engine = state.engine();
+                self.assets.extend(message.asset_ids.iter().copied());
+
+                message
+                    .asset_ids
+                    .into_iter()
+                    .filter_map(|asset_id| engine.book_json(asset_id))
+                    .map(|book| book.to_string())
+                    .collect()
+            }
+        }
+    }
+
+    /// The frame to send for `event`, if this connection subscribed to it.
+    fn filter(&self, event: Event) -> Option<String> {
+        let payload: Value = match event {
+            Event::Book { asset_id, payload } if self.assets.contains(&asset_id) => payload,
+            Event::User {
+                owner,
+                market,
+                payload,
+            } if self.owner == Some(owner)
+                && (self.markets.is_empty() || self.markets.contains(&market)) =>
+            {
+                payload
+            }
+            _ => return None,
+        };
+
+        Some(payload.to_string())
+    }
+}
@@ -0,0 +1,290 @@
+//! Keeps cached tick sizes in step with tick size changes from the market channel.
+//!
+//! The tick size of a token changes when its price gets close to either end of the range. A
+//! [`Client`] caches tick sizes, so without an update, orders built after a change are validated
+//! against the stale tick and rejected by the exchange. A [`TickSizeSync`] applies every
+//! [`TickSizeChange`] of the market channel to the cache with [`Client::set_tick_size`], then
+//! publishes a [`TickSizeUpdate`] naming the open orders on that token whose price is no longer a
+//! multiple of the new tick, so they can be re-quoted.
+//!
+//! Changes may be missed while the market channel reconnects, so after every reconnect the tick
+//! sizes of the subscribed tokens are fetched again and an update is published for those that
+//! changed in the meantime.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::str::FromStr as _;
+//!
+//! use polymarket_client_sdk::POLYGON;
+//! use polymarket_client_sdk::auth::{LocalSigner, Signer as _};
+//! use polymarket_client_sdk::clob::tick_size_sync::TickSizeSync;
+//! use polymarket_client_sdk::clob::{Client, Config, ws};
+//! use polymarket_client_sdk::types::U256;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let signer = LocalSigner::from_str("0x...")?.with_chain_id(Some(POLYGON));
+//! let client = Client::new("https://clob.polymarket.com", Config::default())?
+//!     .authentication_builder(&signer)
+//!     .authenticate()
+//!     .await?;
+//!
+//! let mut sync = TickSizeSync::new(client.clone());
+//! sync.listen(&ws::Client::default(), vec![U256::from_str("1234")?])?;
+//!
+//! let mut updates = sync.subscribe();
+//! while let Ok(update) = updates.recv().await {
+//!     for order in &update.invalid_orders {
+//!         println!("{} at {} must be re-quoted at {}", order.id, order.price, update.tick_size);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use futures::StreamExt as _;
+use futures::TryStreamExt as _;
+use futures::stream::BoxStream;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+
+use super::Client;
+use super::types::TickSize;
+use super::types::request::OrdersRequest;
+use super::types::response::OpenOrderResponse;
+use super::ws::{self, ChannelType, TickSizeChange};
+use crate::Result;
+use crate::auth::state::{Authenticated, State};
+use crate::auth::{Kind, Normal};
+use crate::error::Error;
+use crate::types::{Decimal, U256};
+use crate::ws::connection::ConnectionState;
+
+const UPDATES_CAPACITY: usize = 256;
+
+/// A tick size change applied to the cache.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TickSizeUpdate {
+    /// Asset/token identifier
+    pub asset_id: U256,
+    /// Tick size cached before the change, if any
+    pub old_tick_size: Option<TickSize>,
+    /// Tick size now cached
+    pub tick_size: TickSize,
+    /// Open orders on the token whose price is not valid at the new tick size
+    pub invalid_orders: Vec<OpenOrderResponse>,
+}
+
+/// Applies tick size changes to the cache of a [`Client`] and reports the open orders they
+/// invalidate.
+///
+/// Call [`Self::listen`] to apply the changes of the market channel as they arrive; changes from
+/// another source can be applied with [`Self::apply`].
+///
+/// Dropping it stops the background task started by [`Self::listen`].
+#[derive(Debug)]
+pub struct TickSizeSync<K: Kind = Normal> {
+    client: Client<Authenticated<K>>,
+    updates: broadcast::Sender<TickSizeUpdate>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl<K: Kind> TickSizeSync<K> {
+    /// Creates a sync updating the tick size cache of `client`, and looking up its open orders.
+    #[must_use]
+    pub fn new(client: Client<Authenticated<K>>) -> Self {
+        Self {
+            client,
+            updates: broadcast::Sender::new(UPDATES_CAPACITY),
+            task: None,
+        }
+    }
+
+    /// Applies the tick size changes of `asset_ids` from the market channel in the background,
+    /// fetching their tick sizes again whenever the channel reconnects.
+    ///
+    /// Replaces the background task of any previous call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the subscription cannot be created.
+    pub fn listen<S: State + 'static>(
+        &mut self,
+        ws: &ws::Client<S>,
+        asset_ids: Vec<U256>,
+    ) -> Result<()> {
+        let stream = ws.subscribe_tick_size_change(asset_ids.clone())?.boxed();
+        let state = ws
+            .connection_state_receiver(ChannelType::Market)
+            .ok_or(Error::validation("Market channel is not initialized"))?;
+
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.task = Some(tokio::spawn(run(
+            self.client.clone(),
+            self.updates.clone(),
+            asset_ids,
+            stream,
+            state,
+        )));
+
+        Ok(())
+    }
+
+    /// Applies `change` to the tick size cache and publishes the resulting update.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new tick size is unknown or the open orders cannot be fetched. The
+    /// cache is updated before the open orders are fetched.
+    pub async fn apply(&self, change: &TickSizeChange) -> Result<TickSizeUpdate> {
+        apply(&self.client, &self.updates, change).await
+    }
+
+    /// Subscribes to tick size updates.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<TickSizeUpdate> {
+        self.updates.subscribe()
+    }
+}
+
+impl<K: Kind> Drop for TickSizeSync<K> {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+}
+
+async fn run<K: Kind>(
+    client: Client<Authenticated<K>>,
+    updates: broadcast::Sender<TickSizeUpdate>,
+    asset_ids: Vec<U256>,
+    mut stream: BoxStream<'static, Result<TickSizeChange>>,
+    mut state: watch::Receiver<ConnectionState>,
+) {
+    let mut interrupted = false;
+
+    loop {
+        tokio::select! {
+            change = stream.next() => match change {
+                Some(Ok(change)) => log_failure(apply(&client, &updates, &change).await.map(drop)),
+                Some(Err(e)) => log_failure(Err(e)),
+                None => return,
+            },
+            Ok(()) = state.changed() => {
+                let connected = state.borrow_and_update().is_connected();
+                if !connected {
+                    interrupted = true;
+                } else if interrupted {
+                    interrupted = false;
+                    log_failure(refresh(&client, &updates, &asset_ids).await);
+                }
+            }
+        }
+    }
+}
+
+async fn apply<K: Kind>(
+    client: &Client<Authenticated<K>>,
+    updates: &broadcast::Sender<TickSizeUpdate>,
+    change: &TickSizeChange,
+) -> Result<TickSizeUpdate> {
+    let tick_size = TickSize::try_from(change.new_tick_size)?;
+    let old_tick_size = client.cached_tick_size(change.asset_id);
+
+    update(client, updates, change.asset_id, old_tick_size, tick_size).await
+}
+
+/// Fetches the tick size of every asset again, bypassing the cache, and applies those that
+/// changed.
+async fn refresh<K: Kind>(
+    client: &Client<Authenticated<K>>,
+    updates: &broadcast::Sender<TickSizeUpdate>,
+    asset_ids: &[U256],
+) -> Result<()> {
+    for &asset_id in asset_ids {
+        let cached = client.cached_tick_size(asset_id);
+        client.forget_tick_size(asset_id);
+
+        let tick_size = client.tick_size(asset_id).await?.minimum_tick_size;
+        if cached.is_some_and(|cached| cached != tick_size) {
+            update(client, updates, asset_id, cached, tick_size).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn update<K: Kind>(
+    client: &Client<Authenticated<K>>,
+    updates: &broadcast::Sender<TickSizeUpdate>,
+    asset_id: U256,
+    old_tick_size: Option<TickSize>,
+    tick_size: TickSize,
+) -> Result<TickSizeUpdate> {
+    client.set_tick_size(asset_id, tick_size);
+
+    let request = OrdersRequest::builder().asset_id(asset_id).build();
+    let open: Vec<OpenOrderResponse> = client
+        .stream_data(|client, cursor| client.orders(&request, cursor))
+        .try_collect()
+        .await?;
+
+    let invalid_orders = open
+        .into_iter()
+        .filter(|order| !is_valid_price(order.price, tick_size))
+        .collect();
+
+    let update = TickSizeUpdate {
+        asset_id,
+        old_tick_size,
+        tick_size,
+        invalid_orders,
+    };
+    // Sending only fails when nobody is subscribed
+    let _: std::result::Result<_, _> = updates.send(update.clone());
+
+    Ok(update)
+}
+
+fn log_failure(result: Result<()>) {
+    if let Err(e) = result {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = %e, "Failed to apply tick size change");
+        #[cfg(not(feature = "tracing"))]
+        let _: &_ = &e;
+    }
+}
+
+/// Whether `price` is a multiple of `tick_size` within the range it allows.
+fn is_valid_price(price: Decimal, tick_size: TickSize) -> bool {
+    let tick = tick_size.as_decimal();
+
+    price >= tick && price <= Decimal::ONE - tick && (price % tick).is_zero()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::dec;
+
+    #[test]
+    fn price_must_be_multiple_of_tick() {
+        assert!(is_valid_price(dec!(0.5), TickSize::Tenth));
+        assert!(is_valid_price(dec!(0.50), TickSize::Tenth));
+        assert!(!is_valid_price(dec!(0.55), TickSize::Tenth));
+        assert!(is_valid_price(dec!(0.55), TickSize::Hundredth));
+        assert!(is_valid_price(dec!(0.955), TickSize::Thousandth));
+    }
+
+    #[test]
+    fn price_must_be_within_tick_range() {
+        assert!(!is_valid_price(dec!(0.005), TickSize::Hundredth));
+        assert!(is_valid_price(dec!(0.99), TickSize::Hundredth));
+        assert!(!is_valid_price(dec!(0.99), TickSize::Tenth));
+        assert!(is_valid_price(dec!(0.9), TickSize::Tenth));
+    }
+}
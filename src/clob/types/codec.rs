@@ -0,0 +1,426 @@
+//! Versioned JSON and binary forms of [`SignableOrder`] and [`SignedOrder`], so that orders can be
+//! built, signed and posted by different processes.
+
+use alloy::primitives::{ChainId, Signature, U256};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Order, OrderType, OrderWithSignature, SignableOrder, SignedOrder, SignedOrderRepr};
+use crate::Result;
+use crate::error::Error;
+use crate::types::Address;
+
+/// Version of the JSON and binary forms written by [`SignableOrder::to_json`],
+/// [`SignableOrder::to_bytes`], [`SignedOrder::to_json`] and [`SignedOrder::to_bytes`]. Reading
+/// any other version fails.
+///
+/// The JSON form of a signed order is the body of a `POST /order` request with `version`,
+/// `chainId` and `negRisk` added, so that it can still be checked with [`SignedOrder::verify`].
+/// The JSON form of a signable order only has `version`, `order` without its signature,
+/// `orderType`, `postOnly`, `negRisk`, only if it was known when the order was built, and
+/// `managedNonce`, only if the order was built without an explicit nonce.
+///
+/// The binary form is a fixed sequence of big-endian fields:
+///
+/// | Field | Bytes |
+/// |-------|-------|
+/// | version | 1 |
+/// | kind: 0 signable, 1 signed | 1 |
+/// | salt, maker, signer, taker, token ID, maker amount, taker amount, expiration, nonce, fee rate | 32 per `uint256`, 20 per address |
+/// | side, signature type | 1 each |
+/// | post only: 0 unset, 1 false, 2 true | 1 |
+/// | length of the order type name, then the name | 1 + length |
+/// | signable only: neg risk: 0 unknown, 1 false, 2 true | 1 |
+/// | signable only: managed nonce | 1 |
+/// | signed only: signature as r, s, v | 65 |
+/// | signed only: owner | 16 |
+/// | signed only: chain ID | 8 |
+/// | signed only: neg risk | 1 |
+pub const ORDER_FORMAT_VERSION: u8 = 1;
+
+const SIGNABLE: u8 = 0;
+const SIGNED: u8 = 1;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignableOrderRepr {
+    version: u8,
+    order: OrderWithSignature,
+    order_type: OrderType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    post_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    neg_risk: Option<bool>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    managed_nonce: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VersionedSignedOrder<'order> {
+    version: u8,
+    #[serde(flatten)]
+    order: &'order SignedOrder,
+    chain_id: ChainId,
+    neg_risk: bool,
+}
+
+pub(super) fn signable_to_json(order: &SignableOrder) -> Result<String> {
+    let repr = SignableOrderRepr {
+        version: ORDER_FORMAT_VERSION,
+        order: OrderWithSignature::new(&order.order, None)?,
+        order_type: order.order_type.clone(),
+        post_only: order.post_only,
+        neg_risk: order.neg_risk,
+        managed_nonce: order.managed_nonce,
+    };
+
+    Ok(serde_json::to_string(&repr)?)
+}
+
+pub(super) fn signable_from_json(json: &str) -> Result<SignableOrder> {
+    let repr: SignableOrderRepr = serde_json::from_str(json)?;
+    check_version(repr.version)?;
+
+    let (order, signature) = repr.order.into_parts()?;
+    if signature.is_some() {
+        return Err(Error::validation(
+            "Expected a signable order, found a signed one",
+        ));
+    }
+
+    Ok(SignableOrder {
+        order,
+        order_type: repr.order_type,
+        post_only: repr.post_only,
+        neg_risk: repr.neg_risk,
+        managed_nonce: repr.managed_nonce,
+    })
+}
+
+pub(super) fn signable_to_bytes(order: &SignableOrder) -> Result<Vec<u8>> {
+    let mut bytes = vec![ORDER_FORMAT_VERSION, SIGNABLE];
+    write_order(&mut bytes, &order.order, order.post_only, &order.order_type)?;
+    bytes.push(optional_flag(order.neg_risk));
+    bytes.push(u8::from(order.managed_nonce));
+
+    Ok(bytes)
+}
+
+pub(super) fn signable_from_bytes(bytes: &[u8]) -> Result<SignableOrder> {
+    let mut reader = Reader::new(bytes, SIGNABLE)?;
+    let (order, post_only, order_type) = reader.order()?;
+    let neg_risk = reader.optional_flag("neg risk")?;
+    let managed_nonce = reader.flag()?;
+    reader.finish()?;
+
+    Ok(SignableOrder {
+        order,
+        order_type,
+        post_only,
+        neg_risk,
+        managed_nonce,
+    })
+}
+
+pub(super) fn signed_to_json(order: &SignedOrder) -> Result<String> {
+    let versioned = VersionedSignedOrder {
+        version: ORDER_FORMAT_VERSION,
+        order,
+        chain_id: order.chain_id,
+        neg_risk: order.neg_risk,
+    };
+
+    Ok(serde_json::to_string(&versioned)?)
+}
+
+pub(super) fn signed_from_json(json: &str) -> Result<SignedOrder> {
+    let repr: SignedOrderRepr = serde_json::from_str(json)?;
+    if repr.version.is_none() {
+        return Err(Error::validation("Order is missing its format version"));
+    }
+    repr.into_signed_order()
+}
+
+pub(super) fn signed_to_bytes(order: &SignedOrder) -> Result<Vec<u8>> {
+    let mut bytes = vec![ORDER_FORMAT_VERSION, SIGNED];
+    write_order(&mut bytes, &order.order, order.post_only, &order.order_type)?;
+    bytes.extend_from_slice(&order.signature.as_bytes());
+    bytes.extend_from_slice(order.owner.as_bytes());
+    bytes.extend_from_slice(&order.chain_id.to_be_bytes());
+    bytes.push(u8::from(order.neg_risk));
+
+    Ok(bytes)
+}
+
+pub(super) fn signed_from_bytes(bytes: &[u8]) -> Result<SignedOrder> {
+    let mut reader = Reader::new(bytes, SIGNED)?;
+    let (order, post_only, order_type) = reader.order()?;
+    let signature = Signature::from_raw_array(&reader.take::<65>()?)
+        .map_err(|e| Error::validation(format!("Invalid order signature: {e}")))?;
+    let owner = Uuid::from_bytes(reader.take()?);
+    let chain_id = ChainId::from_be_bytes(reader.take()?);
+    let neg_risk = reader.flag()?;
+    reader.finish()?;
+
+    Ok(SignedOrder {
+        order,
+        signature,
+        order_type,
+        owner,
+        post_only,
+        chain_id,
+        neg_risk,
+    })
+}
+
+pub(super) fn check_version(version: u8) -> Result<()> {
+    if version == ORDER_FORMAT_VERSION {
+        Ok(())
+    } else {
+        Err(Error::validation(format!(
+            "Unsupported order format version {version}, expected {ORDER_FORMAT_VERSION}"
+        )))
+    }
+}
+
+/// Encodes an optional boolean as 0 when unset, 1 when false and 2 when true.
+fn optional_flag(flag: Option<bool>) -> u8 {
+    flag.map_or(0, |flag| 1 + u8::from(flag))
+}
+
+fn write_order(
+    bytes: &mut Vec<u8>,
+    order: &Order,
+    post_only: Option<bool>,
+    order_type: &OrderType,
+) -> Result<()> {
+    bytes.extend_from_slice(&order.salt.to_be_bytes::<32>());
+    bytes.extend_from_slice(order.maker.as_slice());
+    bytes.extend_from_slice(order.signer.as_slice());
+    bytes.extend_from_slice(order.taker.as_slice());
+    for amount in [
+        order.tokenId,
+        order.makerAmount,
+        order.takerAmount,
+        order.expiration,
+        order.nonce,
+        order.feeRateBps,
+    ] {
+        bytes.extend_from_slice(&amount.to_be_bytes::<32>());
+    }
+    bytes.push(order.side);
+    bytes.push(order.signatureType);
+    bytes.push(optional_flag(post_only));
+
+    let name = serde_json::to_value(order_type)?;
+    let name = name.as_str().unwrap_or_default();
+    let len = u8::try_from(name.len())
+        .map_err(|_e| Error::validation(format!("Order type {name} is too long to encode")))?;
+    bytes.push(len);
+    bytes.extend_from_slice(name.as_bytes());
+
+    Ok(())
+}
+
+/// Reads the fields of the binary form in order.
+struct Reader<'bytes> {
+    bytes: &'bytes [u8],
+}
+
+impl<'bytes> Reader<'bytes> {
+    /// Starts reading `bytes` after checking their version and kind.
+    fn new(bytes: &'bytes [u8], kind: u8) -> Result<Self> {
+        let mut reader = Self { bytes };
+        check_version(reader.byte()?)?;
+
+        match (reader.byte()?, kind) {
+            (found, expected) if found == expected => Ok(reader),
+            (SIGNED, _) => Err(Error::validation(
+                "Expected a signable order, found a signed one",
+            )),
+            (SIGNABLE, _) => Err(Error::validation(
+                "Expected a signed order, found a signable one",
+            )),
+            (found, _) => Err(Error::validation(format!("Unknown order kind {found}"))),
+        }
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let (head, rest) = self
+            .bytes
+            .split_first_chunk::<N>()
+            .ok_or(Error::validation("Order bytes are truncated"))?;
+        self.bytes = rest;
+
+        Ok(*head)
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        self.take().map(u8::from_be_bytes)
+    }
+
+    fn u256(&mut self) -> Result<U256> {
+        self.take().map(U256::from_be_bytes::<32>)
+    }
+
+    fn address(&mut self) -> Result<Address> {
+        self.take::<20>().map(Address::from)
+    }
+
+    fn flag(&mut self) -> Result<bool> {
+        match self.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(Error::validation(format!("Invalid boolean byte {other}"))),
+        }
+    }
+
+    fn optional_flag(&mut self, name: &str) -> Result<Option<bool>> {
+        match self.byte()? {
+            0 => Ok(None),
+            1 => Ok(Some(false)),
+            2 => Ok(Some(true)),
+            other => Err(Error::validation(format!("Invalid {name} byte {other}"))),
+        }
+    }
+
+    fn order(&mut self) -> Result<(Order, Option<bool>, OrderType)> {
+        let order = Order {
+            salt: self.u256()?,
+            maker: self.address()?,
+            signer: self.address()?,
+            taker: self.address()?,
+            tokenId: self.u256()?,
+            makerAmount: self.u256()?,
+            takerAmount: self.u256()?,
+            expiration: self.u256()?,
+            nonce: self.u256()?,
+            feeRateBps: self.u256()?,
+            side: self.byte()?,
+            signatureType: self.byte()?,
+        };
+        let post_only = self.optional_flag("post only")?;
+
+        let len = usize::from(self.byte()?);
+        let (name, rest) = self
+            .bytes
+            .split_at_checked(len)
+            .ok_or(Error::validation("Order bytes are truncated"))?;
+        self.bytes = rest;
+        let name = str::from_utf8(name)
+            .map_err(|e| Error::validation(format!("Invalid order type name: {e}")))?;
+        let order_type = serde_json::from_value(serde_json::Value::from(name))?;
+
+        Ok((order, post_only, order_type))
+    }
+
+    fn finish(self) -> Result<()> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::validation(format!(
+                "Order bytes have {} trailing bytes",
+                self.bytes.len()
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::POLYGON;
+    use crate::clob::types::Side;
+
+    fn signed_order() -> SignedOrder {
+        SignedOrder::builder()
+            .order(Order {
+                salt: U256::from(42),
+                maker: Address::repeat_byte(1),
+                signer: Address::repeat_byte(2),
+                tokenId: U256::from(1234),
+                makerAmount: U256::from(34_000_000),
+                takerAmount: U256::from(100_000_000),
+                side: Side::Sell as u8,
+                ..Order::default()
+            })
+            .signature(Signature::new(U256::from(1), U256::from(2), true))
+            .order_type(OrderType::GTD)
+            .owner(Uuid::from_u128(7))
+            .post_only(true)
+            .chain_id(POLYGON)
+            .neg_risk(true)
+            .build()
+    }
+
+    #[test]
+    fn signed_order_should_round_trip() -> Result<()> {
+        let order = signed_order();
+
+        assert_eq!(SignedOrder::from_json(&order.to_json()?)?, order);
+        assert_eq!(SignedOrder::from_bytes(&order.to_bytes()?)?, order);
+
+        Ok(())
+    }
+
+    #[test]
+    fn signable_order_should_round_trip() -> Result<()> {
+        let order = SignableOrder::builder()
+            .order(signed_order().order)
+            .order_type(OrderType::Unknown("GTX".to_owned()))
+            .neg_risk(false)
+            .managed_nonce(true)
+            .build();
+
+        assert_eq!(SignableOrder::from_json(&order.to_json()?)?, order);
+        assert_eq!(SignableOrder::from_bytes(&order.to_bytes()?)?, order);
+
+        Ok(())
+    }
+
+    #[test]
+    fn post_body_should_not_deserialize_without_chain() -> Result<()> {
+        let order = signed_order();
+        let body = serde_json::to_value(&order)?;
+
+        serde_json::from_value::<SignedOrder>(body.clone()).unwrap_err();
+        SignedOrder::from_json(&body.to_string()).unwrap_err();
+
+        let read: SignedOrder = serde_json::from_str(&order.to_json()?)?;
+        assert_eq!(read, order);
+
+        Ok(())
+    }
+
+    #[test]
+    fn other_version_should_fail() -> Result<()> {
+        let mut bytes = signed_order().to_bytes()?;
+        bytes[0] = 2;
+        SignedOrder::from_bytes(&bytes).unwrap_err();
+
+        let json = signed_order()
+            .to_json()?
+            .replace(r#""version":1"#, r#""version":2"#);
+        SignedOrder::from_json(&json).unwrap_err();
+
+        let err = serde_json::from_str::<SignedOrder>(&json).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unsupported order format version 2")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn wrong_kind_or_length_should_fail() -> Result<()> {
+        let bytes = signed_order().to_bytes()?;
+
+        SignableOrder::from_bytes(&bytes).unwrap_err();
+        SignedOrder::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err();
+        SignedOrder::from_bytes(&[bytes.as_slice(), &[0]].concat()).unwrap_err();
+
+        Ok(())
+    }
+}
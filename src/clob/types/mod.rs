@@ -1,7 +1,10 @@
+use std::borrow::Cow;
 use std::fmt;
 
 use alloy::core::sol;
-use alloy::primitives::{Signature, U256};
+use alloy::dyn_abi::Eip712Domain;
+use alloy::primitives::{ChainId, Signature, U256};
+use alloy::sol_types::SolStruct as _;
 use bon::Builder;
 use rust_decimal_macros::dec;
 use serde::ser::{Error as _, SerializeStruct as _};
@@ -10,15 +13,22 @@ use serde_repr::Serialize_repr;
 use serde_with::{DisplayFromStr, serde_as};
 use strum_macros::Display;
 
-use crate::Result;
 use crate::auth::ApiKey;
 use crate::clob::order_builder::{LOT_SIZE_SCALE, USDC_DECIMALS};
+use crate::clob::types::response::{CancelOrdersResponse, PostOrderResponse};
 use crate::error::Error;
-use crate::types::Decimal;
+use crate::types::{Address, B256, Decimal};
+use crate::{Result, contract_config};
 
+mod codec;
 pub mod request;
 pub mod response;
 
+pub use codec::ORDER_FORMAT_VERSION;
+
+const ORDER_NAME: Option<Cow<'static, str>> = Some(Cow::Borrowed("Polymarket CTF Exchange"));
+const VERSION: Option<Cow<'static, str>> = Some(Cow::Borrowed("1"));
+
 // Re-export RFQ types for convenient access
 #[cfg(feature = "rfq")]
 pub use request::{
@@ -422,6 +432,25 @@ impl<'de> Deserialize<'de> for TickSize {
     }
 }
 
+/// Per-token market parameters that order building and signing otherwise look up over HTTP.
+///
+/// Passing them to [`OrderBuilder::build_offline`](crate::clob::order_builder::OrderBuilder)
+/// builds an order without any network access, e.g. in latency-sensitive quoting loops or on an
+/// air-gapped signing machine.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Builder)]
+pub struct MarketParams {
+    /// Minimum tick size of the token, as returned by
+    /// [`Client::tick_size`](crate::clob::Client::tick_size)
+    pub tick_size: TickSize,
+    /// Base fee rate of the token in basis points, as returned by
+    /// [`Client::fee_rate_bps`](crate::clob::Client::fee_rate_bps)
+    pub fee_rate_bps: u32,
+    /// Whether the token belongs to a negative risk market, as returned by
+    /// [`Client::neg_risk`](crate::clob::Client::neg_risk)
+    pub neg_risk: bool,
+}
+
 sol! {
     /// Alloy solidity type representing an order in the context of the Polymarket exchange
     ///
@@ -464,6 +493,10 @@ fn ser_salt<S: Serializer>(value: &U256, serializer: S) -> std::result::Result<S
     serializer.serialize_u64(v)
 }
 
+fn de_salt<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<U256, D::Error> {
+    u64::deserialize(deserializer).map(U256::from)
+}
+
 #[non_exhaustive]
 #[derive(Clone, Debug, Default, Serialize, Builder, PartialEq)]
 pub struct SignableOrder {
@@ -471,6 +504,66 @@ pub struct SignableOrder {
     pub order_type: OrderType,
     #[serde(rename = "postOnly", skip_serializing_if = "Option::is_none")]
     pub post_only: Option<bool>,
+    /// Whether the token belongs to a negative risk market, if known when the order was built.
+    /// [`Client::sign`](crate::clob::Client::sign) looks it up otherwise. It is not part of the
+    /// serialized order.
+    #[serde(skip)]
+    pub neg_risk: Option<bool>,
+    /// Whether the order was built without an explicit nonce, in which case
+    /// [`Client::sign`](crate::clob::Client::sign) replaces it with the nonce synced by a
+    /// `NonceManager`, if any. It is not part of the serialized order.
+    #[serde(skip)]
+    #[builder(default)]
+    pub managed_nonce: bool,
+}
+
+impl SignableOrder {
+    /// Hash of the order as computed by the exchange contract settling it on `chain_id`, which
+    /// depends on whether the token belongs to a negative risk market. This is the hash signed by
+    /// [`Client::sign`](crate::clob::Client::sign).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no exchange contract is configured for `chain_id` and `neg_risk`.
+    pub fn order_hash(&self, chain_id: ChainId, neg_risk: bool) -> Result<B256> {
+        order_hash(&self.order, chain_id, neg_risk)
+    }
+
+    /// Writes the order as versioned JSON, see [`ORDER_FORMAT_VERSION`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the order has an unknown side.
+    pub fn to_json(&self) -> Result<String> {
+        codec::signable_to_json(self)
+    }
+
+    /// Reads an order written by [`Self::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is malformed, of another version, or holds a signed order.
+    pub fn from_json(json: &str) -> Result<Self> {
+        codec::signable_from_json(json)
+    }
+
+    /// Writes the order in the compact binary form, see [`ORDER_FORMAT_VERSION`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name of the order type is longer than 255 bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        codec::signable_to_bytes(self)
+    }
+
+    /// Reads an order written by [`Self::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` are malformed, of another version, or hold a signed order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        codec::signable_from_bytes(bytes)
+    }
 }
 
 #[non_exhaustive]
@@ -481,40 +574,287 @@ pub struct SignedOrder {
     pub order_type: OrderType,
     pub owner: ApiKey,
     pub post_only: Option<bool>,
+    /// Chain the order was signed for
+    pub chain_id: ChainId,
+    /// Whether the token belongs to a negative risk market, which selects the exchange contract
+    /// the order was signed for
+    pub neg_risk: bool,
 }
 
-/// Helper struct for serializing Order with signature injected.
-/// This avoids the overhead of `serde_json::to_value()` followed by mutation.
+impl SignedOrder {
+    /// Hash of the order as computed by the exchange contract it was signed for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no exchange contract is configured for the order's chain and neg risk
+    /// flag.
+    pub fn order_hash(&self) -> Result<B256> {
+        order_hash(&self.order, self.chain_id, self.neg_risk)
+    }
+
+    /// Recovers the address that signed the order and checks it is the order's `signer`, without
+    /// trusting whoever produced it. Returns the recovered address.
+    ///
+    /// Only the signature is checked. For proxy and Safe wallets, whether `signer` controls the
+    /// order's `maker` is left to the exchange.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature is malformed or was not made by the order's `signer` for
+    /// this order, chain and exchange.
+    pub fn verify(&self) -> Result<Address> {
+        let hash = self.order_hash()?;
+        let recovered = self
+            .signature
+            .recover_address_from_prehash(&hash)
+            .map_err(|e| Error::validation(format!("Unable to recover order signer: {e}")))?;
+
+        if recovered != self.order.signer {
+            return Err(Error::validation(format!(
+                "Order is signed by {recovered}, not by its signer {}",
+                self.order.signer
+            )));
+        }
+
+        Ok(recovered)
+    }
+
+    /// Writes the order as versioned JSON, see [`ORDER_FORMAT_VERSION`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the order has an unknown side.
+    pub fn to_json(&self) -> Result<String> {
+        codec::signed_to_json(self)
+    }
+
+    /// Reads an order written by [`Self::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is malformed, of another version, misses the chain or the neg
+    /// risk flag, or holds an order without a signature.
+    pub fn from_json(json: &str) -> Result<Self> {
+        codec::signed_from_json(json)
+    }
+
+    /// Writes the order in the compact binary form, see [`ORDER_FORMAT_VERSION`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name of the order type is longer than 255 bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        codec::signed_to_bytes(self)
+    }
+
+    /// Reads an order written by [`Self::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` are malformed, of another version, or hold a signable order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        codec::signed_from_bytes(bytes)
+    }
+
+    /// The side of this order, or [`Side::Unknown`] if the signed side is invalid.
+    #[must_use]
+    pub fn side(&self) -> Side {
+        Side::try_from(self.order.side).unwrap_or(Side::Unknown)
+    }
+
+    /// Number of shares exchanged by this order.
+    #[must_use]
+    pub fn size(&self) -> Decimal {
+        self.amounts().0
+    }
+
+    /// Amount of USDC exchanged by this order.
+    #[must_use]
+    pub fn notional(&self) -> Decimal {
+        self.amounts().1
+    }
+
+    /// Price per share implied by the maker and taker amounts.
+    #[must_use]
+    pub fn price(&self) -> Decimal {
+        let (size, notional) = self.amounts();
+        notional.checked_div(size).unwrap_or_default().normalize()
+    }
+
+    fn amounts(&self) -> (Decimal, Decimal) {
+        order_amounts(self.side(), self.order.makerAmount, self.order.takerAmount)
+    }
+}
+
+/// Which leg of [`Client::replace_order`](crate::clob::Client::replace_order) goes first.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ReplaceStrategy {
+    /// Cancel the old order, then post the new one only if the old one was canceled. Never leaves
+    /// both orders resting, at the cost of a window with neither.
+    #[default]
+    CancelFirst,
+    /// Post the new order, then cancel the old one only if the new one was accepted. Never leaves
+    /// a window without a quote, at the cost of both orders resting until the cancel lands.
+    PostFirst,
+}
+
+/// Outcome of each leg of [`Client::replace_order`](crate::clob::Client::replace_order).
+///
+/// A leg is `None` when it was not attempted because the first leg failed.
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct ReplaceOrderResult {
+    /// ID of the order being replaced
+    pub order_id: String,
+    /// Result of canceling the old order
+    pub cancel: Option<Result<CancelOrdersResponse>>,
+    /// Result of posting the new order
+    pub post: Option<Result<PostOrderResponse>>,
+}
+
+impl ReplaceOrderResult {
+    /// Whether the old order was reported as canceled.
+    #[must_use]
+    pub fn canceled(&self) -> bool {
+        matches!(&self.cancel, Some(Ok(response)) if response.canceled.contains(&self.order_id))
+    }
+
+    /// Whether the new order was accepted by the exchange.
+    #[must_use]
+    pub fn posted(&self) -> bool {
+        matches!(&self.post, Some(Ok(response)) if response.success)
+    }
+
+    /// Whether both legs succeeded, so the new order replaced the old one.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.canceled() && self.posted()
+    }
+}
+
+/// EIP-712 domain of `exchange`, under which orders it settles are signed.
+pub(crate) fn order_domain(chain_id: ChainId, exchange: Address) -> Eip712Domain {
+    Eip712Domain {
+        name: ORDER_NAME,
+        version: VERSION,
+        chain_id: Some(U256::from(chain_id)),
+        verifying_contract: Some(exchange),
+        ..Eip712Domain::default()
+    }
+}
+
+fn order_hash(order: &Order, chain_id: ChainId, neg_risk: bool) -> Result<B256> {
+    let exchange = contract_config(chain_id, neg_risk)
+        .ok_or(Error::missing_contract_config(chain_id, neg_risk))?
+        .exchange;
+
+    Ok(order.eip712_signing_hash(&order_domain(chain_id, exchange)))
+}
+
+/// Shares and USDC exchanged by an order, in that order. Buys exchange USDC for shares and sells
+/// exchange shares for USDC.
+pub(crate) fn order_amounts(
+    side: Side,
+    maker_amount: U256,
+    taker_amount: U256,
+) -> (Decimal, Decimal) {
+    let maker_amount = fixed_to_decimal(maker_amount);
+    let taker_amount = fixed_to_decimal(taker_amount);
+
+    match side {
+        Side::Sell => (maker_amount, taker_amount),
+        _ => (taker_amount, maker_amount),
+    }
+}
+
+/// Converts a fixed point amount with [`USDC_DECIMALS`] decimals, as used by signed orders.
+fn fixed_to_decimal(amount: U256) -> Decimal {
+    u128::try_from(amount)
+        .ok()
+        .and_then(|amount| i128::try_from(amount).ok())
+        .and_then(|amount| Decimal::try_from_i128_with_scale(amount, USDC_DECIMALS).ok())
+        .unwrap_or_default()
+}
+
+/// Order as the CLOB represents it, with the side as a string and the signature, if any, folded
+/// in. This is the wire format of [`SignedOrder`], also used for the JSON form of
+/// [`SignableOrder`].
 #[serde_as]
-#[derive(Serialize)]
-struct OrderWithSignature<'order> {
-    #[serde(serialize_with = "ser_salt")]
-    salt: &'order U256,
-    maker: &'order alloy::primitives::Address,
-    signer: &'order alloy::primitives::Address,
-    taker: &'order alloy::primitives::Address,
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderWithSignature {
+    #[serde(serialize_with = "ser_salt", deserialize_with = "de_salt")]
+    salt: U256,
+    maker: Address,
+    signer: Address,
+    taker: Address,
     #[serde_as(as = "DisplayFromStr")]
-    #[serde(rename = "tokenId")]
-    token_id: &'order U256,
+    token_id: U256,
     #[serde_as(as = "DisplayFromStr")]
-    #[serde(rename = "makerAmount")]
-    maker_amount: &'order U256,
+    maker_amount: U256,
     #[serde_as(as = "DisplayFromStr")]
-    #[serde(rename = "takerAmount")]
-    taker_amount: &'order U256,
+    taker_amount: U256,
     #[serde_as(as = "DisplayFromStr")]
-    expiration: &'order U256,
+    expiration: U256,
     #[serde_as(as = "DisplayFromStr")]
-    nonce: &'order U256,
+    nonce: U256,
     #[serde_as(as = "DisplayFromStr")]
-    #[serde(rename = "feeRateBps")]
-    fee_rate_bps: &'order U256,
+    fee_rate_bps: U256,
     /// Side serialized as "BUY"/"SELL" string (CLOB API requirement)
     side: Side,
-    #[serde(rename = "signatureType")]
     signature_type: u8,
     /// Signature injected into the order object
-    signature: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+}
+
+impl OrderWithSignature {
+    fn new(order: &Order, signature: Option<&Signature>) -> Result<Self> {
+        Ok(Self {
+            salt: order.salt,
+            maker: order.maker,
+            signer: order.signer,
+            taker: order.taker,
+            token_id: order.tokenId,
+            maker_amount: order.makerAmount,
+            taker_amount: order.takerAmount,
+            expiration: order.expiration,
+            nonce: order.nonce,
+            fee_rate_bps: order.feeRateBps,
+            side: Side::try_from(order.side)?,
+            signature_type: order.signatureType,
+            signature: signature.map(ToString::to_string),
+        })
+    }
+
+    /// Splits into the order and its signature, if any.
+    fn into_parts(self) -> Result<(Order, Option<Signature>)> {
+        let signature = self
+            .signature
+            .map(|signature| {
+                signature
+                    .parse()
+                    .map_err(|e| Error::validation(format!("Invalid order signature: {e}")))
+            })
+            .transpose()?;
+        let order = Order {
+            salt: self.salt,
+            maker: self.maker,
+            signer: self.signer,
+            taker: self.taker,
+            tokenId: self.token_id,
+            makerAmount: self.maker_amount,
+            takerAmount: self.taker_amount,
+            expiration: self.expiration,
+            nonce: self.nonce,
+            feeRateBps: self.fee_rate_bps,
+            side: Side::try_from(self.side as u8)? as u8,
+            signatureType: self.signature_type,
+        };
+
+        Ok((order, signature))
+    }
 }
 
 // CLOB expects a struct that has the `signature` "folded" into the `order` key
@@ -523,25 +863,9 @@ impl Serialize for SignedOrder {
         let len = if self.post_only.is_some() { 4 } else { 3 };
         let mut st = serializer.serialize_struct("SignedOrder", len)?;
 
-        // Convert numeric side to Side enum for string serialization
-        let side = Side::try_from(self.order.side).map_err(S::Error::custom)?;
-
         // Serialize order directly with signature injected, avoiding intermediate JSON tree
-        let order_with_sig = OrderWithSignature {
-            salt: &self.order.salt,
-            maker: &self.order.maker,
-            signer: &self.order.signer,
-            taker: &self.order.taker,
-            token_id: &self.order.tokenId,
-            maker_amount: &self.order.makerAmount,
-            taker_amount: &self.order.takerAmount,
-            expiration: &self.order.expiration,
-            nonce: &self.order.nonce,
-            fee_rate_bps: &self.order.feeRateBps,
-            side,
-            signature_type: self.order.signatureType,
-            signature: self.signature.to_string(),
-        };
+        let order_with_sig = OrderWithSignature::new(&self.order, Some(&self.signature))
+            .map_err(S::Error::custom)?;
 
         st.serialize_field("order", &order_with_sig)?;
         st.serialize_field("orderType", &self.order_type)?;
@@ -554,11 +878,59 @@ impl Serialize for SignedOrder {
     }
 }
 
+/// Owned form of the [`SignedOrder`] wire format, also holding the fields that only
+/// [`SignedOrder::to_json`] writes.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignedOrderRepr {
+    #[serde(default)]
+    version: Option<u8>,
+    order: OrderWithSignature,
+    order_type: OrderType,
+    owner: ApiKey,
+    #[serde(default)]
+    post_only: Option<bool>,
+    chain_id: ChainId,
+    neg_risk: bool,
+}
+
+impl SignedOrderRepr {
+    fn into_signed_order(self) -> Result<SignedOrder> {
+        if let Some(version) = self.version {
+            codec::check_version(version)?;
+        }
+        let (order, signature) = self.order.into_parts()?;
+
+        Ok(SignedOrder {
+            order,
+            signature: signature.ok_or(Error::validation("Order is missing its signature"))?,
+            order_type: self.order_type,
+            owner: self.owner,
+            post_only: self.post_only,
+            chain_id: self.chain_id,
+            neg_risk: self.neg_risk,
+        })
+    }
+}
+
+/// Reads the form written by [`SignedOrder::to_json`], with or without its `version`, but rejects
+/// any version other than [`ORDER_FORMAT_VERSION`]. The body of
+/// a `POST /order` request, as written by the [`Serialize`] implementation, does not name the chain
+/// or the exchange the order was signed for, so it cannot be read back.
+impl<'de> Deserialize<'de> for SignedOrder {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        SignedOrderRepr::deserialize(deserializer)?
+            .into_signed_order()
+            .map_err(de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::to_value;
 
     use super::*;
+    use crate::POLYGON;
     use crate::error::Validation;
 
     #[test]
@@ -716,6 +1088,8 @@ mod tests {
             order_type: OrderType::GTC,
             owner: ApiKey::nil(),
             post_only: None,
+            chain_id: POLYGON,
+            neg_risk: false,
         };
 
         let value = to_value(&signed_order).expect("serialize SignedOrder");
@@ -0,0 +1,163 @@
+//! Detection of the Polymarket wallet that funds a signer's orders.
+//!
+//! Polymarket deploys either a Proxy wallet (Magic/email accounts) or a Gnosis Safe (browser
+//! wallet accounts) at a deterministic address for every signer. [`detect`] derives both
+//! candidates and asks a Polygon JSON-RPC node which one is in use: a deployed wallet is preferred,
+//! then one holding collateral. When neither is, the signer is assumed to trade from its own EOA.
+//!
+//! The node is queried with its own HTTP client, so the requests are neither rate limited nor sent
+//! with the CLOB's default headers.
+
+use std::error::Error as StdError;
+use std::fmt;
+
+use alloy::primitives::{Bytes, ChainId, U256};
+use alloy::sol;
+use alloy::sol_types::SolCall as _;
+use reqwest::{Client, Method, Request};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use url::Url;
+
+use crate::clob::types::SignatureType;
+use crate::error::{Error, Kind};
+use crate::timeout::{self, DEFAULT_CONNECT_TIMEOUT, DEFAULT_REQUEST_TIMEOUT};
+use crate::types::Address;
+use crate::{Result, contract_config, derive_proxy_wallet, derive_safe_wallet};
+
+sol! {
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    result: Option<Bytes>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "JSON-RPC error {}: {}", self.code, self.message)
+    }
+}
+
+impl StdError for RpcError {}
+
+/// A wallet Polymarket may have deployed for a signer.
+#[derive(Debug)]
+struct Candidate {
+    address: Address,
+    signature_type: SignatureType,
+    deployed: bool,
+}
+
+/// Detects the funder and [`SignatureType`] of `signer` on `chain_id` by querying the JSON-RPC node
+/// at `rpc_host`. Returns `None` when the signer trades from its own EOA.
+///
+/// Errors if both candidate wallets are deployed, or both hold collateral, since the wallet
+/// in use cannot be told apart.
+pub(crate) async fn detect(
+    rpc_host: &Url,
+    signer: Address,
+    chain_id: ChainId,
+) -> Result<Option<(Address, SignatureType)>> {
+    let client = Client::builder()
+        .connect_timeout(DEFAULT_CONNECT_TIMEOUT)
+        .build()?;
+    let client = &client;
+
+    let derived = [
+        (derive_proxy_wallet(signer, chain_id), SignatureType::Proxy),
+        (
+            derive_safe_wallet(signer, chain_id),
+            SignatureType::GnosisSafe,
+        ),
+    ];
+
+    let mut candidates = Vec::with_capacity(derived.len());
+    for (address, signature_type) in derived {
+        let Some(address) = address else {
+            continue;
+        };
+        let code = call(client, rpc_host, "eth_getCode", json!([address, "latest"])).await?;
+        candidates.push(Candidate {
+            address,
+            signature_type,
+            deployed: !code.is_empty(),
+        });
+    }
+
+    let deployed: Vec<_> = candidates.iter().filter(|c| c.deployed).collect();
+    if let Some(candidate) = single(&deployed, "deployed")? {
+        return Ok(Some(candidate));
+    }
+
+    // Deposits can reach a wallet before Polymarket deploys it
+    let collateral = contract_config(chain_id, false)
+        .ok_or_else(|| Error::missing_contract_config(chain_id, false))?
+        .collateral;
+    let mut funded = Vec::new();
+    for candidate in &candidates {
+        let data = IERC20::balanceOfCall {
+            account: candidate.address,
+        }
+        .abi_encode();
+        let transaction = json!({ "to": collateral, "data": Bytes::from(data) });
+        let output = call(client, rpc_host, "eth_call", json!([transaction, "latest"])).await?;
+        let balance = IERC20::balanceOfCall::abi_decode_returns(&output)
+            .map_err(|e| Error::with_source(Kind::Internal, e))?;
+        if balance > U256::ZERO {
+            funded.push(candidate);
+        }
+    }
+
+    single(&funded, "funded")
+}
+
+/// Returns the only candidate in `candidates`, if any, and errors if there are several.
+fn single(candidates: &[&Candidate], state: &str) -> Result<Option<(Address, SignatureType)>> {
+    match candidates {
+        [] => Ok(None),
+        [candidate] => Ok(Some((candidate.address, candidate.signature_type))),
+        _ => Err(Error::validation(format!(
+            "Both the Proxy and the Safe wallet of the signer are {state}, cannot tell which one \
+             is in use. Please provide an explicit funder and signature type."
+        ))),
+    }
+}
+
+/// Sends a JSON-RPC request for `method` and returns its result.
+async fn call(client: &Client, rpc_host: &Url, method: &str, params: Value) -> Result<Bytes> {
+    let mut request: Request = client
+        .request(Method::POST, rpc_host.clone())
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        }))
+        .build()?;
+    *request.timeout_mut() = Some(timeout::scoped().unwrap_or(DEFAULT_REQUEST_TIMEOUT));
+
+    let response: RpcResponse = client
+        .execute(request)
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    match (response.result, response.error) {
+        (_, Some(error)) => Err(Error::with_source(Kind::Internal, error)),
+        (Some(result), None) => Ok(result),
+        (None, None) => Err(Error::validation(format!(
+            "JSON-RPC response to {method} has neither a result nor an error"
+        ))),
+    }
+}
@@ -5,8 +5,10 @@ use dashmap::mapref::one::{Ref, RefMut};
 use dashmap::{DashMap, Entry};
 use futures::Stream;
 use futures::StreamExt as _;
+use tokio::sync::watch;
 
 use super::interest::InterestTracker;
+use super::orderbook::OrderBookTracker;
 use super::subscription::{ChannelType, SubscriptionManager};
 use super::types::response::{
     BestBidAsk, BookUpdate, LastTradePrice, MarketResolved, MidpointUpdate, NewMarket,
@@ -121,11 +123,7 @@ impl Client<Unauthenticated> {
 
         Ok(Client {
             inner: Arc::new(ClientInner {
-                state: Authenticated {
-                    address,
-                    credentials,
-                    kind: Normal,
-                },
+                state: Authenticated::new(address, credentials, Normal),
                 config,
                 base_endpoint,
                 channels,
@@ -358,6 +356,55 @@ impl<S: State> Client<S> {
         }))
     }
 
+    /// Maintains a [`LocalOrderBook`](super::LocalOrderBook) for each of the specified assets.
+    ///
+    /// The returned tracker builds each book from the market channel's `book` snapshots and
+    /// `price_change` deltas. When a delta disagrees with the local book, or the stream lags, the
+    /// affected books are rebuilt through `rest`'s [`order_book`](crate::clob::Client::order_book).
+    ///
+    /// # Arguments
+    ///
+    /// * `asset_ids` - List of asset/token IDs to maintain books for
+    /// * `rest` - CLOB REST client used to resync books
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the subscription cannot be created or the WebSocket
+    /// connection is not established.
+    pub fn local_order_books<R>(
+        &self,
+        asset_ids: Vec<U256>,
+        rest: crate::clob::Client<R>,
+    ) -> Result<OrderBookTracker>
+    where
+        S: Send + Sync + 'static,
+        R: State + Send + Sync + 'static,
+    {
+        let stream = self.subscribe_market_messages(asset_ids.clone())?;
+
+        let client = Client {
+            inner: Arc::clone(&self.inner),
+        };
+
+        Ok(OrderBookTracker::spawn(
+            client,
+            rest,
+            asset_ids,
+            stream.boxed(),
+            self.inner.config.reconnect.clone(),
+        ))
+    }
+
+    /// Subscribes to all market channel messages for the specified assets.
+    pub(crate) fn subscribe_market_messages(
+        &self,
+        asset_ids: Vec<U256>,
+    ) -> Result<impl Stream<Item = Result<WsMessage>> + Send + use<S>> {
+        let resources = self.inner.get_or_create_channel(ChannelType::Market)?;
+
+        resources.subscriptions.subscribe_market(asset_ids)
+    }
+
     /// Get the current connection state for a specific channel.
     ///
     /// Returns [`ConnectionState::Disconnected`] if the channel has not been
@@ -370,6 +417,17 @@ impl<S: State> Client<S> {
         )
     }
 
+    /// Returns a receiver notified of connection state changes on a specific channel, or `None`
+    /// if the channel has not been initialized yet.
+    pub(crate) fn connection_state_receiver(
+        &self,
+        channel_type: ChannelType,
+    ) -> Option<watch::Receiver<ConnectionState>> {
+        self.inner
+            .channel(channel_type)
+            .map(|resources| resources.connection.state_receiver())
+    }
+
     /// Check if the WebSocket connection is established for a specific channel.
     ///
     /// Returns `false` if no subscriptions have been made yet for this channel.
@@ -452,7 +510,7 @@ impl<K: AuthKind> Client<Authenticated<K>> {
 
         resources
             .subscriptions
-            .subscribe_user(markets, &self.inner.state.credentials)
+            .subscribe_user(markets, &self.inner.state.credentials())
     }
 
     /// Subscribes to real-time order status updates for the authenticated user.
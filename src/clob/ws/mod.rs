@@ -5,11 +5,13 @@
 
 pub mod client;
 pub mod interest;
+pub mod orderbook;
 pub mod subscription;
 pub mod types;
 
 // Re-export commonly used types
 pub use client::Client;
+pub use orderbook::{LocalOrderBook, OrderBookTracker};
 pub use subscription::{ChannelType, SubscriptionInfo, SubscriptionTarget};
 pub use types::request::SubscriptionRequest;
 pub use types::response::{
@@ -0,0 +1,791 @@
+//! Locally maintained order books built from the market channel.
+//!
+//! A [`LocalOrderBook`] starts from a [`BookUpdate`] snapshot and applies the
+//! [`PriceChangeBatchEntry`] deltas that follow it. Each delta carries the server's view of the
+//! top of book (`best_bid`/`best_ask`) and the `hash` of the server's book after the change. When
+//! either no longer agrees with the local book, the book is considered out of sync and must be
+//! rebuilt from a fresh snapshot.
+//!
+//! The hash is computed like [`OrderBookSummaryResponse::hash`], so it also covers the market's
+//! tick size, minimum order size, negative risk flag and last trade price, which `book` snapshots
+//! do not carry. It is therefore only verified once the book was built from, or carried over
+//! from, a REST [`OrderBookSummaryResponse`]; until then only the top of book is checked.
+//!
+//! [`OrderBookTracker`] drives this process for a set of assets: it consumes the market channel,
+//! resyncs books through [`crate::clob::Client::order_book`] in the background, starting with an
+//! initial resync that provides the hashed market fields, and publishes every change on a
+//! [`watch`] channel per asset. An out-of-sync book is not published with the delta that broke it:
+//! the last book in sync is marked [stale](LocalOrderBook::is_stale) instead, and deltas are
+//! ignored until the resync replaces it. When the stream ends or fails, the tracker resubscribes
+//! with the exponential backoff of the WebSocket client's [`ReconnectConfig`], and rebuilds every
+//! book.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use backoff::ExponentialBackoff;
+use backoff::backoff::Backoff as _;
+use chrono::DateTime;
+use futures::StreamExt as _;
+use futures::stream::BoxStream;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+use super::client::Client;
+use super::types::response::{BookUpdate, OrderBookLevel, PriceChangeBatchEntry, WsMessage};
+use crate::Result;
+use crate::auth::state::State;
+use crate::clob::types::request::OrderBookSummaryRequest;
+use crate::clob::types::response::{OrderBookSummaryResponse, OrderSummary};
+use crate::clob::types::{Side, TickSize};
+use crate::error::Error;
+use crate::types::{B256, Decimal, U256};
+use crate::ws::config::ReconnectConfig;
+
+/// Order book for a single asset, maintained from WebSocket snapshots and deltas.
+///
+/// Levels are keyed by price, so the book is independent of the order in which the server lists
+/// them. A level whose size drops to zero is removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalOrderBook {
+    asset_id: U256,
+    market: B256,
+    timestamp: i64,
+    hash: Option<String>,
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+    /// Market fields covered by the book hash, known once the book was built from a summary
+    metadata: Option<Metadata>,
+    stale: bool,
+}
+
+/// Fields of an [`OrderBookSummaryResponse`] that the market channel's snapshots do not carry.
+#[derive(Debug, Clone, PartialEq)]
+struct Metadata {
+    min_order_size: Decimal,
+    neg_risk: bool,
+    tick_size: TickSize,
+    last_trade_price: Option<Decimal>,
+}
+
+impl LocalOrderBook {
+    /// Builds a book from a WebSocket `book` snapshot.
+    #[must_use]
+    pub fn from_snapshot(book: &BookUpdate) -> Self {
+        Self {
+            asset_id: book.asset_id,
+            market: book.market,
+            timestamp: book.timestamp,
+            hash: book.hash.clone(),
+            bids: levels(book.bids.iter().map(|level| (level.price, level.size))),
+            asks: levels(book.asks.iter().map(|level| (level.price, level.size))),
+            metadata: None,
+            stale: false,
+        }
+    }
+
+    /// Builds a book from a REST order book summary, as returned by
+    /// [`crate::clob::Client::order_book`].
+    #[must_use]
+    pub fn from_summary(summary: &OrderBookSummaryResponse) -> Self {
+        Self {
+            asset_id: summary.asset_id,
+            market: summary.market,
+            timestamp: summary.timestamp.timestamp_millis(),
+            hash: summary.hash.clone(),
+            bids: levels(summary.bids.iter().map(|level| (level.price, level.size))),
+            asks: levels(summary.asks.iter().map(|level| (level.price, level.size))),
+            metadata: Some(Metadata {
+                min_order_size: summary.min_order_size,
+                neg_risk: summary.neg_risk,
+                tick_size: summary.tick_size,
+                last_trade_price: summary.last_trade_price,
+            }),
+            stale: false,
+        }
+    }
+
+    /// Applies a single `price_change` entry to this book.
+    ///
+    /// `timestamp` is the timestamp of the enclosing [`super::PriceChange`] message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry targets a different asset or if, after applying it, the
+    /// local best bid/ask no longer matches the `best_bid`/`best_ask` reported alongside the
+    /// entry, or the hash of the local book no longer matches the entry's `hash`. In the latter
+    /// cases the change is still applied, but the book should be rebuilt from a fresh snapshot.
+    pub fn apply_price_change(
+        &mut self,
+        change: &PriceChangeBatchEntry,
+        timestamp: i64,
+    ) -> Result<()> {
+        if change.asset_id != self.asset_id {
+            return Err(Error::validation(format!(
+                "price change for asset {} applied to order book for asset {}",
+                change.asset_id, self.asset_id
+            )));
+        }
+
+        let side = match change.side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+            Side::Unknown => {
+                return Err(Error::validation(format!(
+                    "price change for asset {} has unknown side",
+                    self.asset_id
+                )));
+            }
+        };
+
+        if let Some(size) = change.size {
+            if size.is_zero() {
+                side.remove(&change.price);
+            } else {
+                side.insert(change.price, size);
+            }
+        }
+
+        self.timestamp = timestamp;
+        self.hash.clone_from(&change.hash);
+
+        self.verify(change)
+    }
+
+    /// Checks the local book against the server's view carried by a price change: the top of
+    /// book and, when the market fields it covers are known, the book hash.
+    ///
+    /// The server reports an empty bid side as a best bid of `0` and an empty ask side as a best
+    /// ask of `1`.
+    fn verify(&self, change: &PriceChangeBatchEntry) -> Result<()> {
+        let best_bid = self
+            .bids
+            .last_key_value()
+            .map_or(Decimal::ZERO, |(p, _)| *p);
+        let best_ask = self
+            .asks
+            .first_key_value()
+            .map_or(Decimal::ONE, |(p, _)| *p);
+
+        let bid_matches = change.best_bid.is_none_or(|expected| expected == best_bid);
+        let ask_matches = change.best_ask.is_none_or(|expected| expected == best_ask);
+
+        if !bid_matches || !ask_matches {
+            return Err(Error::validation(format!(
+                "order book for asset {} is out of sync at hash {}: local best bid/ask {best_bid}/{best_ask}, \
+                 server {}/{}",
+                self.asset_id,
+                change.hash.as_deref().unwrap_or("<none>"),
+                change
+                    .best_bid
+                    .map_or_else(|| "-".to_owned(), |p| p.to_string()),
+                change
+                    .best_ask
+                    .map_or_else(|| "-".to_owned(), |p| p.to_string()),
+            )));
+        }
+
+        if let Some(expected) = change.hash.as_deref()
+            && let Some(summary) = self.summary()
+        {
+            let local = summary.hash()?;
+            if local != expected {
+                return Err(Error::validation(format!(
+                    "order book for asset {} is out of sync: local hash {local}, server {expected}",
+                    self.asset_id
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The book as a REST order book summary, listing each side from the worst price to the best
+    /// like the server. `None` until the market fields the summary covers are known.
+    fn summary(&self) -> Option<OrderBookSummaryResponse> {
+        let metadata = self.metadata.as_ref()?;
+        let summary = |(price, size): (&Decimal, &Decimal)| {
+            OrderSummary::builder().price(*price).size(*size).build()
+        };
+
+        Some(
+            OrderBookSummaryResponse::builder()
+                .market(self.market)
+                .asset_id(self.asset_id)
+                .timestamp(DateTime::from_timestamp_millis(self.timestamp)?)
+                .bids(self.bids.iter().map(summary).collect())
+                .asks(self.asks.iter().rev().map(summary).collect())
+                .min_order_size(metadata.min_order_size)
+                .neg_risk(metadata.neg_risk)
+                .tick_size(metadata.tick_size)
+                .maybe_last_trade_price(metadata.last_trade_price)
+                .build(),
+        )
+    }
+
+    /// Asset/token identifier of this book.
+    #[must_use]
+    pub fn asset_id(&self) -> U256 {
+        self.asset_id
+    }
+
+    /// Market condition ID of this book.
+    #[must_use]
+    pub fn market(&self) -> B256 {
+        self.market
+    }
+
+    /// Unix timestamp in milliseconds of the last snapshot or delta applied.
+    #[must_use]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Server hash of the book state this book reflects, if the server provided one.
+    #[must_use]
+    pub fn hash(&self) -> Option<&str> {
+        self.hash.as_deref()
+    }
+
+    /// Whether this book fell out of sync with the server and awaits a resync. A stale book
+    /// reflects the last state that was in sync, so its levels may be outdated.
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    /// Highest bid level, if any.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<OrderBookLevel> {
+        self.bids
+            .last_key_value()
+            .map(|(price, size)| level(*price, *size))
+    }
+
+    /// Lowest ask level, if any.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<OrderBookLevel> {
+        self.asks
+            .first_key_value()
+            .map(|(price, size)| level(*price, *size))
+    }
+
+    /// Midpoint between the best bid and best ask, if both sides are populated.
+    #[must_use]
+    pub fn midpoint(&self) -> Option<Decimal> {
+        let bid = self.best_bid()?;
+        let ask = self.best_ask()?;
+
+        Some((bid.price + ask.price) / Decimal::TWO)
+    }
+
+    /// Difference between the best ask and best bid, if both sides are populated.
+    #[must_use]
+    pub fn spread(&self) -> Option<Decimal> {
+        let bid = self.best_bid()?;
+        let ask = self.best_ask()?;
+
+        Some(ask.price - bid.price)
+    }
+
+    /// Up to `depth` bid levels, best (highest) price first.
+    #[must_use]
+    pub fn bids(&self, depth: usize) -> Vec<OrderBookLevel> {
+        self.bids
+            .iter()
+            .rev()
+            .take(depth)
+            .map(|(price, size)| level(*price, *size))
+            .collect()
+    }
+
+    /// Up to `depth` ask levels, best (lowest) price first.
+    #[must_use]
+    pub fn asks(&self, depth: usize) -> Vec<OrderBookLevel> {
+        self.asks
+            .iter()
+            .take(depth)
+            .map(|(price, size)| level(*price, *size))
+            .collect()
+    }
+}
+
+/// Keeps a [`LocalOrderBook`] up to date for each tracked asset.
+///
+/// Created by [`Client::local_order_books`]. The book for an asset is `None` until the first
+/// snapshot arrives, and [stale](LocalOrderBook::is_stale) while it is out of sync and a resync
+/// is pending or failing. Dropping the tracker stops the background task; call
+/// [`Client::unsubscribe_orderbook`] to release the underlying subscription.
+#[derive(Debug)]
+pub struct OrderBookTracker {
+    books: Arc<HashMap<U256, watch::Sender<Option<LocalOrderBook>>>>,
+    task: JoinHandle<()>,
+}
+
+impl OrderBookTracker {
+    pub(crate) fn spawn<S, R>(
+        client: Client<S>,
+        rest: crate::clob::Client<R>,
+        asset_ids: Vec<U256>,
+        stream: BoxStream<'static, Result<WsMessage>>,
+        reconnect: ReconnectConfig,
+    ) -> Self
+    where
+        S: State + Send + Sync + 'static,
+        R: State + Send + Sync + 'static,
+    {
+        let books: Arc<HashMap<_, _>> = Arc::new(
+            asset_ids
+                .iter()
+                .map(|id| (*id, watch::Sender::new(None)))
+                .collect(),
+        );
+
+        let task = tokio::spawn(run(
+            client,
+            rest,
+            asset_ids,
+            stream,
+            reconnect.into(),
+            Arc::clone(&books),
+        ));
+
+        Self { books, task }
+    }
+
+    /// Current book for `asset_id`, or `None` if the asset is not tracked or has no book yet.
+    #[must_use]
+    pub fn book(&self, asset_id: U256) -> Option<LocalOrderBook> {
+        self.books.get(&asset_id)?.borrow().clone()
+    }
+
+    /// Highest bid level for `asset_id`.
+    #[must_use]
+    pub fn best_bid(&self, asset_id: U256) -> Option<OrderBookLevel> {
+        self.books
+            .get(&asset_id)?
+            .borrow()
+            .as_ref()
+            .and_then(LocalOrderBook::best_bid)
+    }
+
+    /// Lowest ask level for `asset_id`.
+    #[must_use]
+    pub fn best_ask(&self, asset_id: U256) -> Option<OrderBookLevel> {
+        self.books
+            .get(&asset_id)?
+            .borrow()
+            .as_ref()
+            .and_then(LocalOrderBook::best_ask)
+    }
+
+    /// Up to `depth` levels of bids and asks for `asset_id`, best prices first.
+    #[must_use]
+    pub fn depth(
+        &self,
+        asset_id: U256,
+        depth: usize,
+    ) -> Option<(Vec<OrderBookLevel>, Vec<OrderBookLevel>)> {
+        let sender = self.books.get(&asset_id)?;
+        let book = sender.borrow();
+        let book = book.as_ref()?;
+
+        Some((book.bids(depth), book.asks(depth)))
+    }
+
+    /// Subscribes to changes of the book for `asset_id`.
+    ///
+    /// Returns `None` if the asset is not tracked by this tracker.
+    #[must_use]
+    pub fn subscribe(&self, asset_id: U256) -> Option<watch::Receiver<Option<LocalOrderBook>>> {
+        self.books.get(&asset_id).map(watch::Sender::subscribe)
+    }
+
+    /// Asset IDs tracked by this tracker.
+    pub fn asset_ids(&self) -> impl Iterator<Item = U256> + '_ {
+        self.books.keys().copied()
+    }
+}
+
+impl Drop for OrderBookTracker {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+type Books = Arc<HashMap<U256, watch::Sender<Option<LocalOrderBook>>>>;
+
+/// Rebuilds books from REST snapshots in background tasks, at most one per asset at a time.
+struct Resyncer<R: State> {
+    rest: Arc<crate::clob::Client<R>>,
+    books: Books,
+    pending: Arc<Mutex<HashSet<U256>>>,
+}
+
+impl<R: State + Send + Sync + 'static> Resyncer<R> {
+    fn start(&self, asset_id: U256) {
+        if !self
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(asset_id)
+        {
+            return;
+        }
+
+        let rest = Arc::clone(&self.rest);
+        let books = Arc::clone(&self.books);
+        let pending = Arc::clone(&self.pending);
+        tokio::spawn(async move {
+            if let Some(sender) = books.get(&asset_id) {
+                resync(&rest, asset_id, sender).await;
+            }
+            pending
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(&asset_id);
+        });
+    }
+}
+
+async fn run<S, R>(
+    client: Client<S>,
+    rest: crate::clob::Client<R>,
+    asset_ids: Vec<U256>,
+    mut stream: BoxStream<'static, Result<WsMessage>>,
+    mut backoff: ExponentialBackoff,
+    books: Books,
+) where
+    S: State + Send + Sync + 'static,
+    R: State + Send + Sync + 'static,
+{
+    let resyncer = Resyncer {
+        rest: Arc::new(rest),
+        books: Arc::clone(&books),
+        pending: Arc::default(),
+    };
+    // Snapshots on the market channel lack the market fields the book hash covers
+    for asset_id in &asset_ids {
+        resyncer.start(*asset_id);
+    }
+
+    loop {
+        while let Some(message) = stream.next().await {
+            if message.is_ok() {
+                backoff.reset();
+            }
+
+            match message {
+                Ok(WsMessage::Book(book)) => {
+                    if let Some(sender) = books.get(&book.asset_id) {
+                        let mut snapshot = LocalOrderBook::from_snapshot(&book);
+                        sender.send_modify(|current| {
+                            snapshot.metadata = current.take().and_then(|book| book.metadata);
+                            *current = Some(snapshot);
+                        });
+                    }
+                }
+                Ok(WsMessage::PriceChange(price_change)) => {
+                    for change in &price_change.price_changes {
+                        let Some(sender) = books.get(&change.asset_id) else {
+                            continue;
+                        };
+
+                        let (mut out_of_sync, mut stale) = (false, false);
+                        sender.send_if_modified(|book| match book {
+                            Some(book) if !book.stale => {
+                                let mut next = book.clone();
+                                if next
+                                    .apply_price_change(change, price_change.timestamp)
+                                    .is_ok()
+                                {
+                                    *book = next;
+                                } else {
+                                    book.stale = true;
+                                    out_of_sync = true;
+                                }
+                                true
+                            }
+                            // Deltas cannot be applied reliably until the book is rebuilt, which is
+                            // retried here in case the last resync failed
+                            Some(_) => {
+                                stale = true;
+                                false
+                            }
+                            None => false,
+                        });
+
+                        if out_of_sync {
+                            #[cfg(feature = "tracing")]
+                            tracing::warn!(asset_id = %change.asset_id, "Order book out of sync, resyncing");
+                        }
+                        if out_of_sync || stale {
+                            resyncer.start(change.asset_id);
+                        }
+                    }
+                }
+                Ok(WsMessage::TickSizeChange(tick_size_change)) => {
+                    if let Some(sender) = books.get(&tick_size_change.asset_id)
+                        && let Ok(tick_size) = TickSize::try_from(tick_size_change.new_tick_size)
+                    {
+                        update_metadata(sender, |metadata| metadata.tick_size = tick_size);
+                    }
+                }
+                Ok(WsMessage::LastTradePrice(last_trade)) => {
+                    if let Some(sender) = books.get(&last_trade.asset_id) {
+                        update_metadata(sender, |metadata| {
+                            metadata.last_trade_price = Some(last_trade.price);
+                        });
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %e, "Order book stream interrupted, resyncing all books");
+                    #[cfg(not(feature = "tracing"))]
+                    let _: &_ = &e;
+                    break;
+                }
+            }
+        }
+
+        // A stream that keeps ending right away must not make the tracker spin
+        if let Some(duration) = backoff.next_backoff() {
+            sleep(duration).await;
+        }
+
+        // Messages may have been missed, so re-establish the stream before rebuilding every book.
+        // Subscribing before unsubscribing keeps the reference count above zero, so no request is
+        // sent to the server and the connection stays open.
+        let Ok(next) = client.subscribe_market_messages(asset_ids.clone()) else {
+            return;
+        };
+        if client.unsubscribe_orderbook(&asset_ids).is_err() {
+            return;
+        }
+        stream = next.boxed();
+
+        for (asset_id, sender) in books.iter() {
+            sender.send_if_modified(|book| match book {
+                Some(book) if !book.stale => {
+                    book.stale = true;
+                    true
+                }
+                _ => false,
+            });
+            resyncer.start(*asset_id);
+        }
+    }
+}
+
+/// Applies `update` to the market fields of the book, if they are known. Not published, as the
+/// levels are unchanged.
+fn update_metadata(
+    sender: &watch::Sender<Option<LocalOrderBook>>,
+    update: impl FnOnce(&mut Metadata),
+) {
+    sender.send_if_modified(|book| {
+        if let Some(metadata) = book.as_mut().and_then(|book| book.metadata.as_mut()) {
+            update(metadata);
+        }
+        false
+    });
+}
+
+/// Replaces the book with a REST snapshot. On failure, the current book is kept as is.
+async fn resync<R: State>(
+    rest: &crate::clob::Client<R>,
+    asset_id: U256,
+    sender: &watch::Sender<Option<LocalOrderBook>>,
+) {
+    let request = OrderBookSummaryRequest::builder()
+        .token_id(asset_id)
+        .build();
+
+    match rest.order_book(&request).await {
+        Ok(summary) => {
+            sender.send_replace(Some(LocalOrderBook::from_summary(&summary)));
+        }
+        Err(e) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(%asset_id, error = %e, "Failed to resync order book");
+            #[cfg(not(feature = "tracing"))]
+            let _: &_ = &e;
+        }
+    }
+}
+
+fn levels(levels: impl Iterator<Item = (Decimal, Decimal)>) -> BTreeMap<Decimal, Decimal> {
+    levels.filter(|(_, size)| !size.is_zero()).collect()
+}
+
+fn level(price: Decimal, size: Decimal) -> OrderBookLevel {
+    OrderBookLevel { price, size }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn snapshot() -> LocalOrderBook {
+        let book: BookUpdate = serde_json::from_value(serde_json::json!({
+            "asset_id": "1",
+            "market": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "timestamp": "1000",
+            "bids": [
+                { "price": ".48", "size": "30" },
+                { "price": ".49", "size": "20" },
+                { "price": ".50", "size": "15" }
+            ],
+            "asks": [
+                { "price": ".54", "size": "10" },
+                { "price": ".53", "size": "60" },
+                { "price": ".52", "size": "25" }
+            ],
+            "hash": "a"
+        }))
+        .unwrap();
+
+        LocalOrderBook::from_snapshot(&book)
+    }
+
+    fn summary() -> LocalOrderBook {
+        let summary: OrderBookSummaryResponse = serde_json::from_value(serde_json::json!({
+            "market": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "asset_id": "1",
+            "timestamp": "1000",
+            "hash": "a",
+            "bids": [
+                { "price": "0.48", "size": "30" },
+                { "price": "0.49", "size": "20" },
+                { "price": "0.50", "size": "15" }
+            ],
+            "asks": [
+                { "price": "0.53", "size": "60" },
+                { "price": "0.52", "size": "25" }
+            ],
+            "min_order_size": "5",
+            "neg_risk": false,
+            "tick_size": "0.01"
+        }))
+        .unwrap();
+
+        LocalOrderBook::from_summary(&summary)
+    }
+
+    fn change(
+        side: &str,
+        price: &str,
+        size: &str,
+        best_bid: &str,
+        best_ask: &str,
+    ) -> PriceChangeBatchEntry {
+        serde_json::from_value(serde_json::json!({
+            "asset_id": "1",
+            "price": price,
+            "size": size,
+            "side": side,
+            "hash": "b",
+            "best_bid": best_bid,
+            "best_ask": best_ask
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn snapshot_orders_levels_by_price() {
+        let book = snapshot();
+
+        assert_eq!(book.best_bid().unwrap().price, dec!(0.50));
+        assert_eq!(book.best_ask().unwrap().price, dec!(0.52));
+        assert_eq!(book.midpoint(), Some(dec!(0.51)));
+        assert_eq!(book.spread(), Some(dec!(0.02)));
+
+        let bids: Vec<_> = book.bids(2).into_iter().map(|l| l.price).collect();
+        let asks: Vec<_> = book.asks(5).into_iter().map(|l| l.price).collect();
+        assert_eq!(bids, vec![dec!(0.50), dec!(0.49)]);
+        assert_eq!(asks, vec![dec!(0.52), dec!(0.53), dec!(0.54)]);
+    }
+
+    #[test]
+    fn apply_price_change_should_succeed() {
+        let mut book = snapshot();
+
+        book.apply_price_change(&change("BUY", "0.51", "5", "0.51", "0.52"), 2000)
+            .unwrap();
+        book.apply_price_change(&change("SELL", "0.52", "0", "0.51", "0.53"), 3000)
+            .unwrap();
+
+        assert_eq!(book.best_bid().unwrap().size, dec!(5));
+        assert_eq!(book.best_ask().unwrap().price, dec!(0.53));
+        assert_eq!(book.timestamp(), 3000);
+        assert_eq!(book.hash(), Some("b"));
+    }
+
+    #[test]
+    fn apply_price_change_detects_desync() {
+        let mut book = snapshot();
+
+        let err = book
+            .apply_price_change(&change("BUY", "0.47", "5", "0.51", "0.52"), 2000)
+            .unwrap_err();
+
+        assert!(
+            err.to_string().contains("out of sync"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn apply_price_change_verifies_hash() {
+        let mut book = summary();
+        let mut expected = book.clone();
+        expected.bids.insert(dec!(0.49), dec!(40));
+        expected.timestamp = 2000;
+        let hash = expected.summary().unwrap().hash().unwrap();
+
+        let mut change = change("BUY", "0.49", "40", "0.50", "0.52");
+        change.hash = Some(hash.clone());
+        book.apply_price_change(&change, 2000).unwrap();
+
+        assert_eq!(book.hash(), Some(hash.as_str()));
+        assert_eq!(book.bids(2)[1].size, dec!(40));
+    }
+
+    #[test]
+    fn apply_price_change_detects_divergence_below_top() {
+        let mut book = summary();
+        // The server's book also holds a level that the local book missed
+        let mut server = book.clone();
+        server.bids.insert(dec!(0.47), dec!(5));
+        server.bids.insert(dec!(0.49), dec!(40));
+        server.timestamp = 2000;
+
+        let mut change = change("BUY", "0.49", "40", "0.50", "0.52");
+        change.hash = Some(server.summary().unwrap().hash().unwrap());
+        let err = book.apply_price_change(&change, 2000).unwrap_err();
+
+        assert!(
+            err.to_string().contains("local hash"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn apply_price_change_treats_empty_side_as_bounds() {
+        let mut book = snapshot();
+
+        book.apply_price_change(&change("BUY", "0.48", "0", "0.50", "0.52"), 2000)
+            .unwrap();
+        book.apply_price_change(&change("BUY", "0.49", "0", "0.50", "0.52"), 2000)
+            .unwrap();
+        book.apply_price_change(&change("BUY", "0.50", "0", "0", "0.52"), 2000)
+            .unwrap();
+
+        assert!(book.best_bid().is_none(), "bids should be empty");
+        assert_eq!(book.midpoint(), None);
+    }
+}
@@ -282,11 +282,14 @@ impl SubscriptionManager {
 
         // Create filtered stream with its own receiver
         let mut rx = self.connection.subscribe();
+        let received = self.connection.received();
         let asset_ids_set: HashSet<U256> = asset_ids.into_iter().collect();
 
         Ok(try_stream! {
             loop {
-                match rx.recv().await {
+                let result = rx.recv().await;
+                received.notify_one();
+                match result {
                     Ok(msg) => {
                         // Filter messages by asset_id
                         let should_yield = match &msg {
@@ -390,10 +393,13 @@ impl SubscriptionManager {
 
         // Create stream for user messages
         let mut rx = self.connection.subscribe();
+        let received = self.connection.received();
 
         Ok(try_stream! {
             loop {
-                match rx.recv().await {
+                let result = rx.recv().await;
+                received.notify_one();
+                match result {
                     Ok(msg) => {
                         if msg.is_user() {
                             yield msg;
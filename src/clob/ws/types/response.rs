@@ -6,7 +6,7 @@ use serde_with::{DefaultOnNull, DisplayFromStr, NoneAsEmptyString, serde_as};
 use tracing::warn;
 
 use crate::auth::ApiKey;
-use crate::clob::types::{OrderStatusType, Side, TraderSide};
+use crate::clob::types::{OrderStatusType, Side, TradeStatusType, TraderSide};
 use crate::clob::ws::interest::MessageInterest;
 use crate::error::Kind;
 use crate::types::{B256, Decimal, U256};
@@ -321,6 +321,21 @@ pub enum TradeMessageStatus {
     Unknown(String),
 }
 
+impl From<&TradeMessageStatus> for TradeStatusType {
+    fn from(status: &TradeMessageStatus) -> Self {
+        match status {
+            TradeMessageStatus::Matched => Self::Matched,
+            TradeMessageStatus::Mined => Self::Mined,
+            TradeMessageStatus::Confirmed => Self::Confirmed,
+            TradeMessageStatus::Unknown(status) => match status.to_ascii_uppercase().as_str() {
+                "RETRYING" => Self::Retrying,
+                "FAILED" => Self::Failed,
+                _ => Self::Unknown(status.clone()),
+            },
+        }
+    }
+}
+
 /// User trade execution message (authenticated channel only).
 #[non_exhaustive]
 #[serde_as]
@@ -26,6 +26,10 @@ pub enum Kind {
     WebSocket,
     /// Error related to geographic restrictions blocking access
     Geoblock,
+    /// Error related to an order rejected by a pre-trade risk check
+    Risk,
+    /// Error related to a request that did not complete within its timeout
+    Timeout,
 }
 
 #[derive(Debug)]
@@ -214,6 +218,43 @@ impl From<Geoblock> for Error {
     }
 }
 
+/// Error indicating that an order was rejected by a pre-trade risk check and was not sent.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct RiskRejection {
+    /// Name of the check that rejected the order
+    pub check: String,
+    /// Why the order was rejected
+    pub reason: String,
+}
+
+impl RiskRejection {
+    pub fn new<C: Into<String>, R: Into<String>>(check: C, reason: R) -> Self {
+        Self {
+            check: check.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for RiskRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "order rejected by {} risk check: {}",
+            self.check, self.reason
+        )
+    }
+}
+
+impl StdError for RiskRejection {}
+
+impl From<RiskRejection> for Error {
+    fn from(err: RiskRejection) -> Self {
+        Error::with_source(Kind::Risk, err)
+    }
+}
+
 impl From<base64::DecodeError> for Error {
     fn from(e: base64::DecodeError) -> Self {
         Error::with_source(Kind::Internal, e)
@@ -222,7 +263,13 @@ impl From<base64::DecodeError> for Error {
 
 impl From<reqwest::Error> for Error {
     fn from(e: reqwest::Error) -> Self {
-        Error::with_source(Kind::Internal, e)
+        let kind = if e.is_timeout() {
+            Kind::Timeout
+        } else {
+            Kind::Internal
+        };
+
+        Error::with_source(kind, e)
     }
 }
 
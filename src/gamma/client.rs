@@ -26,13 +26,12 @@
 //! ```
 
 use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
 
 use async_stream::try_stream;
 use futures::Stream;
-use reqwest::{
-    Client as ReqwestClient, Method,
-    header::{HeaderMap, HeaderValue},
-};
+use reqwest::Method;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 #[cfg(feature = "tracing")]
@@ -51,7 +50,10 @@ use super::types::response::{
     SportsMarketTypesResponse, SportsMetadata, Tag, Team,
 };
 use crate::error::Error;
-use crate::{Result, ToQueryParams as _};
+use crate::retry::RetryPolicy;
+use crate::timeout::DEFAULT_REQUEST_TIMEOUT;
+use crate::transport::Transport;
+use crate::{HttpClient, Result, ToQueryParams as _};
 
 const MAX_LIMIT: i32 = 500;
 
@@ -78,7 +80,7 @@ const MAX_LIMIT: i32 = 500;
 #[derive(Clone, Debug)]
 pub struct Client {
     host: Url,
-    client: ReqwestClient,
+    client: HttpClient,
 }
 
 impl Default for Client {
@@ -99,13 +101,7 @@ impl Client {
     ///
     /// Returns an error if the URL is invalid or the HTTP client cannot be created.
     pub fn new(host: &str) -> Result<Client> {
-        let mut headers = HeaderMap::new();
-
-        headers.insert("User-Agent", HeaderValue::from_static("rs_clob_client"));
-        headers.insert("Accept", HeaderValue::from_static("*/*"));
-        headers.insert("Connection", HeaderValue::from_static("keep-alive"));
-        headers.insert("Content-Type", HeaderValue::from_static("application/json"));
-        let client = ReqwestClient::builder().default_headers(headers).build()?;
+        let client = HttpClient::new(RetryPolicy::default(), DEFAULT_REQUEST_TIMEOUT)?;
 
         Ok(Self {
             host: Url::parse(host)?,
@@ -113,6 +109,39 @@ impl Client {
         })
     }
 
+    /// Sets the [`RetryPolicy`] applied to every request made by this client.
+    ///
+    /// The default policy makes a single attempt.
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.client.set_retry_policy(retry_policy);
+        self
+    }
+
+    /// Sets the timeout of every request made by this client, covering the connection, the
+    /// request and reading the response. A request that times out fails with an error of kind
+    /// [`Kind::Timeout`](crate::error::Kind::Timeout).
+    ///
+    /// Defaults to [`DEFAULT_REQUEST_TIMEOUT`]. See [`timeout`](crate::timeout) to override it
+    /// for a single call.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client.set_timeout(timeout);
+        self
+    }
+
+    /// Sets the [`Transport`] every request made by this client is sent through, e.g. a
+    /// [`reqwest::Client`] configured with a proxy or timeouts, or a
+    /// [`Stack`](crate::transport::Stack) of middleware.
+    ///
+    /// Defaults to a [`reqwest::Client`] that gives up connecting after
+    /// [`DEFAULT_CONNECT_TIMEOUT`](crate::timeout::DEFAULT_CONNECT_TIMEOUT).
+    #[must_use]
+    pub fn with_transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.client.set_transport(Arc::new(transport));
+        self
+    }
+
     /// Returns the base URL of the API.
     #[must_use]
     pub fn host(&self) -> &Url {
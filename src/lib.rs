@@ -12,14 +12,43 @@ pub mod data;
 pub mod error;
 #[cfg(feature = "gamma")]
 pub mod gamma;
+#[cfg(any(
+    feature = "bridge",
+    feature = "clob",
+    feature = "data",
+    feature = "gamma"
+))]
+pub mod retry;
 #[cfg(feature = "rtds")]
 pub mod rtds;
 pub(crate) mod serde_helpers;
+#[cfg(any(
+    feature = "bridge",
+    feature = "clob",
+    feature = "data",
+    feature = "gamma"
+))]
+pub mod timeout;
+pub mod transport;
 pub mod types;
 #[cfg(any(feature = "ws", feature = "rtds"))]
 pub mod ws;
 
 use std::fmt::Write as _;
+#[cfg(any(
+    feature = "bridge",
+    feature = "clob",
+    feature = "data",
+    feature = "gamma"
+))]
+use std::sync::Arc;
+#[cfg(any(
+    feature = "bridge",
+    feature = "clob",
+    feature = "data",
+    feature = "gamma"
+))]
+use std::time::Duration;
 
 use alloy::primitives::ChainId;
 use alloy::primitives::{B256, b256, keccak256};
@@ -30,7 +59,7 @@ use phf::phf_map;
     feature = "data",
     feature = "gamma"
 ))]
-use reqwest::{Request, StatusCode, header::HeaderMap};
+use reqwest::{IntoUrl, Method, Request, RequestBuilder, Response, StatusCode, header::HeaderMap};
 use serde::Serialize;
 #[cfg(any(
     feature = "bridge",
@@ -40,7 +69,23 @@ use serde::Serialize;
 ))]
 use serde::de::DeserializeOwned;
 
+#[cfg(feature = "clob")]
+use crate::clob::rate_limit::RateLimiter;
 use crate::error::Error;
+#[cfg(any(
+    feature = "bridge",
+    feature = "clob",
+    feature = "data",
+    feature = "gamma"
+))]
+use crate::retry::RetryPolicy;
+#[cfg(any(
+    feature = "bridge",
+    feature = "clob",
+    feature = "data",
+    feature = "gamma"
+))]
+use crate::transport::Transport;
 use crate::types::{Address, address};
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -234,6 +279,144 @@ pub trait ToQueryParams: Serialize {
 
 impl<T: Serialize> ToQueryParams for T {}
 
+/// HTTP client used by the REST clients, pairing the [`Transport`] requests are sent through with
+/// the [`RetryPolicy`] that [`request`] applies to every call.
+#[cfg(any(
+    feature = "bridge",
+    feature = "clob",
+    feature = "data",
+    feature = "gamma"
+))]
+#[derive(Clone, Debug)]
+pub(crate) struct HttpClient {
+    /// Only used to build requests, which are sent through `transport`
+    client: reqwest::Client,
+    transport: Arc<dyn Transport>,
+    retry_policy: RetryPolicy,
+    /// Timeout of each attempt, unless overridden by [`timeout::within`]
+    timeout: Duration,
+    #[cfg(feature = "clob")]
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+#[cfg(any(
+    feature = "bridge",
+    feature = "clob",
+    feature = "data",
+    feature = "gamma"
+))]
+impl HttpClient {
+    /// Creates a client sending requests through a default [`reqwest::Client`].
+    pub(crate) fn new(retry_policy: RetryPolicy, timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(timeout::DEFAULT_CONNECT_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            transport: Arc::new(client.clone()),
+            client,
+            retry_policy,
+            timeout,
+            #[cfg(feature = "clob")]
+            rate_limiter: None,
+        })
+    }
+
+    #[cfg(feature = "clob")]
+    pub(crate) fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(Arc::new(rate_limiter));
+        self
+    }
+
+    #[cfg(feature = "clob")]
+    pub(crate) fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    pub(crate) fn request<U: IntoUrl>(&self, method: Method, url: U) -> RequestBuilder {
+        self.client.request(method, url)
+    }
+
+    #[cfg(any(feature = "bridge", feature = "data", feature = "gamma"))]
+    pub(crate) fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    #[cfg(any(feature = "bridge", feature = "data", feature = "gamma"))]
+    pub(crate) fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub(crate) fn set_transport(&mut self, transport: Arc<dyn Transport>) {
+        self.transport = transport;
+    }
+
+    /// The [`Transport`] requests are sent through, for callers that do not go through [`Self::execute`].
+    #[cfg(feature = "clob")]
+    pub(crate) fn transport(&self) -> &Arc<dyn Transport> {
+        &self.transport
+    }
+
+    /// Executes `request`, retrying it as allowed by the [`RetryPolicy`]. Every attempt first waits
+    /// for capacity from the rate limiter, if one is set. Requests signed with L1 or L2
+    /// authentication headers are sent once.
+    ///
+    /// Returns the first response that is not retried, which may still have a non-success status.
+    pub(crate) async fn execute(&self, mut request: Request) -> Result<Response> {
+        transport::insert_default_headers(request.headers_mut());
+        if request.timeout().is_none() {
+            *request.timeout_mut() = Some(timeout::scoped().unwrap_or(self.timeout));
+        }
+        let policy = &self.retry_policy;
+        let idempotent = policy.is_idempotent(request.method());
+        // Authentication headers sign a timestamp the server only accepts briefly, so resending
+        // them after a backoff could be rejected or be replayed outside the signer's control
+        #[cfg(feature = "clob")]
+        let idempotent = idempotent && !request.headers().contains_key(auth::l2::POLY_SIGNATURE);
+        let mut attempt = 1;
+
+        loop {
+            let retry = if idempotent && attempt < policy.max_attempts {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            #[cfg(feature = "clob")]
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire(&request).await;
+            }
+
+            let result = self.transport.execute(request).await;
+            let Some(next) = retry else {
+                return result;
+            };
+
+            let delay = match &result {
+                Ok(response) if policy.is_retryable_status(response.status()) => {
+                    policy.delay(attempt, Some(response.headers()))
+                }
+                Err(e) if RetryPolicy::is_retryable_error(e) => policy.delay(attempt, None),
+                _ => return result,
+            };
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                attempt,
+                ?delay,
+                method = %next.method(),
+                path = next.url().path(),
+                status = ?result.as_ref().map(Response::status).ok(),
+                "Retrying request"
+            );
+
+            tokio::time::sleep(delay).await;
+            request = next;
+            attempt += 1;
+        }
+    }
+}
+
 #[cfg(any(
     feature = "bridge",
     feature = "clob",
@@ -253,7 +436,7 @@ impl<T: Serialize> ToQueryParams for T {}
     )
 )]
 async fn request<Response: DeserializeOwned>(
-    client: &reqwest::Client,
+    client: &HttpClient,
     mut request: Request,
     headers: Option<HeaderMap>,
 ) -> Result<Response> {
@@ -0,0 +1,251 @@
+#![expect(
+    clippy::module_name_repetitions,
+    reason = "RetryPolicy intentionally mirrors the module name for clarity"
+)]
+
+//! Retry and backoff policy for REST requests.
+//!
+//! Every REST call made by the [`clob`](crate::clob), [`data`](crate::data),
+//! [`gamma`](crate::gamma) and [`bridge`](crate::bridge) clients goes through a [`RetryPolicy`].
+//! The default policy makes a single attempt, matching the behavior of a plain request. Raising
+//! [`RetryPolicy::max_attempts`] retries failed requests whose HTTP method is listed in
+//! [`RetryPolicy::idempotent_methods`], which by default excludes `POST`. Order placement
+//! (`post_order`/`post_orders`) is therefore never retried unless explicitly opted into.
+//! Authenticated CLOB requests are never retried either, since their signed headers cover a
+//! timestamp that would be stale by the time of a retry.
+
+use std::time::Duration;
+
+use bon::Builder;
+use chrono::{DateTime, Utc};
+use rand::RngExt as _;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 1;
+const DEFAULT_INITIAL_BACKOFF_DURATION: Duration = Duration::from_millis(200);
+const DEFAULT_MAX_BACKOFF_DURATION: Duration = Duration::from_secs(10);
+const DEFAULT_BACKOFF_MULTIPLIER: u32 = 2;
+
+/// Policy describing if and how a failed REST request is retried.
+///
+/// A request is retried when all of the following hold:
+/// - fewer than [`Self::max_attempts`] attempts have been made,
+/// - its method is one of [`Self::idempotent_methods`],
+/// - it does not carry CLOB authentication headers,
+/// - it failed with one of [`Self::retry_statuses`], or with a connection error or timeout
+///   before a response was received.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use polymarket_client_sdk::retry::RetryPolicy;
+///
+/// let policy = RetryPolicy::builder()
+///     .max_attempts(4)
+///     .initial_backoff(Duration::from_millis(100))
+///     .build();
+///
+/// assert!(policy.backoff(1) <= Duration::from_millis(200));
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Debug, Builder)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one. `1` disables retries.
+    #[builder(default = DEFAULT_MAX_ATTEMPTS)]
+    pub max_attempts: u32,
+    /// Backoff before the first retry
+    #[builder(default = DEFAULT_INITIAL_BACKOFF_DURATION)]
+    pub initial_backoff: Duration,
+    /// Upper bound for any single backoff, including one requested through `Retry-After`
+    #[builder(default = DEFAULT_MAX_BACKOFF_DURATION)]
+    pub max_backoff: Duration,
+    /// Multiplier applied to the backoff after each retry
+    #[builder(default = DEFAULT_BACKOFF_MULTIPLIER)]
+    pub backoff_multiplier: u32,
+    /// Whether to randomize each backoff between half and all of its computed value
+    #[builder(default = true)]
+    pub jitter: bool,
+    /// Whether to wait for the duration given by a `Retry-After` response header, when present,
+    /// instead of the computed backoff
+    #[builder(default = true)]
+    pub respect_retry_after: bool,
+    /// HTTP methods considered safe to retry. Defaults to `GET`, `HEAD`, `OPTIONS`, `PUT` and
+    /// `DELETE`.
+    #[builder(default = default_idempotent_methods())]
+    pub idempotent_methods: Vec<Method>,
+    /// Response statuses that trigger a retry. Defaults to `429`, `500`, `502`, `503` and `504`.
+    #[builder(default = default_retry_statuses())]
+    pub retry_statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    #[must_use]
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Whether requests using `method` may be retried under this policy.
+    #[must_use]
+    pub fn is_idempotent(&self, method: &Method) -> bool {
+        self.idempotent_methods.contains(method)
+    }
+
+    /// Whether a response with `status` may be retried under this policy.
+    #[must_use]
+    pub fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retry_statuses.contains(&status)
+    }
+
+    /// Whether a transport-level failure may be retried under this policy.
+    pub(crate) fn is_retryable_error(error: &crate::error::Error) -> bool {
+        error
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_connect() || e.is_timeout() || e.is_request())
+    }
+
+    /// Backoff to wait before retry number `retry` (starting at `1`), without jitter applied.
+    #[must_use]
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self
+            .backoff_multiplier
+            .checked_pow(retry.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+
+    /// Delay to wait before retry number `retry`, honoring `Retry-After` from `headers` and
+    /// applying jitter as configured.
+    pub(crate) fn delay(&self, retry: u32, headers: Option<&HeaderMap>) -> Duration {
+        if self.respect_retry_after
+            && let Some(retry_after) = headers.and_then(retry_after)
+        {
+            return retry_after.min(self.max_backoff);
+        }
+
+        let backoff = self.backoff(retry);
+        if !self.jitter {
+            return backoff;
+        }
+
+        let millis = u64::try_from(backoff.as_millis()).unwrap_or(u64::MAX);
+        let half = millis / 2;
+        Duration::from_millis(half + rand::rng().random_range(0..=millis - half))
+    }
+}
+
+/// Parses a `Retry-After` header given either as delay seconds or as an HTTP date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+}
+
+fn default_idempotent_methods() -> Vec<Method> {
+    vec![
+        Method::GET,
+        Method::HEAD,
+        Method::OPTIONS,
+        Method::PUT,
+        Method::DELETE,
+    ]
+}
+
+fn default_retry_statuses() -> Vec<StatusCode> {
+    vec![
+        StatusCode::TOO_MANY_REQUESTS,
+        StatusCode::INTERNAL_SERVER_ERROR,
+        StatusCode::BAD_GATEWAY,
+        StatusCode::SERVICE_UNAVAILABLE,
+        StatusCode::GATEWAY_TIMEOUT,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn default_policy_never_retries() {
+        let policy = RetryPolicy::default();
+
+        assert_eq!(policy.max_attempts, 1);
+        assert!(
+            !policy.is_idempotent(&Method::POST),
+            "POST must not be retried"
+        );
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_max() {
+        let policy = RetryPolicy::builder()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(1))
+            .build();
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(10), Duration::from_secs(1));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn delay_applies_jitter_within_bounds() {
+        let policy = RetryPolicy::builder()
+            .initial_backoff(Duration::from_millis(100))
+            .build();
+
+        for _ in 0..100 {
+            let delay = policy.delay(1, None);
+            assert!(
+                delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100),
+                "delay {delay:?} out of bounds"
+            );
+        }
+    }
+
+    #[test]
+    fn delay_honors_retry_after_seconds() {
+        let policy = RetryPolicy::default();
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+
+        assert_eq!(policy.delay(1, Some(&headers)), Duration::from_secs(3));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3600"));
+        assert_eq!(policy.delay(1, Some(&headers)), policy.max_backoff);
+    }
+
+    #[test]
+    fn delay_honors_retry_after_date() {
+        let policy = RetryPolicy::builder().jitter(false).build();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+
+        // A date in the past yields no wait from the header, so the computed backoff is used
+        assert_eq!(policy.delay(1, Some(&headers)), policy.initial_backoff);
+    }
+}
@@ -101,11 +101,7 @@ impl Client<Unauthenticated> {
 
         Ok(Client {
             inner: Arc::new(ClientInner {
-                state: Authenticated {
-                    address,
-                    credentials,
-                    kind: Normal,
-                },
+                state: Authenticated::new(address, credentials, Normal),
                 config: inner.config,
                 endpoint: inner.endpoint,
                 connection: inner.connection,
@@ -300,8 +296,8 @@ impl Client<Authenticated<Normal>> {
         &self,
         comment_type: Option<CommentType>,
     ) -> Result<impl Stream<Item = Result<Comment>>> {
-        let subscription = Subscription::comments(comment_type)
-            .with_clob_auth(self.inner.state.credentials.clone());
+        let subscription =
+            Subscription::comments(comment_type).with_clob_auth(self.inner.state.credentials());
         let stream = self.inner.subscriptions.subscribe(subscription)?;
 
         Ok(stream.filter_map(|msg_result| async move {
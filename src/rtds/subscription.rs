@@ -232,12 +232,15 @@ impl SubscriptionManager {
 
         // Create filtered stream with its own receiver
         let mut rx = self.connection.subscribe();
+        let received = self.connection.received();
         let target_topic = topic_type.topic;
         let target_type = topic_type.msg_type;
 
         Ok(try_stream! {
             loop {
-                match rx.recv().await {
+                let result = rx.recv().await;
+                received.notify_one();
+                match result {
                     Ok(msg) => {
                         // Filter messages by topic and type
                         let matches_topic = msg.topic == target_topic;
@@ -0,0 +1,84 @@
+//! Timeouts for REST requests.
+//!
+//! Every REST call made by the [`clob`](crate::clob), [`data`](crate::data),
+//! [`gamma`](crate::gamma) and [`bridge`](crate::bridge) clients is bounded by a request timeout,
+//! covering the connection, the request and reading the whole response body. Each attempt made by
+//! the [`RetryPolicy`](crate::retry::RetryPolicy) gets the full timeout. The default of
+//! [`DEFAULT_REQUEST_TIMEOUT`] can be changed per client, with
+//! [`clob::Config::timeout`](crate::clob::Config) or the `with_timeout` method of the other
+//! clients, and per call with [`within`].
+//!
+//! The default [`Transport`](crate::transport::Transport) also gives up on establishing a
+//! connection after [`DEFAULT_CONNECT_TIMEOUT`]. A custom transport is expected to honor the
+//! timeout set on each [`Request`](reqwest::Request) it is given, as [`reqwest::Client`] does.
+//!
+//! A request that times out fails with an error of kind [`Kind::Timeout`], distinct from the
+//! [`Kind::Status`] of a non-success response. Dropping the future of a call cancels its request.
+
+use std::future::Future;
+use std::time::Duration;
+
+use crate::Result;
+use crate::error::{Error, Kind};
+
+/// Request timeout used when none is configured.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Connect timeout of the default [`Transport`](crate::transport::Transport).
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+tokio::task_local! {
+    static REQUEST_TIMEOUT: Duration;
+}
+
+/// Runs `future`, typically a single client call, failing with [`Kind::Timeout`] if it does not
+/// complete within `timeout`.
+///
+/// Requests made inside `future` use `timeout` instead of their client's request timeout, so it
+/// can be longer as well as shorter than the client's. Retries and rate limiting count towards
+/// the deadline.
+///
+/// # Errors
+///
+/// Returns an error of kind [`Kind::Timeout`] if `future` does not complete in time, or the error
+/// `future` resolved to.
+///
+/// # Examples
+///
+/// ```rust, no_run
+/// use std::time::Duration;
+///
+/// use polymarket_client_sdk::clob::{Client, Config};
+/// use polymarket_client_sdk::clob::types::request::OrderBookSummaryRequest;
+/// use polymarket_client_sdk::error::Kind;
+/// use polymarket_client_sdk::timeout::within;
+/// use polymarket_client_sdk::types::U256;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("https://clob.polymarket.com", Config::default())?;
+/// let request = OrderBookSummaryRequest::builder()
+///     .token_id(U256::from(1))
+///     .build();
+///
+/// match within(Duration::from_millis(500), client.order_book(&request)).await {
+///     Ok(book) => println!("{} bids", book.bids.len()),
+///     Err(e) if e.kind() == Kind::Timeout => println!("book is too slow, skipping"),
+///     Err(e) => return Err(e.into()),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn within<T, F>(timeout: Duration, future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    REQUEST_TIMEOUT
+        .scope(timeout, tokio::time::timeout(timeout, future))
+        .await
+        .map_err(|e| Error::with_source(Kind::Timeout, e))?
+}
+
+/// The timeout set by an enclosing [`within`], if any.
+pub(crate) fn scoped() -> Option<Duration> {
+    REQUEST_TIMEOUT.try_with(|timeout| *timeout).ok()
+}
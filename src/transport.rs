@@ -0,0 +1,278 @@
+//! Pluggable HTTP transport for the REST clients.
+//!
+//! Every REST call made by the [`clob`](crate::clob), [`data`](crate::data),
+//! [`gamma`](crate::gamma) and [`bridge`](crate::bridge) clients is dispatched through a
+//! [`Transport`]. By default this is a [`reqwest::Client`] created by the SDK, but any type
+//! implementing the trait can be supplied instead:
+//!
+//! - a [`reqwest::Client`] configured with proxies, timeouts, connection pool sizes or custom TLS
+//!   roots,
+//! - a [`Stack`] running [`Middleware`] around another transport, e.g. to add headers, record
+//!   metrics or inject faults,
+//! - an entirely custom implementation.
+//!
+//! The SDK builds each [`Request`] fully, including authentication headers and the default
+//! `User-Agent`, `Accept`, `Connection` and `Content-Type` headers, before handing it to the
+//! transport. Retries and rate limiting happen outside of the transport, so every attempt goes
+//! through the whole [`Stack`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use polymarket_client_sdk::Result;
+//! use polymarket_client_sdk::transport::{Middleware, Next, Request, Response, Stack, async_trait};
+//!
+//! /// Counts the requests going through it.
+//! #[derive(Debug, Default)]
+//! struct Counter(std::sync::atomic::AtomicU64);
+//!
+//! #[async_trait]
+//! impl Middleware for Counter {
+//!     async fn handle(&self, request: Request, next: Next<'_>) -> Result<Response> {
+//!         self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+//!         next.run(request).await
+//!     }
+//! }
+//!
+//! # fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+//! let client = reqwest::Client::builder()
+//!     .timeout(Duration::from_secs(5))
+//!     .pool_max_idle_per_host(4)
+//!     .build()?;
+//! let transport = Stack::new(client).layer(Counter::default());
+//!
+//! # #[cfg(feature = "data")]
+//! let data = polymarket_client_sdk::data::Client::default().with_transport(transport);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Attribute macro used to implement [`Transport`] and [`Middleware`], re-exported from
+/// [`async_trait`](https://docs.rs/async-trait).
+pub use async_trait::async_trait;
+use reqwest::header::{ACCEPT, CONNECTION, CONTENT_TYPE, HeaderMap, HeaderValue, USER_AGENT};
+/// Request and response types handled by a [`Transport`], re-exported from
+/// [`reqwest`](https://docs.rs/reqwest).
+pub use reqwest::{Request, Response};
+
+use crate::Result;
+
+/// Sends HTTP requests on behalf of the REST clients.
+///
+/// Implementations return the response as received, whatever its status: the SDK interprets
+/// non-success statuses itself. Errors wrapping a [`reqwest::Error`] caused by a failed
+/// connection or a timeout are retried according to the client's
+/// [`RetryPolicy`](crate::retry::RetryPolicy).
+#[async_trait]
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Sends `request` and returns its response.
+    async fn execute(&self, request: Request) -> Result<Response>;
+}
+
+#[async_trait]
+impl Transport for reqwest::Client {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        Ok(reqwest::Client::execute(self, request).await?)
+    }
+}
+
+#[async_trait]
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        (**self).execute(request).await
+    }
+}
+
+/// Code run around every request sent through a [`Stack`].
+///
+/// A middleware may inspect or modify the request, call [`Next::run`] to pass it on (possibly
+/// several times, or not at all), and inspect or replace the result.
+#[async_trait]
+pub trait Middleware: fmt::Debug + Send + Sync {
+    /// Handles `request`, usually by passing it on to `next`.
+    async fn handle(&self, request: Request, next: Next<'_>) -> Result<Response>;
+}
+
+#[async_trait]
+impl<M: Middleware + ?Sized> Middleware for Arc<M> {
+    async fn handle(&self, request: Request, next: Next<'_>) -> Result<Response> {
+        (**self).handle(request, next).await
+    }
+}
+
+/// The rest of a [`Stack`]: the middleware after the current one, then the transport.
+#[derive(Clone, Copy, Debug)]
+pub struct Next<'stack> {
+    middleware: &'stack [Arc<dyn Middleware>],
+    transport: &'stack dyn Transport,
+}
+
+impl Next<'_> {
+    /// Passes `request` on to the next middleware, or to the transport if there is none left.
+    pub async fn run(self, request: Request) -> Result<Response> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => {
+                let next = Next {
+                    middleware: rest,
+                    transport: self.transport,
+                };
+                middleware.handle(request, next).await
+            }
+            None => self.transport.execute(request).await,
+        }
+    }
+}
+
+/// A [`Transport`] running a list of [`Middleware`] around another transport.
+///
+/// Middleware run in the order they were added: the first one sees the request first and the
+/// response last.
+#[derive(Clone, Debug)]
+pub struct Stack {
+    middleware: Vec<Arc<dyn Middleware>>,
+    transport: Arc<dyn Transport>,
+}
+
+impl Stack {
+    /// Creates a stack sending requests through `transport`, with no middleware.
+    pub fn new<T: Transport + 'static>(transport: T) -> Self {
+        Self {
+            middleware: Vec::new(),
+            transport: Arc::new(transport),
+        }
+    }
+
+    /// Adds `middleware` inside the ones added so far, closest to the transport.
+    #[must_use]
+    pub fn layer<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+}
+
+#[async_trait]
+impl Transport for Stack {
+    async fn execute(&self, request: Request) -> Result<Response> {
+        let next = Next {
+            middleware: &self.middleware,
+            transport: self.transport.as_ref(),
+        };
+
+        next.run(request).await
+    }
+}
+
+/// Sets the headers every request of the SDK carries, unless `headers` already has them.
+pub(crate) fn insert_default_headers(headers: &mut HeaderMap) {
+    let defaults = [
+        (USER_AGENT, "rs_clob_client"),
+        (ACCEPT, "*/*"),
+        (CONNECTION, "keep-alive"),
+        (CONTENT_TYPE, "application/json"),
+    ];
+
+    for (name, value) in defaults {
+        headers
+            .entry(name)
+            .or_insert_with(|| HeaderValue::from_static(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use reqwest::Method;
+
+    use super::*;
+    use crate::error::{Error, Kind};
+
+    /// Records the requests it sees and fails them all.
+    #[derive(Debug, Default)]
+    struct Recording(Mutex<Vec<String>>);
+
+    #[async_trait]
+    impl Transport for Recording {
+        async fn execute(&self, request: Request) -> Result<Response> {
+            let mut seen = self.0.lock().unwrap();
+            for (name, value) in request.headers() {
+                seen.push(format!("{name}={}", value.to_str().unwrap()));
+            }
+
+            Err(Error::validation("no network in tests"))
+        }
+    }
+
+    #[derive(Debug)]
+    struct Tag(&'static str);
+
+    #[async_trait]
+    impl Middleware for Tag {
+        async fn handle(&self, mut request: Request, next: Next<'_>) -> Result<Response> {
+            let tags = request.headers().get("x-tags").map_or_else(
+                || self.0.to_owned(),
+                |tags| format!("{},{}", tags.to_str().unwrap(), self.0),
+            );
+            request
+                .headers_mut()
+                .insert("x-tags", tags.parse().unwrap());
+
+            next.run(request).await
+        }
+    }
+
+    #[derive(Debug)]
+    struct ShortCircuit;
+
+    #[async_trait]
+    impl Middleware for ShortCircuit {
+        async fn handle(&self, _request: Request, _next: Next<'_>) -> Result<Response> {
+            Err(Error::validation("short-circuited"))
+        }
+    }
+
+    fn request() -> Request {
+        Request::new(Method::GET, "http://localhost/time".parse().unwrap())
+    }
+
+    #[tokio::test]
+    async fn stack_should_run_middleware_in_order() {
+        let transport = Arc::new(Recording::default());
+        let stack = Stack::new(Arc::clone(&transport))
+            .layer(Tag("outer"))
+            .layer(Tag("inner"));
+
+        let err = stack.execute(request()).await.unwrap_err();
+
+        assert_eq!(err.kind(), Kind::Validation);
+        assert_eq!(*transport.0.lock().unwrap(), ["x-tags=outer,inner"]);
+    }
+
+    #[tokio::test]
+    async fn middleware_should_be_able_to_skip_the_transport() {
+        let transport = Arc::new(Recording::default());
+        let stack = Stack::new(Arc::clone(&transport)).layer(ShortCircuit);
+
+        let err = stack.execute(request()).await.unwrap_err();
+
+        assert!(err.to_string().contains("short-circuited"));
+        assert!(transport.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn default_headers_should_not_override_existing_ones() {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static("custom"));
+
+        insert_default_headers(&mut headers);
+
+        assert_eq!(headers[USER_AGENT], "custom");
+        assert_eq!(headers[CONTENT_TYPE], "application/json");
+        assert_eq!(headers.len(), 4);
+    }
+}
@@ -7,6 +7,8 @@ use std::time::Duration;
 
 use backoff::{ExponentialBackoff, ExponentialBackoffBuilder};
 
+use super::recording::{Recorder, Replay};
+
 const DEFAULT_HEARTBEAT_INTERVAL_DURATION: Duration = Duration::from_secs(5);
 const DEFAULT_HEARTBEAT_TIMEOUT_DURATION: Duration = Duration::from_secs(15);
 const DEFAULT_INITIAL_BACKOFF_DURATION: Duration = Duration::from_secs(1);
@@ -23,6 +25,10 @@ pub struct Config {
     pub heartbeat_timeout: Duration,
     /// Reconnection strategy configuration
     pub reconnect: ReconnectConfig,
+    /// Records every text frame received by connections using this configuration
+    pub recorder: Option<Recorder>,
+    /// Replays a recording instead of connecting to the endpoint
+    pub replay: Option<Replay>,
 }
 
 impl Default for Config {
@@ -31,6 +37,8 @@ impl Default for Config {
             heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL_DURATION,
             heartbeat_timeout: DEFAULT_HEARTBEAT_TIMEOUT_DURATION,
             reconnect: ReconnectConfig::default(),
+            recorder: None,
+            replay: None,
         }
     }
 }
@@ -5,6 +5,7 @@
 
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::sync::Arc;
 use std::time::Instant;
 
 use backoff::backoff::Backoff as _;
@@ -12,12 +13,13 @@ use futures::{SinkExt as _, StreamExt as _};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use tokio::net::TcpStream;
-use tokio::sync::{broadcast, mpsc, watch};
-use tokio::time::{interval, sleep, timeout};
+use tokio::sync::{Notify, broadcast, mpsc, watch};
+use tokio::time::{interval, sleep, sleep_until, timeout};
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message};
 
 use super::config::Config;
 use super::error::WsError;
+use super::recording::{Recorder, Replay};
 use super::traits::MessageParser;
 use crate::auth::Credentials;
 use crate::error::Kind;
@@ -29,6 +31,22 @@ type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
 /// Broadcast channel capacity for incoming messages.
 const BROADCAST_CAPACITY: usize = 1024;
 
+/// How long a replay waits for a lagging subscriber before checking again on its own.
+///
+/// Subscription streams signal every message they take, so this only bounds the wait for
+/// receivers that don't, like the ones handed out by [`ConnectionManager::subscribe`].
+const REPLAY_BACKPRESSURE_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Notifications between a [`ConnectionManager`] and its replay task.
+#[derive(Default)]
+struct Signals {
+    /// Notified on every new subscriber, so a replay can wait for the first one
+    subscribed: Notify,
+    /// Notified whenever a subscription stream takes a message, so a replay waiting for a
+    /// lagging subscriber resumes as soon as there is room
+    received: Arc<Notify>,
+}
+
 /// Connection state tracking.
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -100,6 +118,8 @@ where
     sender_tx: mpsc::UnboundedSender<String>,
     /// Broadcast sender for incoming messages
     broadcast_tx: broadcast::Sender<M>,
+    /// Wakes a replay once there is someone, or room, to deliver to
+    signals: Arc<Signals>,
     /// Phantom data for unused type parameters
     _phantom: PhantomData<P>,
 }
@@ -114,27 +134,53 @@ where
     /// The `parser` is used to deserialize incoming WebSocket messages.
     /// The connection loop runs in a background task and automatically
     /// handles reconnection according to the config's `ReconnectConfig`.
+    ///
+    /// If the config has a [`Replay`], the recording is played back instead and no connection
+    /// is made.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`Replay`] speed is invalid.
     pub fn new(endpoint: String, config: Config, parser: P) -> Result<Self> {
+        if let Some(replay) = &config.replay {
+            replay.speed.validate()?;
+        }
+
         let (sender_tx, sender_rx) = mpsc::unbounded_channel();
         let (broadcast_tx, _) = broadcast::channel(BROADCAST_CAPACITY);
         let (state_tx, state_rx) = watch::channel(ConnectionState::Disconnected);
+        let signals = Arc::new(Signals::default());
 
         // Spawn connection task
         let connection_config = config;
         let connection_endpoint = endpoint;
         let broadcast_tx_clone = broadcast_tx.clone();
         let state_tx_clone = state_tx.clone();
+        let signals_clone = Arc::clone(&signals);
 
         tokio::spawn(async move {
-            Self::connection_loop(
-                connection_endpoint,
-                connection_config,
-                sender_rx,
-                broadcast_tx_clone,
-                parser,
-                state_tx_clone,
-            )
-            .await;
+            if let Some(replay) = connection_config.replay {
+                Self::replay_loop(
+                    connection_endpoint,
+                    replay,
+                    sender_rx,
+                    broadcast_tx_clone,
+                    &signals_clone,
+                    parser,
+                    state_tx_clone,
+                )
+                .await;
+            } else {
+                Self::connection_loop(
+                    connection_endpoint,
+                    connection_config,
+                    sender_rx,
+                    broadcast_tx_clone,
+                    parser,
+                    state_tx_clone,
+                )
+                .await;
+            }
         });
 
         Ok(Self {
@@ -142,6 +188,7 @@ where
             state_rx,
             sender_tx,
             broadcast_tx,
+            signals,
             _phantom: PhantomData,
         })
     }
@@ -182,6 +229,7 @@ where
 
                     // Handle connection
                     if let Err(e) = Self::handle_connection(
+                        &endpoint,
                         ws_stream,
                         &mut sender_rx,
                         &broadcast_tx,
@@ -224,8 +272,114 @@ where
         }
     }
 
+    /// Replay loop that plays back a recording instead of connecting.
+    async fn replay_loop(
+        endpoint: String,
+        replay: Replay,
+        mut sender_rx: mpsc::UnboundedReceiver<String>,
+        broadcast_tx: broadcast::Sender<M>,
+        signals: &Signals,
+        parser: P,
+        state_tx: watch::Sender<ConnectionState>,
+    ) {
+        _ = state_tx.send(ConnectionState::Connecting);
+
+        // Frames delivered before anyone subscribed would be lost
+        while broadcast_tx.receiver_count() == 0 {
+            tokio::select! {
+                () = signals.subscribed.notified() => {}
+                // Subscription requests are not sent anywhere, until the ConnectionManager is dropped
+                request = sender_rx.recv() => {
+                    if request.is_none() {
+                        _ = state_tx.send(ConnectionState::Disconnected);
+                        return;
+                    }
+                }
+            }
+        }
+
+        if let Err(e) = Self::replay_frames(
+            &endpoint,
+            &replay,
+            &sender_rx,
+            &broadcast_tx,
+            &signals.received,
+            &parser,
+            &state_tx,
+        )
+        .await
+        {
+            #[cfg(feature = "tracing")]
+            tracing::error!("Error replaying recording: {e:?}");
+            #[cfg(not(feature = "tracing"))]
+            let _: &_ = &e;
+        }
+
+        _ = state_tx.send(ConnectionState::Disconnected);
+    }
+
+    /// Deliver the frames recorded for `endpoint` at the pace set by `replay`.
+    async fn replay_frames(
+        endpoint: &str,
+        replay: &Replay,
+        sender_rx: &mpsc::UnboundedReceiver<String>,
+        broadcast_tx: &broadcast::Sender<M>,
+        received: &Notify,
+        parser: &P,
+        state_tx: &watch::Sender<ConnectionState>,
+    ) -> Result<()> {
+        let mut frames = replay.frames(endpoint).await?;
+
+        let start = tokio::time::Instant::now();
+        _ = state_tx.send(ConnectionState::Connected {
+            since: Instant::now(),
+        });
+
+        while let Some((delay, text)) = frames.next().await? {
+            // Stop once the ConnectionManager was dropped
+            if sender_rx.is_closed() {
+                break;
+            }
+
+            match delay {
+                Some(delay) => sleep_until(start + delay).await,
+                None => tokio::task::yield_now().await,
+            }
+
+            // Wait for lagging subscribers instead of letting them miss frames
+            while broadcast_tx.len() >= BROADCAST_CAPACITY / 2 && broadcast_tx.receiver_count() > 0
+            {
+                _ = timeout(REPLAY_BACKPRESSURE_INTERVAL, received.notified()).await;
+            }
+
+            Self::dispatch(&text, parser, broadcast_tx);
+        }
+
+        Ok(())
+    }
+
+    /// Parse a text frame and broadcast the resulting messages.
+    fn dispatch(text: &str, parser: &P, broadcast_tx: &broadcast::Sender<M>) {
+        match parser.parse(text.as_bytes()) {
+            Ok(messages) => {
+                for message in messages {
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(?message, "Parsed WebSocket message");
+                    _ = broadcast_tx.send(message);
+                }
+            }
+            Err(e) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(%text, error = %e, "Failed to parse WebSocket message");
+                #[cfg(not(feature = "tracing"))]
+                let _: (&_, &_) = (&text, &e);
+            }
+        }
+    }
+
     /// Handle an active WebSocket connection.
     async fn handle_connection(
+        endpoint: &str,
         ws_stream: WsStream,
         sender_rx: &mut mpsc::UnboundedReceiver<String>,
         broadcast_tx: &broadcast::Sender<M>,
@@ -234,6 +388,7 @@ where
         parser: &P,
     ) -> Result<()> {
         let (mut write, mut read) = ws_stream.split();
+        let recorder: Option<Recorder> = config.recorder.clone();
 
         // Channel to notify heartbeat loop when PONG is received
         let (pong_tx, pong_rx) = watch::channel(Instant::now());
@@ -255,22 +410,12 @@ where
                             #[cfg(feature = "tracing")]
                             tracing::trace!(%text, "Received WebSocket text message");
 
-                            // Parse messages using the provided parser
-                            match parser.parse(text.as_bytes()) {
-                                Ok(messages) => {
-                                    for message in messages {
-                                        #[cfg(feature = "tracing")]
-                                        tracing::trace!(?message, "Parsed WebSocket message");
-                                        _ = broadcast_tx.send(message);
-                                    }
-                                }
-                                Err(e) => {
-                                    #[cfg(feature = "tracing")]
-                                    tracing::warn!(%text, error = %e, "Failed to parse WebSocket message");
-                                    #[cfg(not(feature = "tracing"))]
-                                    let _: (&_, &_) = (&text, &e);
-                                }
+                            if let Some(recorder) = &recorder {
+                                recorder.record(endpoint, &text);
                             }
+
+                            // Parse messages using the provided parser
+                            Self::dispatch(&text, parser, broadcast_tx);
                         }
                         Ok(Message::Close(_)) => {
                             heartbeat_handle.abort();
@@ -412,7 +557,16 @@ where
     /// receive messages concurrently without blocking each other.
     #[must_use]
     pub fn subscribe(&self) -> broadcast::Receiver<M> {
-        self.broadcast_tx.subscribe()
+        let rx = self.broadcast_tx.subscribe();
+        self.signals.subscribed.notify_one();
+        rx
+    }
+
+    /// Notifier to signal after taking a message from a [`subscribe`](Self::subscribe) receiver,
+    /// so a replay waiting for lagging subscribers resumes as soon as there is room.
+    #[cfg(any(all(feature = "clob", feature = "ws"), feature = "rtds"))]
+    pub(crate) fn received(&self) -> Arc<Notify> {
+        Arc::clone(&self.signals.received)
     }
 
     /// Subscribe to connection state changes.
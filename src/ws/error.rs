@@ -29,6 +29,8 @@ pub enum WsError {
         /// Number of messages that were missed
         count: u64,
     },
+    /// Error reading or writing a session recording
+    Recording(std::io::Error),
 }
 
 impl fmt::Display for WsError {
@@ -42,6 +44,7 @@ impl fmt::Display for WsError {
             Self::Timeout => write!(f, "WebSocket operation timed out"),
             Self::InvalidMessage(msg) => write!(f, "Invalid WebSocket message: {msg}"),
             Self::Lagged { count } => write!(f, "Subscription lagged, missed {count} messages"),
+            Self::Recording(e) => write!(f, "WebSocket recording error: {e}"),
         }
     }
 }
@@ -51,6 +54,7 @@ impl StdError for WsError {
         match self {
             Self::Connection(e) => Some(e),
             Self::MessageParse(e) => Some(e),
+            Self::Recording(e) => Some(e),
             _ => None,
         }
     }
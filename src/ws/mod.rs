@@ -7,6 +7,7 @@
 //!
 //! - [`ConnectionManager`]: Generic WebSocket connection handler with heartbeat and reconnection
 //! - [`MessageParser`]: Trait for parsing incoming WebSocket messages
+//! - [`recording`]: Recording of live sessions and their replay in place of a connection
 //!
 //! # Example
 //!
@@ -22,6 +23,7 @@
 pub mod config;
 pub mod connection;
 pub mod error;
+pub mod recording;
 pub mod traits;
 
 pub use connection::ConnectionManager;
@@ -0,0 +1,337 @@
+//! Recording and replay of WebSocket sessions.
+//!
+//! A [`Recorder`] set on the [`Config`](super::config::Config) writes every text frame received by
+//! a connection to a file, one [`RecordedFrame`] per line (newline-delimited JSON). Binary frames
+//! are not recorded, as the connections don't deliver them to subscribers either. Setting a
+//! [`Replay`] instead makes connections read their frames back from such a file rather than from
+//! the network. Frames go through the same parsing, filtering and [`Stream`](futures::Stream)s as
+//! live traffic, so a recorded session of the CLOB market or user channel, or of RTDS, can be
+//! replayed through [`clob::ws::Client`](crate::clob::ws::Client) or
+//! [`rtds::Client`](crate::rtds::Client) without a network connection.
+//!
+//! A single file may hold frames from several connections. Each connection only replays the frames
+//! recorded from an endpoint with the same path, e.g. `/ws/market`, regardless of the host.
+//!
+//! # Examples
+//!
+//! ```rust, no_run
+//! use polymarket_client_sdk::clob::ws::Client;
+//! use polymarket_client_sdk::ws::config::Config;
+//! use polymarket_client_sdk::ws::recording::{Recorder, Replay, ReplaySpeed};
+//!
+//! # fn main() -> polymarket_client_sdk::Result<()> {
+//! // Record a live session
+//! let mut config = Config::default();
+//! config.recorder = Some(Recorder::create("session.ndjson")?);
+//! let live = Client::new("wss://ws-subscriptions-clob.polymarket.com", config)?;
+//!
+//! // Replay it later, ten times faster than it was recorded
+//! let mut config = Config::default();
+//! config.replay = Some(Replay::new("session.ndjson").with_speed(ReplaySpeed::Accelerated(10.0)));
+//! let replayed = Client::new("wss://ws-subscriptions-clob.polymarket.com", config)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::File;
+use std::io::{LineWriter, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt as _, BufReader, Lines};
+use url::Url;
+
+use super::error::WsError;
+use crate::{Result, error::Error};
+
+/// A text frame received on a WebSocket connection, as written by a [`Recorder`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// When the frame was received
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub received_at: DateTime<Utc>,
+    /// Endpoint of the connection that received the frame
+    pub endpoint: String,
+    /// Raw text of the frame
+    pub frame: String,
+}
+
+/// Writes every text frame received by the connections it is set on to a newline-delimited JSON
+/// file.
+///
+/// Clones share the same file, so one recorder can capture several connections at once. Frames
+/// are written by a dedicated thread, which exits once every clone is dropped, so recording never
+/// blocks the connections.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    lines: mpsc::Sender<String>,
+}
+
+impl Recorder {
+    /// Creates a recorder writing to `path`, truncating the file if it already exists.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let (recorder, _writer) = Self::spawn(path)?;
+        Ok(recorder)
+    }
+
+    /// Creates a recorder along with the thread writing its lines to `path`.
+    fn spawn<P: AsRef<Path>>(path: P) -> Result<(Self, JoinHandle<()>)> {
+        let file = File::create(path).map_err(WsError::Recording)?;
+        let (lines, rx) = mpsc::channel::<String>();
+
+        let writer = thread::Builder::new()
+            .name("polymarket-ws-recorder".to_owned())
+            .spawn(move || {
+                let mut writer = LineWriter::new(file);
+                for line in rx {
+                    if let Err(e) = writeln!(writer, "{line}") {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(error = %e, "Failed to record WebSocket frame");
+                        #[cfg(not(feature = "tracing"))]
+                        let _: &_ = &e;
+                    }
+                }
+            })
+            .map_err(WsError::Recording)?;
+
+        Ok((Self { lines }, writer))
+    }
+
+    /// Appends `frame`, received now on `endpoint`, to the recording.
+    pub(crate) fn record(&self, endpoint: &str, frame: &str) {
+        let frame = RecordedFrame {
+            received_at: Utc::now(),
+            endpoint: endpoint.to_owned(),
+            frame: frame.to_owned(),
+        };
+
+        let result = serde_json::to_string(&frame)
+            .map_err(WsError::MessageParse)
+            .and_then(|line| {
+                self.lines.send(line).map_err(|_e| {
+                    WsError::Recording(std::io::Error::other("recording thread has stopped"))
+                })
+            });
+
+        if let Err(e) = result {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(error = %e, "Failed to record WebSocket frame");
+            #[cfg(not(feature = "tracing"))]
+            let _: &_ = &e;
+        }
+    }
+}
+
+/// Pace at which a [`Replay`] delivers frames.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ReplaySpeed {
+    /// Frames are delivered with the same spacing as they were received
+    #[default]
+    RealTime,
+    /// Frames are delivered the given number of times faster than they were received. The factor
+    /// must be finite and positive.
+    Accelerated(f64),
+    /// Frames are delivered as fast as subscribers consume them
+    Unthrottled,
+}
+
+impl ReplaySpeed {
+    /// Rejects [`ReplaySpeed::Accelerated`] factors that are not finite and positive.
+    pub(crate) fn validate(self) -> Result<()> {
+        match self {
+            Self::Accelerated(factor) if !(factor.is_finite() && factor > 0.0) => {
+                Err(Error::validation(format!(
+                    "Replay acceleration factor must be finite and positive, got {factor}"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Time to wait from the start of the replay before delivering a frame received `offset` after
+    /// the first one, or `None` if it should be delivered right away.
+    ///
+    /// The speed must have been [validated](Self::validate) first.
+    fn delay(self, offset: Duration) -> Option<Duration> {
+        match self {
+            Self::RealTime => Some(offset),
+            Self::Accelerated(factor) => Some(offset.div_f64(factor)),
+            Self::Unthrottled => None,
+        }
+    }
+}
+
+/// Replays a file written by a [`Recorder`] in place of a live connection.
+///
+/// Playback starts once the first stream is subscribed, and the connection reports
+/// [`ConnectionState::Disconnected`](super::connection::ConnectionState::Disconnected) after the
+/// last frame. Streams subscribed after playback started only see the frames from that point on,
+/// so create every stream before awaiting any of them. Subscription requests are not sent anywhere.
+///
+/// Frames are never dropped: if a subscriber falls behind, playback waits for it to catch up.
+/// Creating a client with an [`Accelerated`](ReplaySpeed::Accelerated) factor that is not finite
+/// and positive fails.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    /// File to replay
+    pub path: PathBuf,
+    /// Pace at which frames are delivered. Defaults to [`ReplaySpeed::RealTime`].
+    pub speed: ReplaySpeed,
+}
+
+impl Replay {
+    /// Replays `path` in real time.
+    #[must_use]
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            speed: ReplaySpeed::default(),
+        }
+    }
+
+    /// Sets the pace at which frames are delivered.
+    #[must_use]
+    pub fn with_speed(mut self, speed: ReplaySpeed) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Opens the frames recorded from `endpoint`.
+    pub(crate) async fn frames(&self, endpoint: &str) -> Result<Frames> {
+        let file = tokio::fs::File::open(&self.path)
+            .await
+            .map_err(WsError::Recording)?;
+
+        Ok(Frames {
+            lines: BufReader::new(file).lines(),
+            path: endpoint_path(endpoint),
+            speed: self.speed,
+            first: None,
+        })
+    }
+}
+
+/// Frames of a [`Replay`] recorded from a single endpoint, read as they are needed.
+pub(crate) struct Frames {
+    lines: Lines<BufReader<tokio::fs::File>>,
+    path: String,
+    speed: ReplaySpeed,
+    first: Option<DateTime<Utc>>,
+}
+
+impl Frames {
+    /// The next frame, paired with the delay after which it should be delivered, relative to the
+    /// start of the replay, or `None` at the end of the recording.
+    pub(crate) async fn next(&mut self) -> Result<Option<(Option<Duration>, String)>> {
+        while let Some(line) = self.lines.next_line().await.map_err(WsError::Recording)? {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let frame: RecordedFrame =
+                serde_json::from_str(&line).map_err(WsError::MessageParse)?;
+            if endpoint_path(&frame.endpoint) != self.path {
+                continue;
+            }
+
+            let first = *self.first.get_or_insert(frame.received_at);
+            let offset = (frame.received_at - first).to_std().unwrap_or_default();
+
+            return Ok(Some((self.speed.delay(offset), frame.frame)));
+        }
+
+        Ok(None)
+    }
+}
+
+/// The path of `endpoint`, used to match recorded frames to connections independently of the host.
+fn endpoint_path(endpoint: &str) -> String {
+    Url::parse(endpoint).map_or_else(
+        |_| endpoint.to_owned(),
+        |url| url.path().trim_end_matches('/').to_owned(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path() -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "polymarket-recording-{}.ndjson",
+            uuid::Uuid::new_v4()
+        ))
+    }
+
+    #[tokio::test]
+    async fn replay_should_read_frames_recorded_on_the_same_path() {
+        let path = temp_path();
+        let (recorder, writer) = Recorder::spawn(&path).unwrap();
+        recorder.record("wss://example.com/ws/market", r#"{"event_type":"book"}"#);
+        recorder.record("wss://example.com/ws/user", r#"{"event_type":"order"}"#);
+        recorder.record("wss://example.com/ws/market/", "PONG");
+        drop(recorder);
+        writer.join().unwrap();
+
+        let mut frames = Replay::new(&path)
+            .with_speed(ReplaySpeed::Unthrottled)
+            .frames("ws://127.0.0.1:1234/ws/market")
+            .await
+            .unwrap();
+        let mut read = Vec::new();
+        while let Some(frame) = frames.next().await.unwrap() {
+            read.push(frame);
+        }
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            read,
+            vec![
+                (None, r#"{"event_type":"book"}"#.to_owned()),
+                (None, "PONG".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn replay_speed_should_scale_delays() {
+        let offset = Duration::from_secs(10);
+
+        assert_eq!(ReplaySpeed::RealTime.delay(offset), Some(offset));
+        assert_eq!(
+            ReplaySpeed::Accelerated(4.0).delay(offset),
+            Some(Duration::from_millis(2_500))
+        );
+        assert_eq!(ReplaySpeed::Unthrottled.delay(offset), None);
+    }
+
+    #[test]
+    fn replay_speed_should_reject_invalid_factors() {
+        for factor in [0.0, -2.0, f64::NAN, f64::INFINITY] {
+            ReplaySpeed::Accelerated(factor).validate().unwrap_err();
+        }
+        ReplaySpeed::Accelerated(0.5).validate().unwrap();
+        ReplaySpeed::RealTime.validate().unwrap();
+    }
+
+    #[test]
+    fn recorded_frame_should_serialize_to_a_single_line() {
+        let frame = RecordedFrame {
+            received_at: DateTime::from_timestamp_millis(1_700_000_000_123).unwrap(),
+            endpoint: "wss://example.com/ws/market".to_owned(),
+            frame: "{\n\"event_type\": \"book\"\n}".to_owned(),
+        };
+
+        let line = serde_json::to_string(&frame).unwrap();
+        assert!(!line.contains('\n'), "frames must fit on one line");
+        assert!(line.contains(r#""received_at":1700000000123"#));
+        assert_eq!(serde_json::from_str::<RecordedFrame>(&line).unwrap(), frame);
+    }
+}
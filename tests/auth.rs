@@ -263,3 +263,796 @@ async fn authenticated_client_should_expose_credentials() -> anyhow::Result<()>
 
     Ok(())
 }
+
+mod rotation {
+    use polymarket_client_sdk::auth::Uuid;
+
+    use super::*;
+    use crate::common::{POLY_API_KEY, POLY_NONCE};
+
+    const NEW_API_KEY: Uuid = Uuid::max();
+
+    fn mock_create_key(server: &MockServer) -> httpmock::Mock<'_> {
+        server.mock(|when, then| {
+            when.method(httpmock::Method::POST)
+                .path("/auth/api-key")
+                .header(POLY_NONCE, "7");
+            then.status(StatusCode::OK).json_body(json!({
+                "apiKey": NEW_API_KEY.to_string(),
+                "passphrase": PASSPHRASE,
+                "secret": SECRET
+            }));
+        })
+    }
+
+    #[tokio::test]
+    async fn rotate_credentials_should_update_live_clones() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let clone = client.clone();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        let create = mock_create_key(&server);
+        let delete = server.mock(|when, then| {
+            when.method(httpmock::Method::DELETE)
+                .path("/auth/api-key")
+                .header(POLY_API_KEY, API_KEY.to_string());
+            then.status(StatusCode::OK).json_body(json!({}));
+        });
+        let api_keys = server.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/auth/api-keys")
+                .header(POLY_API_KEY, NEW_API_KEY.to_string());
+            then.status(StatusCode::OK)
+                .json_body(json!({ "apiKeys": [NEW_API_KEY.to_string()] }));
+        });
+
+        let credentials = client.rotate_credentials(&signer, 7, true).await?;
+
+        assert_eq!(credentials.key(), NEW_API_KEY);
+        assert_eq!(clone.credentials().key(), NEW_API_KEY);
+        clone.api_keys().await?;
+
+        create.assert();
+        delete.assert();
+        api_keys.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rotate_credentials_should_keep_old_key_on_failure() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        server.mock(|when, then| {
+            when.method(httpmock::Method::POST).path("/auth/api-key");
+            then.status(StatusCode::BAD_REQUEST);
+        });
+
+        client
+            .rotate_credentials(&signer, 7, true)
+            .await
+            .unwrap_err();
+
+        assert_eq!(client.credentials().key(), API_KEY);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rotate_credentials_with_other_signer_should_fail() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let other = LocalSigner::random().with_chain_id(Some(POLYGON));
+        let create = mock_create_key(&server);
+
+        let err = client
+            .rotate_credentials(&other, 7, false)
+            .await
+            .unwrap_err();
+
+        assert!(err.downcast_ref::<Validation>().is_some());
+        create.assert_calls(0);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn order_manager_should_use_rotated_key() -> anyhow::Result<()> {
+        use polymarket_client_sdk::clob::OrderManager;
+        use polymarket_client_sdk::clob::ws::TradeMessage;
+
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let manager = OrderManager::new(client.clone());
+        mock_create_key(&server);
+
+        client.rotate_credentials(&signer, 7, false).await?;
+
+        let trade: TradeMessage = serde_json::from_value(json!({
+            "event_type": "trade",
+            "id": "trade-1",
+            "market": "0x5f65177b394277fd294cd75650044e32ba009a95022d88a0c1d565897d72f8f1",
+            "asset_id": "1",
+            "side": "SELL",
+            "size": "4",
+            "price": "0.5",
+            "status": "MATCHED",
+            "taker_order_id": "0xtaker",
+            "trader_side": "MAKER",
+            "maker_orders": [
+                {
+                    "asset_id": "1",
+                    "matched_amount": "4",
+                    "order_id": "0xmaker",
+                    "outcome": "Yes",
+                    "owner": NEW_API_KEY,
+                    "price": "0.5",
+                }
+            ],
+        }))?;
+        manager.apply_trade_message(&trade);
+
+        let order = manager.order("0xmaker").unwrap();
+        assert_eq!(order.fills.len(), 1);
+
+        Ok(())
+    }
+}
+
+mod persistence {
+    use std::sync::Arc;
+
+    use polymarket_client_sdk::auth::persistence::{CredentialStore as _, MemoryStore};
+
+    use super::*;
+    use crate::common::POLY_API_KEY;
+
+    #[tokio::test]
+    async fn authenticate_should_derive_once_then_load_from_store() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let store = Arc::new(MemoryStore::new());
+
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::POST).path("/auth/api-key");
+            then.status(StatusCode::OK).json_body(json!({
+                "apiKey": API_KEY.to_string(),
+                "passphrase": PASSPHRASE,
+                "secret": SECRET
+            }));
+        });
+        let check = server.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/auth/api-keys")
+                .header(POLY_API_KEY, API_KEY.to_string());
+            then.status(StatusCode::OK)
+                .json_body(json!({ "apiKeys": [API_KEY.to_string()] }));
+        });
+
+        for _ in 0..2 {
+            let client = Client::new(&server.base_url(), Config::default())?
+                .authentication_builder(&signer)
+                .credential_store(Arc::clone(&store))
+                .authenticate()
+                .await?;
+
+            assert_eq!(client.credentials().key(), API_KEY);
+        }
+
+        mock.assert_calls(1);
+        check.assert_calls(1);
+        let stored = store.load(signer.address()).await?.unwrap();
+        assert_eq!(stored.key(), API_KEY);
+        assert_eq!(stored.secret().expose_secret(), SECRET);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejected_stored_credentials_should_be_derived_again() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let store = Arc::new(MemoryStore::new());
+        store
+            .save(signer.address(), &Credentials::default())
+            .await?;
+
+        let check = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/auth/api-keys");
+            then.status(StatusCode::UNAUTHORIZED)
+                .json_body(json!({ "error": "Unauthorized/Invalid api key" }));
+        });
+        let create = server.mock(|when, then| {
+            when.method(httpmock::Method::POST).path("/auth/api-key");
+            then.status(StatusCode::OK).json_body(json!({
+                "apiKey": API_KEY.to_string(),
+                "passphrase": PASSPHRASE,
+                "secret": SECRET
+            }));
+        });
+
+        let client = Client::new(&server.base_url(), Config::default())?
+            .authentication_builder(&signer)
+            .credential_store(Arc::clone(&store))
+            .authenticate()
+            .await?;
+
+        assert_eq!(client.credentials().key(), API_KEY);
+        check.assert_calls(1);
+        create.assert_calls(1);
+        assert_eq!(store.load(signer.address()).await?.unwrap().key(), API_KEY);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn explicit_credentials_should_bypass_store() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let store = Arc::new(MemoryStore::new());
+
+        Client::new(&server.base_url(), Config::default())?
+            .authentication_builder(&signer)
+            .credentials(Credentials::default())
+            .credential_store(Arc::clone(&store))
+            .authenticate()
+            .await?;
+
+        assert!(store.load(signer.address()).await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn store_and_nonce_should_fail() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        let err = Client::new(&server.base_url(), Config::default())?
+            .authentication_builder(&signer)
+            .nonce(123)
+            .credential_store(MemoryStore::new())
+            .authenticate()
+            .await
+            .unwrap_err();
+
+        assert!(
+            err.downcast_ref::<Validation>()
+                .unwrap()
+                .reason
+                .starts_with("Credential store and nonce are both set")
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "keystore")]
+    #[tokio::test]
+    async fn file_store_should_round_trip_encrypted_credentials() -> anyhow::Result<()> {
+        use polymarket_client_sdk::auth::persistence::FileStore;
+        use polymarket_client_sdk::types::Address;
+
+        let path = std::env::temp_dir().join(format!(
+            "credentials-{}.json",
+            polymarket_client_sdk::auth::Uuid::new_v4()
+        ));
+        let store = FileStore::new(&path, "correct horse").iterations(1_000);
+        let credentials = Credentials::new(API_KEY, SECRET.to_owned(), PASSPHRASE.to_owned());
+        let other = Address::repeat_byte(1);
+
+        assert!(store.load(other).await?.is_none());
+        store.save(other, &Credentials::default()).await?;
+        store.save(Address::ZERO, &credentials).await?;
+
+        let contents = std::fs::read_to_string(&path)?;
+        assert!(!contents.contains(SECRET));
+        assert!(!contents.contains(PASSPHRASE));
+
+        let loaded = store.load(Address::ZERO).await?.unwrap();
+        assert_eq!(loaded.key(), API_KEY);
+        assert_eq!(loaded.secret().expose_secret(), SECRET);
+        assert_eq!(loaded.passphrase().expose_secret(), PASSPHRASE);
+        assert!(store.load(other).await?.is_some());
+
+        let err = FileStore::new(&path, "wrong horse")
+            .load(Address::ZERO)
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<Validation>().is_some());
+
+        std::fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "keystore")]
+    #[tokio::test]
+    async fn file_store_should_keep_concurrent_saves() -> anyhow::Result<()> {
+        use polymarket_client_sdk::auth::persistence::FileStore;
+        use polymarket_client_sdk::types::Address;
+
+        let path = std::env::temp_dir().join(format!(
+            "credentials-{}.json",
+            polymarket_client_sdk::auth::Uuid::new_v4()
+        ));
+        let store = FileStore::new(&path, "correct horse").iterations(1_000);
+        let addresses: Vec<_> = (1..=8).map(Address::repeat_byte).collect();
+
+        let saves = addresses.iter().map(|&address| {
+            let store = store.clone();
+            tokio::spawn(async move { store.save(address, &Credentials::default()).await })
+        });
+        for save in futures_util::future::join_all(saves).await {
+            save??;
+        }
+
+        for address in addresses {
+            assert!(store.load(address).await?.is_some());
+        }
+
+        std::fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "keystore")]
+    #[tokio::test]
+    async fn file_store_should_reject_excessive_iterations() -> anyhow::Result<()> {
+        use polymarket_client_sdk::auth::persistence::FileStore;
+        use polymarket_client_sdk::types::Address;
+
+        let path = std::env::temp_dir().join(format!(
+            "credentials-{}.json",
+            polymarket_client_sdk::auth::Uuid::new_v4()
+        ));
+        let store = FileStore::new(&path, "correct horse").iterations(1_000);
+        store.save(Address::ZERO, &Credentials::default()).await?;
+
+        let contents = std::fs::read_to_string(&path)?;
+        std::fs::write(
+            &path,
+            contents.replace(r#""iterations": 1000"#, r#""iterations": 4294967295"#),
+        )?;
+
+        let err = store.load(Address::ZERO).await.unwrap_err();
+        assert!(
+            err.downcast_ref::<Validation>()
+                .unwrap()
+                .reason
+                .contains("exceed the limit")
+        );
+
+        std::fs::remove_file(&path)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "signing-service")]
+mod signing {
+    use alloy::dyn_abi::TypedData;
+    use polymarket_client_sdk::auth::signing::RemoteSigner;
+    use polymarket_client_sdk::auth::signing::service::SigningServer;
+    use polymarket_client_sdk::clob::types::{MarketParams, Side, TickSize};
+    use polymarket_client_sdk::types::Address;
+    use rust_decimal_macros::dec;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::common::{create_authenticated, token_1};
+
+    const TOKEN: &str = "service-token";
+
+    async fn start() -> anyhow::Result<(SigningServer, RemoteSigner)> {
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let service = SigningServer::bind("127.0.0.1:0", signer, TOKEN).await?;
+        let remote = RemoteSigner::connect(&service.host(), TOKEN)
+            .await?
+            .with_chain_id(Some(POLYGON));
+
+        Ok((service, remote))
+    }
+
+    fn clob_auth(domain_name: &str, address: Address) -> Value {
+        json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" }
+                ],
+                "ClobAuth": [
+                    { "name": "address", "type": "address" },
+                    { "name": "timestamp", "type": "string" },
+                    { "name": "nonce", "type": "uint256" },
+                    { "name": "message", "type": "string" }
+                ]
+            },
+            "primaryType": "ClobAuth",
+            "domain": { "name": domain_name, "version": "1", "chainId": POLYGON },
+            "message": {
+                "address": address,
+                "timestamp": "1",
+                "nonce": 0,
+                "message": "This message attests that I control the given wallet"
+            }
+        })
+    }
+
+    async fn post_sign(service: &SigningServer, body: Value) -> anyhow::Result<StatusCode> {
+        let response = reqwest::Client::new()
+            .post(format!("{}/sign", service.host()))
+            .bearer_auth(TOKEN)
+            .json(&body)
+            .send()
+            .await?;
+
+        Ok(response.status())
+    }
+
+    #[tokio::test]
+    async fn authenticate_through_remote_signer_should_succeed() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let (service, remote) = start().await?;
+        assert_eq!(remote.address(), service.address());
+
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/auth/derive-api-key")
+                .header(POLY_ADDRESS, remote.address().to_string().to_lowercase());
+            then.status(StatusCode::OK).json_body(json!({
+                "apiKey": API_KEY,
+                "passphrase": PASSPHRASE,
+                "secret": SECRET
+            }));
+        });
+
+        let client = Client::new(&server.base_url(), Config::default())?
+            .authentication_builder(&remote)
+            .nonce(1)
+            .authenticate()
+            .await?;
+
+        assert_eq!(client.address(), service.address());
+        mock.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn order_signed_through_remote_signer_should_verify() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let (_service, remote) = start().await?;
+
+        let params = MarketParams::builder()
+            .tick_size(TickSize::Hundredth)
+            .fee_rate_bps(0)
+            .neg_risk(false)
+            .build();
+        let signable_order = client
+            .limit_order()
+            .token_id(token_1())
+            .price(dec!(0.5))
+            .size(dec!(10))
+            .side(Side::Buy)
+            .build_offline(&params)?;
+
+        let signed_order = client.sign(&remote, signable_order).await?;
+
+        assert_eq!(signed_order.verify()?, remote.address());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn remote_signer_with_wrong_token_should_fail() -> anyhow::Result<()> {
+        let (service, _remote) = start().await?;
+
+        let err = RemoteSigner::connect(&service.host(), "wrong")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), Kind::Internal);
+
+        let typed_data: TypedData =
+            serde_json::from_value(clob_auth("ClobAuthDomain", service.address()))?;
+        let remote = RemoteSigner::new(&service.host(), "wrong", service.address())?;
+        remote
+            .sign_dynamic_typed_data(&typed_data)
+            .await
+            .unwrap_err();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn service_without_token_should_not_bind() -> anyhow::Result<()> {
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let err = SigningServer::bind("127.0.0.1:0", signer, "")
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), Kind::Validation);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn remote_signer_should_not_sign_raw_hashes() -> anyhow::Result<()> {
+        let (_service, remote) = start().await?;
+
+        remote.sign_hash(&[1; 32].into()).await.unwrap_err();
+        remote.sign_message(b"hello").await.unwrap_err();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn service_should_only_sign_known_messages_on_known_domains() -> anyhow::Result<()> {
+        let (service, _remote) = start().await?;
+        let address = service.address();
+
+        let allowed = json!({
+            "address": address,
+            "typedData": clob_auth("ClobAuthDomain", address)
+        });
+        assert_eq!(post_sign(&service, allowed).await?, StatusCode::OK);
+
+        let other_domain = json!({
+            "address": address,
+            "typedData": clob_auth("OtherDomain", address)
+        });
+        assert_eq!(
+            post_sign(&service, other_domain).await?,
+            StatusCode::FORBIDDEN
+        );
+
+        let other_signer = json!({
+            "address": address,
+            "typedData": clob_auth("ClobAuthDomain", Address::ZERO)
+        });
+        assert_eq!(
+            post_sign(&service, other_signer).await?,
+            StatusCode::FORBIDDEN
+        );
+
+        let other_type = json!({
+            "address": address,
+            "typedData": {
+                "types": {
+                    "EIP712Domain": [{ "name": "name", "type": "string" }],
+                    "Mail": [{ "name": "contents", "type": "string" }]
+                },
+                "primaryType": "Mail",
+                "domain": { "name": "ClobAuthDomain" },
+                "message": { "contents": "hello" }
+            }
+        });
+        assert_eq!(
+            post_sign(&service, other_type).await?,
+            StatusCode::FORBIDDEN
+        );
+
+        let oversized = json!({ "padding": "a".repeat(128 * 1024) });
+        assert_eq!(
+            post_sign(&service, oversized).await?,
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn signature_by_another_key_should_be_rejected() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?;
+        let typed_data: TypedData =
+            serde_json::from_value(clob_auth("ClobAuthDomain", signer.address()))?;
+        let signature = LocalSigner::random()
+            .sign_hash(&typed_data.eip712_signing_hash()?)
+            .await?;
+
+        server.mock(|when, then| {
+            when.method(httpmock::Method::POST).path("/sign");
+            then.status(StatusCode::OK)
+                .json_body(json!({ "signature": signature.to_string() }));
+        });
+
+        let remote = RemoteSigner::new(&server.base_url(), TOKEN, signer.address())?;
+        let err = remote
+            .sign_dynamic_typed_data(&typed_data)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("not by"), "{err}");
+
+        Ok(())
+    }
+}
+
+mod wallet_detection {
+    use polymarket_client_sdk::clob::types::{MarketParams, Side, SignatureType, TickSize};
+    use polymarket_client_sdk::types::Address;
+    use polymarket_client_sdk::{derive_proxy_wallet, derive_safe_wallet};
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::common::token_1;
+
+    const NO_CODE: &str = "0x";
+    const ZERO_BALANCE: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+    fn mock_rpc(server: &MockServer, method: &str, address: Address, result: &str) {
+        server.mock(|when, then| {
+            when.method(httpmock::Method::POST)
+                .path("/rpc")
+                .body_includes(method)
+                // Unprefixed, as the address is also ABI-encoded in `eth_call` data
+                .body_includes(format!("{address:x}"));
+            then.status(StatusCode::OK)
+                .json_body(json!({ "jsonrpc": "2.0", "id": 1, "result": result }));
+        });
+    }
+
+    fn mock_derive_api_key(server: &MockServer) {
+        server.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/auth/derive-api-key");
+            then.status(StatusCode::OK).json_body(json!({
+                "apiKey": API_KEY,
+                "passphrase": PASSPHRASE,
+                "secret": SECRET
+            }));
+        });
+    }
+
+    fn params() -> MarketParams {
+        MarketParams::builder()
+            .tick_size(TickSize::Hundredth)
+            .fee_rate_bps(0)
+            .neg_risk(false)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn deployed_safe_should_be_detected() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let proxy = derive_proxy_wallet(signer.address(), POLYGON).unwrap();
+        let safe = derive_safe_wallet(signer.address(), POLYGON).unwrap();
+
+        mock_rpc(&server, "eth_getCode", proxy, NO_CODE);
+        mock_rpc(&server, "eth_getCode", safe, "0x6080");
+        mock_derive_api_key(&server);
+
+        let config = Config::builder().rpc_host(server.url("/rpc")).build();
+        let client = Client::new(&server.base_url(), config)?
+            .authentication_builder(&signer)
+            .nonce(0)
+            .auto_detect_wallet()
+            .authenticate()
+            .await?;
+
+        let signable_order = client
+            .limit_order()
+            .token_id(token_1())
+            .price(dec!(0.5))
+            .size(dec!(10))
+            .side(Side::Buy)
+            .build_offline(&params())?;
+
+        assert_eq!(signable_order.order.maker, safe);
+        assert_eq!(
+            signable_order.order.signatureType,
+            SignatureType::GnosisSafe as u8
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn no_wallet_should_fall_back_to_eoa() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let proxy = derive_proxy_wallet(signer.address(), POLYGON).unwrap();
+        let safe = derive_safe_wallet(signer.address(), POLYGON).unwrap();
+
+        for address in [proxy, safe] {
+            mock_rpc(&server, "eth_getCode", address, NO_CODE);
+            mock_rpc(&server, "eth_call", address, ZERO_BALANCE);
+        }
+        mock_derive_api_key(&server);
+
+        let config = Config::builder().rpc_host(server.url("/rpc")).build();
+        let client = Client::new(&server.base_url(), config)?
+            .authentication_builder(&signer)
+            .nonce(0)
+            .auto_detect_wallet()
+            .authenticate()
+            .await?;
+
+        let signable_order = client
+            .limit_order()
+            .token_id(token_1())
+            .price(dec!(0.5))
+            .size(dec!(10))
+            .side(Side::Buy)
+            .build_offline(&params())?;
+
+        assert_eq!(signable_order.order.maker, signer.address());
+        assert_eq!(signable_order.order.signatureType, SignatureType::Eoa as u8);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn auto_detect_wallet_with_funder_should_fail() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        let err = Client::new(&server.base_url(), Config::default())?
+            .authentication_builder(&signer)
+            .funder(signer.address())
+            .auto_detect_wallet()
+            .authenticate()
+            .await
+            .unwrap_err();
+
+        let validation_err = err.downcast_ref::<Validation>().unwrap();
+        assert_eq!(
+            validation_err.reason,
+            "Wallet auto-detection and funder or signature type are both set. If auto-detection is enabled, then you must not supply a funder or signature type"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn auto_detect_wallet_with_explicit_eoa_should_fail() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        let config = Config::builder().rpc_host(server.url("/rpc")).build();
+        let err = Client::new(&server.base_url(), config)?
+            .authentication_builder(&signer)
+            .signature_type(SignatureType::Eoa)
+            .auto_detect_wallet()
+            .authenticate()
+            .await
+            .unwrap_err();
+
+        let validation_err = err.downcast_ref::<Validation>().unwrap();
+        assert_eq!(
+            validation_err.reason,
+            "Wallet auto-detection and funder or signature type are both set. If auto-detection is enabled, then you must not supply a funder or signature type"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn auto_detect_wallet_without_rpc_host_should_fail() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        let err = Client::new(&server.base_url(), Config::default())?
+            .authentication_builder(&signer)
+            .auto_detect_wallet()
+            .authenticate()
+            .await
+            .unwrap_err();
+
+        let validation_err = err.downcast_ref::<Validation>().unwrap();
+        assert_eq!(
+            validation_err.reason,
+            "Wallet auto-detection requires a Polygon JSON-RPC endpoint, set one with Config::rpc_host"
+        );
+
+        Ok(())
+    }
+}
@@ -27,10 +27,15 @@ use crate::common::{
 };
 
 mod unauthenticated {
+    use std::time::Duration;
+
+    use alloy::signers::Signer as _;
+    use alloy::signers::local::LocalSigner;
 
     use chrono::{TimeDelta, TimeZone as _};
     use futures_util::future;
     use futures_util::stream::StreamExt as _;
+    use polymarket_client_sdk::clob::rate_limit::{EndpointGroup, Quota, RateLimits};
     use polymarket_client_sdk::clob::types::request::{
         LastTradePriceRequest, MidpointRequest, OrderBookSummaryRequest, PriceHistoryRequest,
         PriceRequest, SpreadRequest,
@@ -43,7 +48,9 @@ mod unauthenticated {
         SpreadsResponse, TickSizeResponse, Token,
     };
     use polymarket_client_sdk::clob::types::{Interval, Side, TickSize, TimeRange};
-    use polymarket_client_sdk::error::Status;
+    use polymarket_client_sdk::error::{Kind, Status};
+    use polymarket_client_sdk::retry::RetryPolicy;
+    use polymarket_client_sdk::timeout;
     use polymarket_client_sdk::types::address;
     use reqwest::Method;
 
@@ -1372,9 +1379,169 @@ mod unauthenticated {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn retry_policy_retries_idempotent_requests_should_succeed() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let retry_policy = RetryPolicy::builder()
+            .max_attempts(3)
+            .initial_backoff(Duration::from_millis(1))
+            .build();
+        let config = Config::builder().retry_policy(retry_policy).build();
+        let client = Client::new(&server.base_url(), config)?;
+
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/book");
+            then.status(StatusCode::SERVICE_UNAVAILABLE)
+                .header("Retry-After", "0");
+        });
+
+        let request = OrderBookSummaryRequest::builder()
+            .token_id(token_1())
+            .build();
+        let err = client.order_book(&request).await.unwrap_err();
+
+        let status = err.downcast_ref::<Status>().unwrap();
+        assert_eq!(status.status_code, StatusCode::SERVICE_UNAVAILABLE);
+        mock.assert_calls(3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retry_policy_skips_authenticated_requests_should_succeed() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let retry_policy = RetryPolicy::builder()
+            .max_attempts(3)
+            .initial_backoff(Duration::from_millis(1))
+            .build();
+        let config = Config::builder().retry_policy(retry_policy).build();
+        let client = Client::new(&server.base_url(), config)?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/auth/derive-api-key");
+            then.status(StatusCode::SERVICE_UNAVAILABLE)
+                .header("Retry-After", "0");
+        });
+
+        let err = client.derive_api_key(&signer, None).await.unwrap_err();
+
+        let status = err.downcast_ref::<Status>().unwrap();
+        assert_eq!(status.status_code, StatusCode::SERVICE_UNAVAILABLE);
+        mock.assert_calls(1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rate_limits_queue_requests_should_succeed() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let rate_limits = RateLimits::builder()
+            .market_data(Quota::new(1, Duration::from_millis(100)))
+            .build();
+        let config = Config::builder().rate_limits(rate_limits).build();
+        let client = Client::new(&server.base_url(), config)?;
+
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/");
+            then.status(StatusCode::OK).body("\"OK\"");
+        });
+
+        let start = std::time::Instant::now();
+        let (first, second, third) = tokio::join!(client.ok(), client.ok(), client.ok());
+        first?;
+        second?;
+        third?;
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        mock.assert_calls(3);
+
+        let stats = client.rate_limit_stats(EndpointGroup::MarketData).unwrap();
+        assert_eq!(stats.queue_depth, 0);
+        assert_eq!(stats.delayed, 2);
+        assert!(stats.max_wait >= Duration::from_millis(150));
+        assert!(client.rate_limit_stats(EndpointGroup::Orders).is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retry_policy_skips_non_idempotent_requests_should_succeed() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let retry_policy = RetryPolicy::builder()
+            .max_attempts(3)
+            .initial_backoff(Duration::from_millis(1))
+            .build();
+        let config = Config::builder().retry_policy(retry_policy).build();
+        let client = Client::new(&server.base_url(), config)?;
+
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::POST).path("/books");
+            then.status(StatusCode::SERVICE_UNAVAILABLE);
+        });
+
+        let request = OrderBookSummaryRequest::builder()
+            .token_id(token_1())
+            .build();
+        client.order_books(&[request]).await.unwrap_err();
+
+        mock.assert_calls(1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn slow_response_should_fail_with_timeout() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let config = Config::builder().timeout(Duration::from_millis(50)).build();
+        let client = Client::new(&server.base_url(), config)?;
+
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/");
+            then.status(StatusCode::OK)
+                .body("\"OK\"")
+                .delay(Duration::from_millis(500));
+        });
+
+        let err = client.ok().await.unwrap_err();
+
+        assert_eq!(err.kind(), Kind::Timeout);
+        assert!(err.downcast_ref::<Status>().is_none());
+        mock.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn within_should_override_client_timeout() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let config = Config::builder().timeout(Duration::from_millis(50)).build();
+        let client = Client::new(&server.base_url(), config)?;
+
+        let mock = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/");
+            then.status(StatusCode::OK)
+                .body("\"OK\"")
+                .delay(Duration::from_millis(200));
+        });
+
+        let response = timeout::within(Duration::from_secs(5), client.ok()).await?;
+        assert_eq!(response, "OK");
+
+        let err = timeout::within(Duration::from_millis(20), client.ok())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), Kind::Timeout);
+        mock.assert_calls(2);
+
+        Ok(())
+    }
 }
 
 mod authenticated {
+    use std::sync::Arc;
     #[cfg(feature = "heartbeats")]
     use std::time::Duration;
 
@@ -1383,6 +1550,9 @@ mod authenticated {
     use alloy::signers::local::LocalSigner;
     use chrono::NaiveDate;
     use httpmock::Method::{DELETE, GET, POST};
+    use polymarket_client_sdk::clob::client::MAX_ORDERS_PER_BATCH;
+    use polymarket_client_sdk::clob::order_batch::Ladder;
+    use polymarket_client_sdk::clob::risk::{MaxOrderSize, PriceBand};
     use polymarket_client_sdk::clob::types::request::{
         BalanceAllowanceRequest, CancelMarketOrderRequest, DeleteNotificationsRequest,
         OrdersRequest, TradesRequest, UserRewardsEarningRequest,
@@ -1395,11 +1565,12 @@ mod authenticated {
         TotalUserEarningResponse, TradeResponse, UserEarningResponse, UserRewardsEarningResponse,
     };
     use polymarket_client_sdk::clob::types::{
-        AssetType, OrderStatusType, OrderType, Side, SignableOrder, SignedOrder, TickSize,
-        TradeStatusType, TraderSide,
+        AssetType, OrderStatusType, OrderType, ReplaceStrategy, Side, SignableOrder, SignedOrder,
+        TickSize, TradeStatusType, TraderSide,
     };
     #[cfg(feature = "heartbeats")]
     use polymarket_client_sdk::error::Synchronization;
+    use polymarket_client_sdk::error::{Kind as ErrorKind, RiskRejection};
     use polymarket_client_sdk::types::{Address, address, b256};
 
     use super::*;
@@ -1549,6 +1720,8 @@ mod authenticated {
                 )?,
                 true,
             ))
+            .chain_id(POLYGON)
+            .neg_risk(false)
             .build();
 
         assert_eq!(signed_order.order.taker, taker);
@@ -1629,6 +1802,193 @@ mod authenticated {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn post_order_should_run_risk_checks() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        server.mock(|when, then| {
+            when.method(GET).path("/auth/derive-api-key");
+            then.status(StatusCode::OK).json_body(json!({
+                "apiKey": API_KEY.to_string(),
+                "passphrase": PASSPHRASE,
+                "secret": SECRET
+            }));
+        });
+
+        let config = Config::builder()
+            .risk_checks(vec![
+                Arc::new(MaxOrderSize::new(dec!(1_000))),
+                Arc::new(PriceBand::new(dec!(0.05))),
+            ])
+            .build();
+        let client = Client::new(&server.base_url(), config)?
+            .authentication_builder(&signer)
+            .authenticate()
+            .await?;
+
+        ensure_requirements(&server, token_1(), TickSize::Hundredth);
+
+        let midpoint = server.mock(|when, then| {
+            when.method(GET)
+                .path("/midpoint")
+                .query_param("token_id", token_1().to_string());
+            then.status(StatusCode::OK)
+                .json_body(json!({ "mid": "0.5" }));
+        });
+        let post = server.mock(|when, then| {
+            when.method(POST).path("/order");
+            then.status(StatusCode::OK).json_body(json!({
+                "error_msg": "",
+                "makingAmount": "",
+                "orderID": "0x23b457271bce9fa09b4f79125c9ec09e968235a462de82e318ef4eb6fe0ffeb0",
+                "status": "live",
+                "success": true,
+                "takingAmount": ""
+            }));
+        });
+
+        // A size mis-scaled by 100x never reaches the exchange
+        let signable_order = client
+            .limit_order()
+            .token_id(token_1())
+            .price(dec!(0.5))
+            .size(dec!(5_000))
+            .side(Side::Buy)
+            .build()
+            .await?;
+        let signed_order = client.sign(&signer, signable_order).await?;
+
+        let err = client.post_order(signed_order).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Risk);
+        assert_eq!(
+            err.downcast_ref::<RiskRejection>().unwrap().check,
+            "max_order_size"
+        );
+        post.assert_calls(0);
+
+        let signable_order = client
+            .limit_order()
+            .token_id(token_1())
+            .price(dec!(0.52))
+            .size(dec!(50))
+            .side(Side::Buy)
+            .build()
+            .await?;
+        let signed_order = client.sign(&signer, signable_order).await?;
+
+        let response = client.post_order(signed_order).await?;
+        assert!(response.success);
+        midpoint.assert();
+        post.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn post_order_batches_should_split_into_requests() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        ensure_requirements(&server, token_1(), TickSize::Hundredth);
+
+        let response = json!({
+            "error_msg": "",
+            "makingAmount": "",
+            "orderID": "0x23b457271bce9fa09b4f79125c9ec09e968235a462de82e318ef4eb6fe0ffeb0",
+            "status": "live",
+            "success": true,
+            "takingAmount": ""
+        });
+        let full = server.mock(|when, then| {
+            when.method(POST).path("/orders").is_true(|request| {
+                serde_json::from_slice::<Vec<serde_json::Value>>(request.body().as_ref())
+                    .is_ok_and(|orders| orders.len() == MAX_ORDERS_PER_BATCH)
+            });
+            then.status(StatusCode::OK)
+                .json_body(json!(vec![response.clone(); MAX_ORDERS_PER_BATCH]));
+        });
+        let rest = server.mock(|when, then| {
+            when.method(POST).path("/orders").is_true(|request| {
+                serde_json::from_slice::<Vec<serde_json::Value>>(request.body().as_ref())
+                    .is_ok_and(|orders| orders.len() == 1)
+            });
+            then.status(StatusCode::OK).json_body(json!([response]));
+        });
+
+        let ladder = Ladder::builder().levels(8).size(dec!(10));
+        let orders = client
+            .order_ladder()
+            .token_id(token_1())
+            .bids(ladder.clone().start(dec!(0.48)).build())
+            .asks(ladder.start(dec!(0.52)).build())
+            .build()
+            .await?;
+        let signed_orders = client.sign_orders(&signer, orders).await?;
+        assert_eq!(signed_orders.len(), 16);
+
+        let batches = client.post_order_batches(signed_orders).await?;
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].as_ref().unwrap().len(), MAX_ORDERS_PER_BATCH);
+        assert_eq!(batches[1].as_ref().unwrap().len(), 1);
+        full.assert_calls(1);
+        rest.assert_calls(1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn post_order_batches_should_send_every_request() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        ensure_requirements(&server, token_1(), TickSize::Hundredth);
+
+        let full = server.mock(|when, then| {
+            when.method(POST).path("/orders").is_true(|request| {
+                serde_json::from_slice::<Vec<serde_json::Value>>(request.body().as_ref())
+                    .is_ok_and(|orders| orders.len() == MAX_ORDERS_PER_BATCH)
+            });
+            then.status(StatusCode::BAD_REQUEST)
+                .json_body(json!({ "error": "invalid order" }));
+        });
+        let rest = server.mock(|when, then| {
+            when.method(POST).path("/orders").is_true(|request| {
+                serde_json::from_slice::<Vec<serde_json::Value>>(request.body().as_ref())
+                    .is_ok_and(|orders| orders.len() == 1)
+            });
+            then.status(StatusCode::OK).json_body(json!([{
+                "error_msg": "",
+                "makingAmount": "",
+                "orderID": "0x23b457271bce9fa09b4f79125c9ec09e968235a462de82e318ef4eb6fe0ffeb0",
+                "status": "live",
+                "success": true,
+                "takingAmount": ""
+            }]));
+        });
+
+        let ladder = Ladder::builder().levels(8).size(dec!(10));
+        let orders = client
+            .order_ladder()
+            .token_id(token_1())
+            .bids(ladder.clone().start(dec!(0.48)).build())
+            .asks(ladder.start(dec!(0.52)).build())
+            .build()
+            .await?;
+        let signed_orders = client.sign_orders(&signer, orders).await?;
+
+        let batches = client.post_order_batches(signed_orders).await?;
+        assert_eq!(batches.len(), 2);
+        batches[0].as_ref().unwrap_err();
+        assert_eq!(batches[1].as_ref().unwrap().len(), 1);
+        full.assert_calls(1);
+        rest.assert_calls(1);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn post_order_should_accept_transactions_hashes_alias() -> anyhow::Result<()> {
         let server = MockServer::start();
@@ -1887,6 +2247,122 @@ mod authenticated {
         Ok(())
     }
 
+    fn mock_post_order(server: &MockServer, status: StatusCode) -> httpmock::Mock<'_> {
+        server.mock(|when, then| {
+            when.method(POST).path("/order");
+            then.status(status).json_body(json!({
+                "error_msg": "",
+                "makingAmount": "",
+                "orderID": "0x23b457271bce9fa09b4f79125c9ec09e968235a462de82e318ef4eb6fe0ffeb0",
+                "status": "live",
+                "success": true,
+                "takingAmount": ""
+            }));
+        })
+    }
+
+    #[tokio::test]
+    async fn replace_order_cancel_first_should_post_after_cancel() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        ensure_requirements(&server, token_1(), TickSize::Hundredth);
+
+        let cancel = server.mock(|when, then| {
+            when.method(DELETE)
+                .path("/order")
+                .json_body(json!({ "orderId": "1" }));
+            then.status(StatusCode::OK)
+                .json_body(json!({ "canceled": ["1"] }));
+        });
+        let post = mock_post_order(&server, StatusCode::OK);
+
+        let result = client
+            .replace_order(
+                &signer,
+                "1",
+                SignableOrder::default(),
+                ReplaceStrategy::CancelFirst,
+            )
+            .await?;
+
+        assert!(result.canceled());
+        assert!(result.posted());
+        assert!(result.is_complete());
+        cancel.assert();
+        post.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replace_order_cancel_first_should_not_post_if_not_canceled() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        ensure_requirements(&server, token_1(), TickSize::Hundredth);
+
+        let cancel = server.mock(|when, then| {
+            when.method(DELETE).path("/order");
+            then.status(StatusCode::OK).json_body(json!({
+                "canceled": [],
+                "not_canceled": { "1": "the order is already canceled" }
+            }));
+        });
+        let post = mock_post_order(&server, StatusCode::OK);
+
+        let result = client
+            .replace_order(
+                &signer,
+                "1",
+                SignableOrder::default(),
+                ReplaceStrategy::CancelFirst,
+            )
+            .await?;
+
+        assert!(!result.canceled());
+        assert!(result.post.is_none());
+        assert!(!result.is_complete());
+        cancel.assert();
+        post.assert_calls(0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replace_order_post_first_should_not_cancel_if_post_fails() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        ensure_requirements(&server, token_1(), TickSize::Hundredth);
+
+        let cancel = server.mock(|when, then| {
+            when.method(DELETE).path("/order");
+            then.status(StatusCode::OK)
+                .json_body(json!({ "canceled": ["1"] }));
+        });
+        let post = mock_post_order(&server, StatusCode::BAD_REQUEST);
+
+        let result = client
+            .replace_order(
+                &signer,
+                "1",
+                SignableOrder::default(),
+                ReplaceStrategy::PostFirst,
+            )
+            .await?;
+
+        assert!(matches!(result.post, Some(Err(_))));
+        assert!(result.cancel.is_none());
+        post.assert();
+        cancel.assert_calls(0);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn cancel_orders_should_succeed() -> anyhow::Result<()> {
         let server = MockServer::start();
@@ -2913,6 +3389,267 @@ mod authenticated {
 
         Ok(())
     }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn order_manager_resync_should_succeed() -> anyhow::Result<()> {
+        use polymarket_client_sdk::clob::OrderManager;
+        use polymarket_client_sdk::clob::order_manager::OrderState;
+
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        ensure_requirements(&server, token_1(), TickSize::Hundredth);
+
+        server.mock(|when, then| {
+            when.method(POST).path("/order");
+            then.status(StatusCode::OK).json_body(json!({
+                "error_msg": "",
+                "makingAmount": "",
+                "orderID": "0xorder",
+                "status": "live",
+                "success": true,
+                "takingAmount": ""
+            }));
+        });
+        let open_orders = server.mock(|when, then| {
+            when.method(GET).path("/data/orders");
+            then.status(StatusCode::OK).json_body(json!({
+                "data": [],
+                "limit": 0,
+                "count": 0,
+                "next_cursor": "LTE="
+            }));
+        });
+        let order = server.mock(|when, then| {
+            when.method(GET).path("/data/order/0xorder");
+            then.status(StatusCode::OK).json_body(json!({
+                "id": "0xorder",
+                "status": "MATCHED",
+                "owner": API_KEY,
+                "maker_address": "0x2222222222222222222222222222222222222222",
+                "market": "0x000000000000000000000000000000000000000000000000006d61726b657461",
+                "asset_id": token_1(),
+                "side": "buy",
+                "original_size": "10",
+                "size_matched": "10",
+                "price": "0.45",
+                "associate_trades": ["trade-1"],
+                "outcome": "YES",
+                "created_at": 1_705_322_096,
+                "expiration": "0",
+                "order_type": "GTC"
+            }));
+        });
+        let trades = server.mock(|when, then| {
+            when.method(GET)
+                .path("/data/trades")
+                .query_param("after", "1705322096");
+            then.status(StatusCode::OK).json_body(json!({
+                "data": [
+                    {
+                        "id": "trade-1",
+                        "taker_order_id": "0xorder",
+                        "market": "0x000000000000000000000000000000000000000000000000006d61726b657461",
+                        "asset_id": token_1(),
+                        "side": "BUY",
+                        "size": "10",
+                        "fee_rate_bps": "0",
+                        "price": "0.44",
+                        "status": "CONFIRMED",
+                        "match_time": "1705322100",
+                        "last_update": "1705322130",
+                        "outcome": "YES",
+                        "bucket_index": 0,
+                        "owner": API_KEY,
+                        "maker_address": "0x2222222222222222222222222222222222222222",
+                        "maker_orders": [
+                            {
+                                "order_id": "0xcounterparty",
+                                "owner": "ffffffff-ffff-ffff-ffff-ffffffffffff",
+                                "maker_address": "0x4444444444444444444444444444444444444444",
+                                "matched_amount": "10",
+                                "price": "0.44",
+                                "fee_rate_bps": "0",
+                                "asset_id": token_1(),
+                                "outcome": "YES",
+                                "side": "SELL"
+                            }
+                        ],
+                        "transaction_hash": "0xabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcd",
+                        "trader_side": "TAKER"
+                    }
+                ],
+                "limit": 1,
+                "count": 1,
+                "next_cursor": "LTE="
+            }));
+        });
+
+        let manager = OrderManager::new(client.clone());
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let signed_order = client.sign(&signer, SignableOrder::default()).await?;
+        manager.post_order(signed_order).await?;
+
+        assert_eq!(manager.open_orders().len(), 1);
+        assert_eq!(manager.order("0xorder").unwrap().state, OrderState::Live);
+
+        manager.resync().await?;
+
+        let tracked = manager.order("0xorder").unwrap();
+        assert_eq!(tracked.state, OrderState::Filled);
+        assert_eq!(tracked.size_matched, dec!(10));
+        assert_eq!(tracked.average_fill_price(), Some(dec!(0.44)));
+        assert!(manager.open_orders().is_empty());
+        assert!(
+            manager.order("0xcounterparty").is_none(),
+            "counterparty orders must not be tracked"
+        );
+        open_orders.assert();
+        order.assert();
+        trades.assert();
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ctf")]
+    #[tokio::test]
+    async fn nonce_manager_sync_should_stamp_orders() -> anyhow::Result<()> {
+        use alloy::providers::ProviderBuilder;
+        use polymarket_client_sdk::clob::nonce_manager::NonceManager;
+
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        ensure_requirements(&server, token_1(), TickSize::Hundredth);
+
+        let rpc = MockServer::start();
+        let nonces = rpc.mock(|when, then| {
+            when.method(POST).path("/");
+            then.json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": "0x0000000000000000000000000000000000000000000000000000000000000005"
+            }));
+        });
+        let provider = ProviderBuilder::new().connect(&rpc.base_url()).await?;
+        let manager = NonceManager::new(client.clone(), provider, POLYGON)?;
+
+        assert_eq!(manager.nonce(false), None);
+        assert_eq!(manager.sync().await?, [U256::from(5), U256::from(5)]);
+        assert_eq!(manager.nonce(false), Some(U256::from(5)));
+        nonces.assert_calls(2);
+
+        let signable_order = client
+            .limit_order()
+            .token_id(token_1())
+            .price(dec!(0.5))
+            .size(dec!(10))
+            .side(Side::Buy)
+            .build()
+            .await?;
+        let signed_order = client.sign(&signer, signable_order).await?;
+        assert_eq!(signed_order.order.nonce, U256::from(5));
+
+        // An explicit nonce is left as is, even zero
+        for nonce in [7, 0] {
+            let signable_order = client
+                .limit_order()
+                .token_id(token_1())
+                .price(dec!(0.5))
+                .size(dec!(10))
+                .side(Side::Buy)
+                .nonce(nonce)
+                .build()
+                .await?;
+            let signed_order = client.sign(&signer, signable_order).await?;
+            assert_eq!(signed_order.order.nonce, U256::from(nonce));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn tick_size_sync_should_update_cache_and_report_invalid_orders() -> anyhow::Result<()> {
+        use polymarket_client_sdk::clob::TickSizeSync;
+        use polymarket_client_sdk::clob::ws::TickSizeChange;
+
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let open_order = |id: &str, price: &str| {
+            json!({
+                "id": id,
+                "status": "LIVE",
+                "owner": "ffffffff-ffff-ffff-ffff-ffffffffffff",
+                "maker_address": "0x2222222222222222222222222222222222222222",
+                "market": "0x000000000000000000000000000000000000000000000000006d61726b657461",
+                "asset_id": token_1(),
+                "side": "buy",
+                "original_size": "10.0",
+                "size_matched": "0",
+                "price": price,
+                "associate_trades": [],
+                "outcome": "YES",
+                "created_at": 1_705_322_096,
+                "expiration": "0",
+                "order_type": "GTC"
+            })
+        };
+        let open_orders = server.mock(|when, then| {
+            when.method(GET)
+                .path("/data/orders")
+                .query_param("asset_id", token_1().to_string());
+            then.status(StatusCode::OK).json_body(json!({
+                "data": [open_order("0xvalid", "0.5"), open_order("0xinvalid", "0.55")],
+                "limit": 2,
+                "count": 2,
+                "next_cursor": "LTE="
+            }));
+        });
+        let tick_size = server.mock(|when, then| {
+            when.method(GET).path("/tick-size");
+            then.status(StatusCode::OK)
+                .json_body(json!({ "minimum_tick_size": "0.01" }));
+        });
+
+        client.set_tick_size(token_1(), TickSize::Hundredth);
+        let sync = TickSizeSync::new(client.clone());
+        let mut updates = sync.subscribe();
+
+        let change = TickSizeChange::builder()
+            .asset_id(token_1())
+            .market(b256!(
+                "000000000000000000000000000000000000000000000000006d61726b657461"
+            ))
+            .old_tick_size(dec!(0.01))
+            .new_tick_size(dec!(0.1))
+            .timestamp(1_705_322_096_000)
+            .build();
+        let update = sync.apply(&change).await?;
+
+        assert_eq!(update.old_tick_size, Some(TickSize::Hundredth));
+        assert_eq!(update.tick_size, TickSize::Tenth);
+        let invalid: Vec<_> = update
+            .invalid_orders
+            .iter()
+            .map(|order| &order.id)
+            .collect();
+        assert_eq!(invalid, ["0xinvalid"]);
+        assert_eq!(updates.try_recv()?, update);
+
+        // Orders are now validated against the new tick size, without a lookup
+        assert_eq!(
+            client.tick_size(token_1()).await?.minimum_tick_size,
+            TickSize::Tenth
+        );
+        open_orders.assert();
+        tick_size.assert_calls(0);
+
+        Ok(())
+    }
 }
 
 mod builder_authenticated {
@@ -3240,3 +3977,172 @@ mod builder_authenticated {
         Ok(())
     }
 }
+
+mod clock_sync {
+    use std::time::Duration;
+
+    use alloy::signers::Signer as _;
+    use alloy::signers::local::LocalSigner;
+    use chrono::TimeDelta;
+    use httpmock::Method::GET;
+    use polymarket_client_sdk::clob::clock::ClockSync;
+
+    use super::*;
+    use crate::common::{API_KEY, PASSPHRASE, POLY_TIMESTAMP, SECRET};
+
+    #[tokio::test]
+    async fn skew_should_be_measured_once_per_interval() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let server_time = Utc::now().timestamp() + 3600;
+
+        let time = server.mock(|when, then| {
+            when.method(GET).path("/time");
+            then.status(StatusCode::OK).json_body(server_time);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/auth/derive-api-key");
+            then.status(StatusCode::OK).json_body(json!({
+                "apiKey": API_KEY.to_string(),
+                "passphrase": PASSPHRASE,
+                "secret": SECRET
+            }));
+        });
+        let api_keys = server.mock(|when, then| {
+            when.method(GET)
+                .path("/auth/api-keys")
+                .header_exists(POLY_TIMESTAMP);
+            then.status(StatusCode::OK)
+                .json_body(json!({ "apiKeys": [API_KEY] }));
+        });
+
+        let config = Config::builder().clock_sync(ClockSync::default()).build();
+        let client = Client::new(&server.base_url(), config)?;
+        assert!(client.clock_skew().is_none());
+
+        let client = client
+            .authentication_builder(&signer)
+            .nonce(0)
+            .authenticate()
+            .await?;
+        for _ in 0..3 {
+            client.api_keys().await?;
+        }
+
+        time.assert_calls(1);
+        api_keys.assert_calls(3);
+        let skew = client.clock_skew().unwrap();
+        assert!(
+            (skew - TimeDelta::hours(1)).abs() < TimeDelta::seconds(2),
+            "unexpected skew {skew}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn skew_should_be_measured_again_after_interval() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        let time = server.mock(|when, then| {
+            when.method(GET).path("/time");
+            then.status(StatusCode::OK)
+                .json_body(Utc::now().timestamp());
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/auth/derive-api-key");
+            then.status(StatusCode::OK).json_body(json!({
+                "apiKey": API_KEY.to_string(),
+                "passphrase": PASSPHRASE,
+                "secret": SECRET
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/auth/api-keys");
+            then.status(StatusCode::OK)
+                .json_body(json!({ "apiKeys": [API_KEY] }));
+        });
+
+        let sync = ClockSync::builder().interval(Duration::ZERO).build();
+        let config = Config::builder().clock_sync(sync).build();
+        Client::new(&server.base_url(), config)?
+            .authentication_builder(&signer)
+            .nonce(0)
+            .authenticate()
+            .await?
+            .api_keys()
+            .await?;
+
+        // The first measurement, then one in the background once the skew is stale
+        wait_for_calls(&time, 2).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn failed_measurement_should_keep_last_skew() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let server_time = Utc::now().timestamp() + 3600;
+
+        let mut time = server.mock(|when, then| {
+            when.method(GET).path("/time");
+            then.status(StatusCode::OK).json_body(server_time);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/auth/derive-api-key");
+            then.status(StatusCode::OK).json_body(json!({
+                "apiKey": API_KEY.to_string(),
+                "passphrase": PASSPHRASE,
+                "secret": SECRET
+            }));
+        });
+        let api_keys = server.mock(|when, then| {
+            when.method(GET).path("/auth/api-keys");
+            then.status(StatusCode::OK)
+                .json_body(json!({ "apiKeys": [API_KEY] }));
+        });
+
+        let interval = Duration::from_millis(500);
+        let sync = ClockSync::builder().interval(interval).build();
+        let config = Config::builder().clock_sync(sync).build();
+        let client = Client::new(&server.base_url(), config)?
+            .authentication_builder(&signer)
+            .nonce(0)
+            .authenticate()
+            .await?;
+        time.assert_calls(1);
+
+        tokio::time::sleep(interval).await;
+        time.delete();
+        let failing = server.mock(|when, then| {
+            when.method(GET).path("/time");
+            then.status(StatusCode::INTERNAL_SERVER_ERROR);
+        });
+
+        client.api_keys().await?;
+        wait_for_calls(&failing, 1).await;
+        client.api_keys().await?;
+
+        api_keys.assert_calls(2);
+        let skew = client.clock_skew().unwrap();
+        assert!(
+            (skew - TimeDelta::hours(1)).abs() < TimeDelta::seconds(2),
+            "unexpected skew {skew}"
+        );
+
+        Ok(())
+    }
+
+    /// Waits for the background measurement to call `mock` at least `calls` times.
+    async fn wait_for_calls(mock: &httpmock::Mock<'_>, calls: usize) {
+        for _ in 0..100 {
+            if mock.calls() >= calls {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("expected at least {calls} calls, got {}", mock.calls());
+    }
+}
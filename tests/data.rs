@@ -41,6 +41,62 @@ mod health {
     }
 }
 
+mod transport {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use httpmock::{Method::GET, MockServer};
+    use polymarket_client_sdk::Result;
+    use polymarket_client_sdk::data::Client;
+    use polymarket_client_sdk::transport::{
+        Middleware, Next, Request, Response, Stack, async_trait,
+    };
+    use reqwest::StatusCode;
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+
+    #[derive(Debug, Default)]
+    struct Tracing(AtomicU32);
+
+    #[async_trait]
+    impl Middleware for Tracing {
+        async fn handle(&self, mut request: Request, next: Next<'_>) -> Result<Response> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            request
+                .headers_mut()
+                .insert("x-trace-id", HeaderValue::from_static("abc"));
+
+            next.run(request).await
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_should_go_through_custom_transport() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let middleware = Arc::new(Tracing::default());
+        let transport = Stack::new(reqwest::Client::new()).layer(Arc::clone(&middleware));
+        let client = Client::new(&server.base_url())?.with_transport(transport);
+
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/")
+                .header("x-trace-id", "abc")
+                .header("user-agent", "rs_clob_client");
+            then.status(StatusCode::OK).json_body(json!({
+                "data": "OK"
+            }));
+        });
+
+        let response = client.health().await?;
+
+        assert_eq!(response.data, "OK");
+        assert_eq!(middleware.0.load(Ordering::Relaxed), 1);
+        mock.assert();
+
+        Ok(())
+    }
+}
+
 mod positions {
     use httpmock::{Method::GET, MockServer};
     use polymarket_client_sdk::data::{Client, types::request::PositionsRequest};
@@ -135,6 +191,66 @@ mod positions {
 
         Ok(())
     }
+
+    #[cfg(feature = "clob")]
+    #[tokio::test]
+    async fn position_book_reconcile_should_report_drift() -> anyhow::Result<()> {
+        use polymarket_client_sdk::auth::ApiKey;
+        use polymarket_client_sdk::clob::PositionBook;
+        use polymarket_client_sdk::types::U256;
+
+        let server = MockServer::start();
+        let client = Client::new(&server.base_url())?;
+
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/positions")
+                .query_param("user", "0x1234567890abcdef1234567890abcdef12345678")
+                .query_param("sizeThreshold", "0")
+                .query_param("limit", "500")
+                .query_param("offset", "0");
+            then.status(StatusCode::OK).json_body(json!([
+                {
+                    "proxyWallet": "0x1234567890abcdef1234567890abcdef12345678",
+                    "asset": "1",
+                    "conditionId": "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890",
+                    "size": 100.5,
+                    "avgPrice": 0.65,
+                    "initialValue": 65.325,
+                    "currentValue": 70.35,
+                    "cashPnl": 5.025,
+                    "percentPnl": 7.69,
+                    "totalBought": 100.5,
+                    "realizedPnl": 0.0,
+                    "percentRealizedPnl": 0.0,
+                    "curPrice": 0.70,
+                    "redeemable": false,
+                    "mergeable": false,
+                    "title": "Will BTC hit $100k?",
+                    "slug": "btc-100k",
+                    "icon": "https://example.com/btc.png",
+                    "eventSlug": "crypto-prices",
+                    "outcome": "Yes",
+                    "outcomeIndex": 0,
+                    "oppositeOutcome": "No",
+                    "oppositeAsset": "2",
+                    "endDate": "2025-12-31",
+                    "negativeRisk": false
+                }
+            ]));
+        });
+
+        let book = PositionBook::new(ApiKey::nil());
+        let drift = book.reconcile(&client, test_user()).await?;
+
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].asset_id, U256::from(1));
+        assert_eq!(drift[0].size_difference(), dec!(100.5));
+        assert_eq!(drift[0].remote_average_price, Some(dec!(0.65)));
+        mock.assert();
+
+        Ok(())
+    }
 }
 
 mod trades {
@@ -874,9 +990,12 @@ mod builder_volume {
 }
 
 mod error_handling {
+    use std::time::Duration;
+
     use httpmock::{Method::GET, MockServer};
     use polymarket_client_sdk::data::{Client, types::request::PositionsRequest};
     use polymarket_client_sdk::error::Kind;
+    use polymarket_client_sdk::retry::RetryPolicy;
     use reqwest::StatusCode;
     use serde_json::json;
 
@@ -952,6 +1071,33 @@ mod error_handling {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn server_error_with_retry_policy_should_retry() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let retry_policy = RetryPolicy::builder()
+            .max_attempts(2)
+            .initial_backoff(Duration::from_millis(1))
+            .build();
+        let client = Client::new(&server.base_url())?.with_retry_policy(retry_policy);
+
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/positions");
+            then.status(StatusCode::INTERNAL_SERVER_ERROR)
+                .json_body(json!({
+                    "error": "Internal server error"
+                }));
+        });
+
+        let request = PositionsRequest::builder().user(test_user()).build();
+
+        let err = client.positions(&request).await.unwrap_err();
+
+        assert_eq!(err.kind(), Kind::Status);
+        mock.assert_calls(2);
+
+        Ok(())
+    }
 }
 
 mod client {
@@ -188,6 +188,47 @@ mod lifecycle {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn counter_salt_should_keep_identical_orders_distinct() -> anyhow::Result<()> {
+        use polymarket_client_sdk::clob::order_builder::CounterSalt;
+
+        let server = MockServer::start();
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        server.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/auth/derive-api-key");
+            then.status(StatusCode::OK).json_body(json!({
+                "apiKey": API_KEY.to_string(),
+                "passphrase": PASSPHRASE,
+                "secret": SECRET
+            }));
+        });
+        ensure_requirements(&server, token_1(), TickSize::Tenth);
+
+        let client = Client::new(&server.base_url(), Config::default())?
+            .authentication_builder(&signer)
+            .salt_generator(CounterSalt::starting_at(10))
+            .authenticate()
+            .await?;
+
+        let mut salts = Vec::new();
+        for _ in 0..3 {
+            let signable_order = client
+                .limit_order()
+                .token_id(token_1())
+                .size(Decimal::ONE_HUNDRED)
+                .price(dec!(0.1))
+                .side(Side::Buy)
+                .build()
+                .await?;
+            salts.push(signable_order.order.salt);
+        }
+
+        assert_eq!(salts, [U256::from(10), U256::from(11), U256::from(12)]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn client_with_funder_should_succeed() -> anyhow::Result<()> {
         let server = MockServer::start();
@@ -3176,3 +3217,695 @@ mod market {
         Ok(())
     }
 }
+
+mod offline {
+    use alloy::signers::Signer as _;
+    use alloy::signers::local::LocalSigner;
+    use polymarket_client_sdk::POLYGON;
+    use polymarket_client_sdk::clob::types::MarketParams;
+    use polymarket_client_sdk::error::Validation;
+
+    use super::*;
+    use crate::common::PRIVATE_KEY;
+
+    /// Mocks every market lookup, so tests can assert none of them is made.
+    fn mock_lookups(server: &MockServer) -> Vec<httpmock::Mock<'_>> {
+        ["/neg-risk", "/fee-rate", "/tick-size", "/book"]
+            .into_iter()
+            .map(|path| {
+                server.mock(|when, then| {
+                    when.method(httpmock::Method::GET).path(path);
+                    then.status(StatusCode::INTERNAL_SERVER_ERROR);
+                })
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn limit_order_should_build_and_sign_without_lookups() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+        let lookups = mock_lookups(&server);
+
+        let params = MarketParams::builder()
+            .tick_size(TickSize::Hundredth)
+            .fee_rate_bps(25)
+            .neg_risk(true)
+            .build();
+        let signable_order = client
+            .limit_order()
+            .token_id(token_1())
+            .price(dec!(0.34))
+            .size(dec!(100))
+            .side(Side::Buy)
+            .build_offline(&params)?;
+
+        assert_eq!(signable_order.order.makerAmount, U256::from(34_000_000));
+        assert_eq!(signable_order.order.takerAmount, U256::from(100_000_000));
+        assert_eq!(signable_order.order.feeRateBps, U256::from(25));
+
+        let signed_order = client.sign(&signer, signable_order).await?;
+        assert!(signed_order.neg_risk);
+
+        for lookup in &lookups {
+            lookup.assert_calls(0);
+        }
+
+        // The params are not recorded in the client's cache
+        client.neg_risk(token_1()).await.unwrap_err();
+        lookups[0].assert_calls(1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn limit_order_should_validate_against_params() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let params = MarketParams::builder()
+            .tick_size(TickSize::Tenth)
+            .fee_rate_bps(0)
+            .neg_risk(false)
+            .build();
+        let err = client
+            .limit_order()
+            .token_id(token_1())
+            .price(dec!(0.35))
+            .size(dec!(10))
+            .side(Side::Buy)
+            .build_offline(&params)
+            .unwrap_err();
+
+        assert!(
+            err.downcast_ref::<Validation>()
+                .unwrap()
+                .reason
+                .contains("Minimum tick size 0.1")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn market_order_should_build_without_lookups() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let lookups = mock_lookups(&server);
+
+        let params = MarketParams::builder()
+            .tick_size(TickSize::Hundredth)
+            .fee_rate_bps(0)
+            .neg_risk(false)
+            .build();
+        let signable_order = client
+            .market_order()
+            .token_id(token_1())
+            .amount(Amount::usdc(Decimal::ONE_HUNDRED)?)
+            .price(dec!(0.5))
+            .side(Side::Buy)
+            .build_offline(&params)?;
+
+        assert_eq!(signable_order.order.makerAmount, U256::from(100_000_000));
+        assert_eq!(signable_order.order.takerAmount, U256::from(200_000_000));
+        assert_eq!(signable_order.order_type, OrderType::FAK);
+
+        for lookup in lookups {
+            lookup.assert_calls(0);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn market_order_without_price_should_fail() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let params = MarketParams::builder()
+            .tick_size(TickSize::Hundredth)
+            .fee_rate_bps(0)
+            .neg_risk(false)
+            .build();
+        let err = client
+            .market_order()
+            .token_id(token_1())
+            .amount(Amount::usdc(Decimal::ONE_HUNDRED)?)
+            .side(Side::Buy)
+            .build_offline(&params)
+            .unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<Validation>().unwrap().reason,
+            "Unable to build Order offline due to missing price or order book"
+        );
+
+        Ok(())
+    }
+}
+
+mod held_book {
+    use polymarket_client_sdk::clob::types::MarketParams;
+    use polymarket_client_sdk::clob::types::response::OrderBookSummaryResponse;
+    use polymarket_client_sdk::error::Validation;
+    use polymarket_client_sdk::types::B256;
+
+    use super::*;
+
+    fn level(price: Decimal, size: Decimal) -> OrderSummary {
+        OrderSummary::builder().price(price).size(size).build()
+    }
+
+    /// A book for `token_1` listed the way the API does, best price last.
+    fn summary() -> OrderBookSummaryResponse {
+        OrderBookSummaryResponse::builder()
+            .market(B256::ZERO)
+            .asset_id(token_1())
+            .timestamp(DateTime::<Utc>::UNIX_EPOCH)
+            .bids(vec![
+                level(dec!(0.3), dec!(100)),
+                level(dec!(0.4), dec!(100)),
+            ])
+            .asks(vec![
+                level(dec!(0.7), dec!(100)),
+                level(dec!(0.6), dec!(100)),
+                level(dec!(0.5), dec!(100)),
+            ])
+            .min_order_size(dec!(5))
+            .neg_risk(false)
+            .tick_size(TickSize::Tenth)
+            .build()
+    }
+
+    fn params() -> MarketParams {
+        MarketParams::builder()
+            .tick_size(TickSize::Tenth)
+            .fee_rate_bps(0)
+            .neg_risk(false)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn build_should_price_against_held_book() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        ensure_requirements(&server, token_1(), TickSize::Tenth);
+        let book = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/book");
+            then.status(StatusCode::INTERNAL_SERVER_ERROR);
+        });
+
+        let signable_order = client
+            .market_order()
+            .token_id(token_1())
+            .amount(Amount::shares(dec!(150))?)
+            .side(Side::Buy)
+            .order_book(&summary())
+            .build()
+            .await?;
+
+        // 150 shares walk the 0.5 level into the 0.6 one
+        assert_eq!(signable_order.order.makerAmount, U256::from(90_000_000));
+        assert_eq!(signable_order.order.takerAmount, U256::from(150_000_000));
+        book.assert_calls(0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn price_limit_should_bound_fill_and_kill_price() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let signable_order = client
+            .market_order()
+            .token_id(token_1())
+            .amount(Amount::shares(dec!(150))?)
+            .side(Side::Buy)
+            .order_book(&summary())
+            .price_limit(dec!(0.55))
+            .build_offline(&params())?;
+
+        // Only the 0.5 level is within the limit, so the order is priced there and fills partially
+        assert_eq!(signable_order.order.makerAmount, U256::from(75_000_000));
+        assert_eq!(signable_order.order.takerAmount, U256::from(150_000_000));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn price_limit_should_reject_fill_or_kill_beyond_it() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let err = client
+            .market_order()
+            .token_id(token_1())
+            .amount(Amount::shares(dec!(150))?)
+            .side(Side::Buy)
+            .order_type(OrderType::FOK)
+            .order_book(&summary())
+            .price_limit(dec!(0.55))
+            .build_offline(&params())
+            .unwrap_err();
+
+        assert!(
+            err.downcast_ref::<Validation>()
+                .unwrap()
+                .reason
+                .starts_with("Insufficient liquidity")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn price_limit_should_apply_to_sells() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let err = client
+            .market_order()
+            .token_id(token_1())
+            .amount(Amount::shares(dec!(50))?)
+            .side(Side::Sell)
+            .order_book(&summary())
+            .price_limit(dec!(0.45))
+            .build_offline(&params())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<Validation>().unwrap().reason,
+            format!(
+                "No opposing orders for {} within the price limit 0.45",
+                token_1()
+            )
+        );
+
+        let err = client
+            .market_order()
+            .token_id(token_1())
+            .amount(Amount::shares(dec!(50))?)
+            .side(Side::Sell)
+            .price(dec!(0.3))
+            .price_limit(dec!(0.35))
+            .build_offline(&params())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<Validation>().unwrap().reason,
+            "Price 0.3 is beyond the price limit 0.35"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn book_for_another_token_should_fail() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let err = client
+            .market_order()
+            .token_id(token_2())
+            .amount(Amount::shares(dec!(10))?)
+            .side(Side::Buy)
+            .order_book(&summary())
+            .build_offline(&params())
+            .unwrap_err();
+
+        assert!(
+            err.downcast_ref::<Validation>()
+                .unwrap()
+                .reason
+                .starts_with("Order book is for")
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn build_should_price_against_local_order_book() -> anyhow::Result<()> {
+        use polymarket_client_sdk::clob::ws::LocalOrderBook;
+
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let signable_order = client
+            .market_order()
+            .token_id(token_1())
+            .amount(Amount::shares(dec!(150))?)
+            .side(Side::Sell)
+            .local_order_book(&LocalOrderBook::from_summary(&summary()))
+            .build_offline(&params())?;
+
+        // 150 shares walk the 0.4 bid into the 0.3 one
+        assert_eq!(signable_order.order.makerAmount, U256::from(150_000_000));
+        assert_eq!(signable_order.order.takerAmount, U256::from(45_000_000));
+
+        Ok(())
+    }
+}
+
+mod ladder {
+    use polymarket_client_sdk::clob::order_batch::Ladder;
+    use polymarket_client_sdk::clob::types::MarketParams;
+    use polymarket_client_sdk::error::Validation;
+    use serde_json::json;
+
+    use super::*;
+
+    fn params() -> MarketParams {
+        MarketParams::builder()
+            .tick_size(TickSize::Hundredth)
+            .fee_rate_bps(0)
+            .neg_risk(false)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn build_should_look_up_market_once() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let fee_rate = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/fee-rate");
+            then.status(StatusCode::OK)
+                .json_body(json!({ "base_fee": 10 }));
+        });
+        let tick_size = server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/tick-size");
+            then.status(StatusCode::OK)
+                .json_body(json!({ "minimum_tick_size": "0.01" }));
+        });
+
+        let orders = client
+            .order_ladder()
+            .token_id(token_1())
+            .bids(
+                Ladder::builder()
+                    .start(dec!(0.48))
+                    .levels(3)
+                    .size(dec!(10))
+                    .build(),
+            )
+            .asks(
+                Ladder::builder()
+                    .start(dec!(0.52))
+                    .levels(3)
+                    .size(dec!(10))
+                    .build(),
+            )
+            .build()
+            .await?;
+
+        assert_eq!(orders.len(), 6);
+        assert!(
+            orders
+                .iter()
+                .all(|order| order.order.feeRateBps == U256::from(10))
+        );
+        fee_rate.assert_calls(1);
+        tick_size.assert_calls(1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn build_offline_should_space_and_skew_levels() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let orders = client
+            .order_ladder()
+            .token_id(token_1())
+            .bids(
+                Ladder::builder()
+                    .start(dec!(0.48))
+                    .levels(3)
+                    .size(dec!(10))
+                    .spacing(2)
+                    .skew(dec!(0.5))
+                    .build(),
+            )
+            .asks(
+                Ladder::builder()
+                    .start(dec!(0.52))
+                    .levels(2)
+                    .size(dec!(10))
+                    .build(),
+            )
+            .order_type(OrderType::GTD)
+            .expiration(DateTime::<Utc>::from_timestamp(1_900_000_000, 0).unwrap())
+            .post_only(true)
+            .build_offline(&params())?;
+
+        // Bids at 0.48, 0.46 and 0.44 for 10, 15 and 22.5 shares
+        let bids = orders
+            .iter()
+            .filter(|order| order.order.side == Side::Buy as u8)
+            .map(|order| (order.order.makerAmount, order.order.takerAmount))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            bids,
+            [
+                (U256::from(4_800_000), U256::from(10_000_000)),
+                (U256::from(6_900_000), U256::from(15_000_000)),
+                (U256::from(9_900_000), U256::from(22_500_000)),
+            ]
+        );
+
+        // Asks at 0.52 and 0.53 for 10 shares each
+        let asks = orders
+            .iter()
+            .filter(|order| order.order.side == Side::Sell as u8)
+            .map(|order| (order.order.makerAmount, order.order.takerAmount))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            asks,
+            [
+                (U256::from(10_000_000), U256::from(5_200_000)),
+                (U256::from(10_000_000), U256::from(5_300_000)),
+            ]
+        );
+
+        assert!(orders.iter().all(|order| order.order_type == OrderType::GTD
+            && order.post_only == Some(true)
+            && order.order.expiration == U256::from(1_900_000_000)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn crossing_ladders_should_fail() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let err = client
+            .order_ladder()
+            .token_id(token_1())
+            .bids(
+                Ladder::builder()
+                    .start(dec!(0.5))
+                    .levels(2)
+                    .size(dec!(10))
+                    .build(),
+            )
+            .asks(
+                Ladder::builder()
+                    .start(dec!(0.5))
+                    .levels(2)
+                    .size(dec!(10))
+                    .build(),
+            )
+            .build_offline(&params())
+            .unwrap_err();
+
+        let msg = &err.downcast_ref::<Validation>().unwrap().reason;
+        assert_eq!(msg, "Bid ladder at 0.5 crosses ask ladder at 0.5");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn level_out_of_range_should_fail_whole_ladder() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        // The fourth bid would be at 0.00
+        let err = client
+            .order_ladder()
+            .token_id(token_1())
+            .bids(
+                Ladder::builder()
+                    .start(dec!(0.03))
+                    .levels(4)
+                    .size(dec!(10))
+                    .build(),
+            )
+            .build_offline(&params())
+            .unwrap_err();
+
+        let msg = &err.downcast_ref::<Validation>().unwrap().reason;
+        assert_eq!(
+            msg,
+            "Price 0.00 is too small or too large for the minimum tick size 0.01"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn missing_ladders_should_fail() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+
+        let err = client
+            .order_ladder()
+            .token_id(token_1())
+            .build_offline(&params())
+            .unwrap_err();
+
+        let msg = &err.downcast_ref::<Validation>().unwrap().reason;
+        assert_eq!(
+            msg,
+            "Unable to build Orders due to missing bid and ask ladders"
+        );
+
+        Ok(())
+    }
+}
+
+mod verification {
+    use alloy::signers::Signer as _;
+    use alloy::signers::local::LocalSigner;
+    use polymarket_client_sdk::POLYGON;
+    use polymarket_client_sdk::clob::types::{MarketParams, SignableOrder, SignedOrder};
+    use polymarket_client_sdk::error::Validation;
+
+    use super::*;
+    use crate::common::PRIVATE_KEY;
+
+    fn params(neg_risk: bool) -> MarketParams {
+        MarketParams::builder()
+            .tick_size(TickSize::Hundredth)
+            .fee_rate_bps(0)
+            .neg_risk(neg_risk)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn signed_order_should_verify_against_its_exchange() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        for neg_risk in [false, true] {
+            let signable_order = client
+                .limit_order()
+                .token_id(token_1())
+                .price(dec!(0.34))
+                .size(dec!(100))
+                .side(Side::Buy)
+                .build_offline(&params(neg_risk))?;
+            let hash = signable_order.order_hash(POLYGON, neg_risk)?;
+
+            let signed_order = client.sign(&signer, signable_order).await?;
+
+            assert_eq!(signed_order.neg_risk, neg_risk);
+            assert_eq!(signed_order.order_hash()?, hash);
+            assert_eq!(signed_order.verify()?, signer.address());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn order_should_survive_serialization_between_processes() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        let signable_order = client
+            .limit_order()
+            .token_id(token_1())
+            .price(dec!(0.34))
+            .size(dec!(100))
+            .side(Side::Sell)
+            .post_only(true)
+            .order_type(OrderType::GTD)
+            .expiration(DateTime::<Utc>::from_timestamp(2_000_000_000, 0).unwrap())
+            .build_offline(&params(true))?;
+
+        let received = SignableOrder::from_bytes(&signable_order.to_bytes()?)?;
+        assert_eq!(received, signable_order);
+        let received = SignableOrder::from_json(&signable_order.to_json()?)?;
+        assert_eq!(received, signable_order);
+
+        let signed_order = client.sign(&signer, received).await?;
+        let json = signed_order.to_json()?;
+        let received = SignedOrder::from_json(&json)?;
+
+        assert_eq!(received, signed_order);
+        assert_eq!(
+            SignedOrder::from_bytes(&signed_order.to_bytes()?)?,
+            signed_order
+        );
+        assert_eq!(received.verify()?, signer.address());
+        assert_eq!(
+            serde_json::to_value(&received)?,
+            serde_json::to_value(&signed_order)?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tampered_order_should_fail_verification() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        let signable_order = client
+            .limit_order()
+            .token_id(token_1())
+            .price(dec!(0.34))
+            .size(dec!(100))
+            .side(Side::Buy)
+            .build_offline(&params(false))?;
+        let mut signed_order = client.sign(&signer, signable_order).await?;
+        signed_order.order.makerAmount = U256::from(1);
+
+        let err = signed_order.verify().unwrap_err();
+
+        assert!(
+            err.downcast_ref::<Validation>()
+                .unwrap()
+                .reason
+                .contains("not by its signer")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn order_signed_for_other_exchange_should_fail_verification() -> anyhow::Result<()> {
+        let server = MockServer::start();
+        let client = create_authenticated(&server).await?;
+        let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+
+        let signable_order = client
+            .limit_order()
+            .token_id(token_1())
+            .price(dec!(0.34))
+            .size(dec!(100))
+            .side(Side::Buy)
+            .build_offline(&params(true))?;
+        let mut signed_order = client.sign(&signer, signable_order).await?;
+        signed_order.neg_risk = false;
+
+        signed_order.verify().unwrap_err();
+
+        Ok(())
+    }
+}
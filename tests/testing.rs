@@ -0,0 +1,249 @@
+#![cfg(feature = "testing")]
+#![allow(
+    clippy::unwrap_used,
+    clippy::missing_panics_doc,
+    reason = "Do not need additional syntax for setting up tests"
+)]
+
+mod common;
+
+use std::str::FromStr as _;
+use std::time::Duration;
+
+use alloy::signers::Signer as _;
+use alloy::signers::k256::ecdsa::SigningKey;
+use alloy::signers::local::LocalSigner;
+use futures_util::StreamExt as _;
+use polymarket_client_sdk::POLYGON;
+use polymarket_client_sdk::clob::testing::{MockExchange, MockMarket};
+use polymarket_client_sdk::clob::types::request::{OrdersRequest, TradesRequest};
+use polymarket_client_sdk::clob::types::{OrderStatusType, OrderType, Side, TraderSide};
+use polymarket_client_sdk::clob::ws::Client as WsClient;
+use polymarket_client_sdk::clob::{Client, Config};
+use polymarket_client_sdk::error::Kind;
+use polymarket_client_sdk::types::{B256, Decimal, U256, dec};
+use polymarket_client_sdk::ws::config::Config as WsConfig;
+use tokio::time::timeout;
+
+use crate::common::{PRIVATE_KEY, TestClient};
+
+const CONDITION_ID: B256 = B256::repeat_byte(7);
+
+fn token_id() -> U256 {
+    U256::from(42)
+}
+
+async fn setup() -> anyhow::Result<(MockExchange, TestClient, LocalSigner<SigningKey>)> {
+    let exchange = MockExchange::start().await?;
+    exchange.add_market(
+        MockMarket::builder()
+            .token_id(token_id())
+            .condition_id(CONDITION_ID)
+            .build(),
+    );
+
+    let signer = LocalSigner::from_str(PRIVATE_KEY)?.with_chain_id(Some(POLYGON));
+    let client = Client::new(&exchange.host(), Config::default())?
+        .authentication_builder(&signer)
+        .authenticate()
+        .await?;
+
+    Ok((exchange, client, signer))
+}
+
+#[tokio::test]
+async fn limit_order_should_match_resting_liquidity() -> anyhow::Result<()> {
+    let (exchange, client, signer) = setup().await?;
+    let maker_id = exchange.add_liquidity(token_id(), Side::Sell, dec!(0.55), dec!(100))?;
+
+    let order = client
+        .limit_order()
+        .token_id(token_id())
+        .side(Side::Buy)
+        .price(dec!(0.56))
+        .size(dec!(10))
+        .order_type(OrderType::GTC)
+        .build()
+        .await?;
+    let response = client
+        .post_order(client.sign(&signer, order).await?)
+        .await?;
+
+    assert!(response.success);
+    assert_eq!(response.status, OrderStatusType::Matched);
+    assert_eq!(response.making_amount, dec!(5.5));
+    assert_eq!(response.taking_amount, dec!(10));
+    assert_eq!(response.trade_ids.len(), 1);
+
+    let trades = client.trades(&TradesRequest::default(), None).await?;
+    assert_eq!(trades.data, exchange.trades()?);
+    let trade = &trades.data[0];
+    assert_eq!(trade.taker_order_id, response.order_id);
+    assert_eq!(trade.trader_side, TraderSide::Taker);
+    assert_eq!(trade.price, dec!(0.55));
+    assert_eq!(trade.maker_orders[0].order_id, maker_id);
+
+    let book = exchange.book(token_id()).unwrap();
+    assert_eq!(book.asks[0].size, dec!(90));
+    assert_eq!(
+        client.order(&maker_id).await.unwrap_err().kind(),
+        Kind::Status
+    );
+
+    let orders = exchange.orders()?;
+    assert_eq!(orders.len(), 2);
+    assert_eq!(orders[1].id, response.order_id);
+    assert_eq!(orders[1].size_matched, dec!(10));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn resting_orders_should_be_listed_and_canceled() -> anyhow::Result<()> {
+    let (exchange, client, signer) = setup().await?;
+
+    let order = client
+        .limit_order()
+        .token_id(token_id())
+        .side(Side::Buy)
+        .price(dec!(0.40))
+        .size(dec!(25))
+        .build()
+        .await?;
+    let response = client
+        .post_order(client.sign(&signer, order).await?)
+        .await?;
+    assert_eq!(response.status, OrderStatusType::Live);
+    assert_eq!(response.making_amount, Decimal::ZERO);
+
+    let orders = client.orders(&OrdersRequest::default(), None).await?;
+    assert_eq!(orders.data.len(), 1);
+    assert_eq!(orders.data[0].price, dec!(0.40));
+    assert_eq!(orders.data[0].market, CONDITION_ID);
+    assert_eq!(client.order(&response.order_id).await?, orders.data[0]);
+
+    let canceled = client.cancel_order(&response.order_id).await?;
+    assert_eq!(canceled.canceled, vec![response.order_id.clone()]);
+
+    let canceled = client.cancel_order(&response.order_id).await?;
+    assert!(canceled.not_canceled.contains_key(&response.order_id));
+    assert!(
+        client
+            .orders(&OrdersRequest::default(), None)
+            .await?
+            .data
+            .is_empty()
+    );
+    assert!(exchange.book(token_id()).unwrap().bids.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn fill_or_kill_order_should_be_rejected_without_enough_liquidity() -> anyhow::Result<()> {
+    let (exchange, client, signer) = setup().await?;
+    exchange.add_liquidity(token_id(), Side::Buy, dec!(0.30), dec!(5))?;
+
+    let order = client
+        .limit_order()
+        .token_id(token_id())
+        .side(Side::Sell)
+        .price(dec!(0.30))
+        .size(dec!(10))
+        .order_type(OrderType::FOK)
+        .build()
+        .await?;
+    let err = client
+        .post_order(client.sign(&signer, order).await?)
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.kind(), Kind::Status);
+    assert!(exchange.trades()?.is_empty());
+    assert_eq!(exchange.book(token_id()).unwrap().bids[0].size, dec!(5));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn batch_with_malformed_order_should_be_rejected() -> anyhow::Result<()> {
+    let (exchange, client, signer) = setup().await?;
+
+    let order = client
+        .limit_order()
+        .token_id(token_id())
+        .side(Side::Sell)
+        .price(dec!(0.30))
+        .size(dec!(10))
+        .build()
+        .await?;
+    let signed = client.sign(&signer, order).await?;
+    let body = serde_json::json!([signed, { "order": { "salt": "x" }, "orderType": "GTC" }]);
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/orders", exchange.host()))
+        .header("POLY_API_KEY", client.credentials().key().to_string())
+        .json(&body)
+        .send()
+        .await?;
+
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = response.json().await?;
+    assert!(error["error"].as_str().unwrap().starts_with("invalid"));
+    assert!(exchange.orders()?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn websocket_should_push_book_order_and_trade_events() -> anyhow::Result<()> {
+    let (exchange, client, signer) = setup().await?;
+    exchange.add_liquidity(token_id(), Side::Sell, dec!(0.60), dec!(50))?;
+
+    let ws = WsClient::new(&exchange.ws_endpoint(), WsConfig::default())?
+        .authenticate(client.credentials(), client.address())?;
+    let mut books = Box::pin(ws.subscribe_orderbook(vec![token_id()])?);
+    let mut orders = Box::pin(ws.subscribe_orders(vec![CONDITION_ID])?);
+    let mut trades = Box::pin(ws.subscribe_trades(vec![CONDITION_ID])?);
+
+    let snapshot = timeout(Duration::from_secs(5), books.next())
+        .await?
+        .unwrap()?;
+    assert_eq!(snapshot.asks[0].price, dec!(0.60));
+    assert_eq!(snapshot.asks[0].size, dec!(50));
+
+    // The user channel connects lazily, so give it a moment to subscribe
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let order = client
+        .limit_order()
+        .token_id(token_id())
+        .side(Side::Buy)
+        .price(dec!(0.60))
+        .size(dec!(20))
+        .build()
+        .await?;
+    let response = client
+        .post_order(client.sign(&signer, order).await?)
+        .await?;
+
+    let trade = timeout(Duration::from_secs(5), trades.next())
+        .await?
+        .unwrap()?;
+    assert_eq!(trade.id, response.trade_ids[0]);
+    assert_eq!(trade.size, dec!(20));
+    assert_eq!(trade.trader_side, Some(TraderSide::Taker));
+
+    let order = timeout(Duration::from_secs(5), orders.next())
+        .await?
+        .unwrap()?;
+    assert_eq!(order.id, response.order_id);
+    assert_eq!(order.size_matched, Some(dec!(20)));
+
+    let update = timeout(Duration::from_secs(5), books.next())
+        .await?
+        .unwrap()?;
+    assert_eq!(update.asks[0].size, dec!(30));
+
+    Ok(())
+}
@@ -459,6 +459,236 @@ mod market_channel {
     }
 }
 
+mod local_order_book {
+    use chrono::{TimeZone as _, Utc};
+    use httpmock::MockServer;
+    use polymarket_client_sdk::clob;
+    use polymarket_client_sdk::clob::types::TickSize;
+    use polymarket_client_sdk::clob::types::response::{OrderBookSummaryResponse, OrderSummary};
+    use polymarket_client_sdk::clob::ws::{LocalOrderBook, OrderBookTracker};
+    use reqwest::StatusCode;
+    use rust_decimal_macros::dec;
+    use serde_json::Value;
+    use tokio::sync::watch;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn local_order_books_applies_snapshot_and_deltas() {
+        let mut server = MockWsServer::start().await;
+        let http = MockServer::start();
+
+        let client = Client::new(&server.ws_url("/ws/market"), Config::default()).unwrap();
+        let rest = clob::Client::new(&http.base_url(), clob::Config::default()).unwrap();
+
+        let tracker = client
+            .local_order_books(vec![payloads::asset_id()], rest)
+            .unwrap();
+        let mut rx = tracker.subscribe(payloads::asset_id()).unwrap();
+
+        let _: Option<String> = server.recv_subscription().await;
+
+        server.send(&payloads::book().to_string());
+        timeout(Duration::from_secs(2), rx.changed())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            tracker.best_bid(payloads::asset_id()).unwrap().price,
+            dec!(0.50)
+        );
+        assert_eq!(
+            tracker.best_ask(payloads::asset_id()).unwrap().price,
+            dec!(0.52)
+        );
+
+        let mut change = payloads::price_change_batch(payloads::asset_id());
+        change["price_changes"][0]["price"] = json!("0.51");
+        change["price_changes"][0]["best_bid"] = json!("0.51");
+        change["price_changes"][0]["best_ask"] = json!("0.52");
+        server.send(&change.to_string());
+        timeout(Duration::from_secs(2), rx.changed())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let (bids, asks) = tracker.depth(payloads::asset_id(), 2).unwrap();
+        assert_eq!(bids[0].price, dec!(0.51));
+        assert_eq!(bids[0].size, dec!(200));
+        assert_eq!(bids[1].price, dec!(0.50));
+        assert_eq!(asks.len(), 2);
+
+        let book = tracker.book(payloads::asset_id()).unwrap();
+        assert_eq!(
+            book.hash(),
+            Some("56621a121a47ed9333273e21c83b660cff37ae50")
+        );
+    }
+
+    fn summary(hash: &str) -> Value {
+        json!({
+            "market": payloads::MARKET_STR,
+            "asset_id": payloads::ASSET_ID_STR,
+            "tick_size": "0.01",
+            "min_order_size": "5",
+            "neg_risk": false,
+            "timestamp": "123456790000",
+            "hash": hash,
+            "bids": [{ "price": "0.45", "size": "10" }],
+            "asks": [{ "price": "0.55", "size": "10" }]
+        })
+    }
+
+    async fn tracker_with_resynced_book(
+        server: &mut MockWsServer,
+        http: &MockServer,
+    ) -> (OrderBookTracker, watch::Receiver<Option<LocalOrderBook>>) {
+        let client = Client::new(&server.ws_url("/ws/market"), Config::default()).unwrap();
+        let rest = clob::Client::new(&http.base_url(), clob::Config::default()).unwrap();
+
+        let tracker = client
+            .local_order_books(vec![payloads::asset_id()], rest)
+            .unwrap();
+        let mut rx = tracker.subscribe(payloads::asset_id()).unwrap();
+
+        let _: Option<String> = server.recv_subscription().await;
+
+        // The initial resync provides the market fields the book hash covers
+        wait_for_hash(&mut rx, "resynced").await;
+        server.send(&payloads::book().to_string());
+        wait_for_hash(&mut rx, "0x1234567890abcdef").await;
+
+        (tracker, rx)
+    }
+
+    async fn wait_for_hash(rx: &mut watch::Receiver<Option<LocalOrderBook>>, hash: &str) {
+        timeout(
+            Duration::from_secs(2),
+            rx.wait_for(|book| {
+                book.as_ref()
+                    .is_some_and(|b| !b.is_stale() && b.hash() == Some(hash))
+            }),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn local_order_books_resyncs_when_out_of_sync() {
+        let mut server = MockWsServer::start().await;
+        let http = MockServer::start();
+
+        let mock = http.mock(|when, then| {
+            when.method(httpmock::Method::GET)
+                .path("/book")
+                .query_param("token_id", payloads::asset_id().to_string());
+            then.status(StatusCode::OK)
+                .delay(Duration::from_millis(200))
+                .json_body(summary("resynced"));
+        });
+        let (_tracker, mut rx) = tracker_with_resynced_book(&mut server, &http).await;
+
+        // Server claims a best ask of 1 (empty ask side), which disagrees with the local book
+        server.send(&payloads::price_change_batch(payloads::asset_id()).to_string());
+
+        // The delta that broke the book is not published
+        let stale = timeout(
+            Duration::from_secs(2),
+            rx.wait_for(|book| book.as_ref().is_some_and(LocalOrderBook::is_stale)),
+        )
+        .await
+        .unwrap()
+        .unwrap()
+        .clone()
+        .unwrap();
+        assert_eq!(stale.best_bid().unwrap().size, dec!(15));
+        assert_eq!(stale.best_ask().unwrap().price, dec!(0.52));
+
+        wait_for_hash(&mut rx, "resynced").await;
+        let book = rx.borrow().clone().unwrap();
+
+        // The initial resync and the one for the out-of-sync book, and more if the stream lagged
+        assert!(
+            mock.calls() >= 2,
+            "expected a resync, got {} calls",
+            mock.calls()
+        );
+        assert_eq!(book.best_bid().unwrap().price, dec!(0.45));
+        assert_eq!(book.best_ask().unwrap().price, dec!(0.55));
+        assert_eq!(book.timestamp(), 123_456_790_000);
+    }
+
+    #[tokio::test]
+    async fn local_order_books_resyncs_when_hash_differs_below_top() {
+        let mut server = MockWsServer::start().await;
+        let http = MockServer::start();
+
+        let mock = http.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/book");
+            then.status(StatusCode::OK)
+                .delay(Duration::from_millis(200))
+                .json_body(summary("resynced"));
+        });
+        let (_tracker, mut rx) = tracker_with_resynced_book(&mut server, &http).await;
+
+        let level = |price, size| OrderSummary::builder().price(price).size(size).build();
+        let timestamp = 1_757_908_892_351;
+        let expected = OrderBookSummaryResponse::builder()
+            .market(payloads::MARKET)
+            .asset_id(payloads::asset_id())
+            .timestamp(Utc.timestamp_millis_opt(timestamp).unwrap())
+            .bids(vec![
+                level(dec!(0.48), dec!(30)),
+                level(dec!(0.49), dec!(40)),
+                level(dec!(0.50), dec!(15)),
+            ])
+            .asks(vec![
+                level(dec!(0.54), dec!(10)),
+                level(dec!(0.53), dec!(60)),
+                level(dec!(0.52), dec!(25)),
+            ])
+            .min_order_size(dec!(5))
+            .neg_risk(false)
+            .tick_size(TickSize::Hundredth)
+            .build()
+            .hash()
+            .unwrap();
+
+        let mut change = payloads::price_change_batch(payloads::asset_id());
+        change["price_changes"][0]["price"] = json!("0.49");
+        change["price_changes"][0]["size"] = json!("40");
+        change["price_changes"][0]["best_bid"] = json!("0.50");
+        change["price_changes"][0]["best_ask"] = json!("0.52");
+        change["price_changes"][0]["hash"] = json!(expected);
+        server.send(&change.to_string());
+        wait_for_hash(&mut rx, &expected).await;
+
+        // Same top of book, but the server's book differs below it
+        change["price_changes"][0]["price"] = json!("0.48");
+        change["price_changes"][0]["size"] = json!("35");
+        change["price_changes"][0]["hash"] = json!("server");
+        server.send(&change.to_string());
+
+        timeout(
+            Duration::from_secs(2),
+            rx.wait_for(|book| book.as_ref().is_some_and(LocalOrderBook::is_stale)),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        wait_for_hash(&mut rx, "resynced").await;
+
+        // The initial resync and the one for the out-of-sync book, and more if the stream lagged
+        assert!(
+            mock.calls() >= 2,
+            "expected a resync, got {} calls",
+            mock.calls()
+        );
+    }
+}
+
 mod user_channel {
     use polymarket_client_sdk::auth::Credentials;
     use polymarket_client_sdk::clob::types::Side;
@@ -1038,6 +1268,75 @@ mod reconnection {
     }
 }
 
+mod record_replay {
+    use polymarket_client_sdk::clob::ws::ChannelType;
+    use polymarket_client_sdk::ws::connection::ConnectionState;
+    use polymarket_client_sdk::ws::recording::{Recorder, Replay, ReplaySpeed};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn replay_yields_recorded_messages_without_network() {
+        let path = std::env::temp_dir().join(format!(
+            "polymarket-websocket-{}.ndjson",
+            uuid::Uuid::new_v4()
+        ));
+
+        // Record a live session
+        let mut server = MockWsServer::start().await;
+        let mut config = Config::default();
+        config.recorder = Some(Recorder::create(&path).unwrap());
+        let client = Client::new(&server.ws_url("/ws/market"), config).unwrap();
+
+        let mut stream = Box::pin(
+            client
+                .subscribe_orderbook(vec![payloads::asset_id()])
+                .unwrap(),
+        );
+        let _: Option<String> = server.recv_subscription().await;
+
+        server.send(&payloads::book().to_string());
+        let live = timeout(Duration::from_secs(2), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        drop(stream);
+        drop(client);
+
+        // Replay it against an endpoint that cannot be reached
+        let mut config = Config::default();
+        config.replay = Some(Replay::new(&path).with_speed(ReplaySpeed::Unthrottled));
+        let client = Client::new("ws://replay.invalid", config).unwrap();
+
+        let mut stream = Box::pin(
+            client
+                .subscribe_orderbook(vec![payloads::asset_id()])
+                .unwrap(),
+        );
+        let replayed = timeout(Duration::from_secs(2), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(replayed.asset_id, live.asset_id);
+        assert_eq!(replayed.hash, live.hash);
+        assert_eq!(replayed.bids.len(), live.bids.len());
+        assert_eq!(replayed.asks[0].price, live.asks[0].price);
+
+        // The replayed connection ends after the last frame
+        timeout(Duration::from_secs(2), async {
+            while client.connection_state(ChannelType::Market) != ConnectionState::Disconnected {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+}
+
 mod unsubscribe {
     use super::*;
     use crate::payloads::OTHER_ASSET_ID_STR;